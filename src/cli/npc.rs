@@ -2,13 +2,13 @@
 
 use clap::{crate_authors, crate_version};
 use nispor::{
-    Iface, IfaceConf, IfaceState, IfaceType, Mptcp, NetConf, NetState,
-    NetStateFilter, NetStateIfaceFilter, NetStateRouteFilter,
-    NetStateRouteRuleFilter, NisporError, Route, RouteProtocol, RouteRule,
-    RouteScope,
+    Devlink, EthtoolTimestampingInfo, Iface, IfaceConf, IfaceState, IfaceType,
+    Mptcp, NetConf, NetState, NetStateFilter, NetStateIfaceFilter,
+    NetStateRouteFilter, NetStateRouteRuleFilter, NisporError, Route,
+    RouteProtocol, RouteRule, RouteScope,
 };
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::fmt::Write as _FmtWrite;
 use std::io::{stderr, stdout, Write};
@@ -221,7 +221,7 @@ impl CliIfaceBrief {
                 },
             })
         }
-        ret.sort_by(|a, b| a.index.cmp(&b.index));
+        ret.sort_by_key(|a| a.index);
         ret
     }
 }
@@ -240,6 +240,8 @@ enum CliReply {
     Routes(Vec<Route>),
     RouteRules(Vec<RouteRule>),
     Mptcp(Mptcp),
+    Devlink(Devlink),
+    Timestamping(BTreeMap<String, EthtoolTimestampingInfo>),
 }
 
 #[derive(PartialEq, Eq)]
@@ -276,6 +278,15 @@ macro_rules! npc_print {
                 writeln!(stdout(), "{}", $display_func(&mptcp).unwrap()).ok();
                 process::exit(0);
             }
+            CliReply::Devlink(devlink) => {
+                writeln!(stdout(), "{}", $display_func(&devlink).unwrap()).ok();
+                process::exit(0);
+            }
+            CliReply::Timestamping(ts_infos) => {
+                writeln!(stdout(), "{}", $display_func(&ts_infos).unwrap())
+                    .ok();
+                process::exit(0);
+            }
         }
     };
 }
@@ -454,6 +465,14 @@ fn main() {
         )
         .subcommand(clap::Command::new("rule").about("Show route route"))
         .subcommand(clap::Command::new("mptcp").about("Show mptcp state"))
+        .subcommand(
+            clap::Command::new("devlink")
+                .about("Show devlink devices, parameters and ports"),
+        )
+        .subcommand(clap::Command::new("timestamping").about(
+            "Show interfaces capable of hardware timestamping and \
+                their PTP clocks",
+        ))
         .subcommand(
             clap::Command::new("set")
                 .about("Set network state from file")
@@ -502,6 +521,12 @@ fn main() {
     } else if let Some(m) = matches.subcommand_matches("mptcp") {
         output_format = parse_arg_output_format(m);
         print_result(get_mptcp(), output_format);
+    } else if let Some(m) = matches.subcommand_matches("devlink") {
+        output_format = parse_arg_output_format(m);
+        print_result(get_devlink(), output_format);
+    } else if let Some(m) = matches.subcommand_matches("timestamping") {
+        output_format = parse_arg_output_format(m);
+        print_result(get_timestamping(), output_format);
     } else {
         print_result(get_brief(&matches), output_format);
     }
@@ -606,6 +631,7 @@ fn get_ifaces(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
         iface_filter.include_bridge_vlan = true;
         iface_filter.include_ethtool = true;
         iface_filter.include_mptcp = true;
+        iface_filter.include_devlink = true;
         filter.iface = Some(iface_filter);
 
         let state = NetState::retrieve_with_filter(&filter)?;
@@ -686,6 +712,35 @@ fn get_mptcp() -> Result<CliReply, CliError> {
     Ok(CliReply::Mptcp(state.mptcp.unwrap_or_default()))
 }
 
+fn get_devlink() -> Result<CliReply, CliError> {
+    let mut iface_filter = NetStateIfaceFilter::minimum();
+    iface_filter.include_devlink = true;
+    let mut filter = NetStateFilter::minimum();
+    filter.iface = Some(iface_filter);
+    let state = NetState::retrieve_with_filter(&filter)?;
+    Ok(CliReply::Devlink(state.devlink.unwrap_or_default()))
+}
+
+fn get_timestamping() -> Result<CliReply, CliError> {
+    let mut iface_filter = NetStateIfaceFilter::minimum();
+    iface_filter.include_ethtool = true;
+    let mut filter = NetStateFilter::minimum();
+    filter.iface = Some(iface_filter);
+    let state = NetState::retrieve_with_filter(&filter)?;
+    let mut ts_infos = BTreeMap::new();
+    for iface in state.ifaces.values() {
+        if let Some(ts_info) = iface
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.timestamping.as_ref())
+            .filter(|t| t.is_hardware_capable())
+        {
+            ts_infos.insert(iface.name.clone(), ts_info.clone());
+        }
+    }
+    Ok(CliReply::Timestamping(ts_infos))
+}
+
 fn get_brief(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
     let mut filter = NetStateFilter::minimum();
     let mut iface_filter = NetStateIfaceFilter::minimum();
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Minimum standard base64 (RFC 4648 with padding) support for WireGuard keys.
+
+use crate::NisporError;
+
+const BASE64_CHARS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_PAD: u8 = b'=';
+
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    let mut ret = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n =
+            (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                ret.push(
+                    BASE64_CHARS[(n >> (18 - 6 * i) & 0x3f) as usize].into(),
+                );
+            } else {
+                ret.push(BASE64_PAD.into());
+            }
+        }
+    }
+    ret
+}
+
+pub(crate) fn base64_decode(data: &str) -> Result<Vec<u8>, NisporError> {
+    let data = data.trim().as_bytes();
+    if data.is_empty() || data.len() % 4 != 0 {
+        return Err(invalid_base64());
+    }
+    let chunk_count = data.len() / 4;
+    let mut ret = Vec::with_capacity(chunk_count * 3);
+    for (chunk_index, chunk) in data.chunks(4).enumerate() {
+        let mut n: u32 = 0;
+        let mut pad_count = 0;
+        for c in chunk {
+            let v = if *c == BASE64_PAD && chunk_index == chunk_count - 1 {
+                pad_count += 1;
+                Some(0)
+            } else if pad_count > 0 {
+                None
+            } else {
+                BASE64_CHARS.iter().position(|b| b == c)
+            };
+            n = (n << 6) | v.ok_or_else(invalid_base64)? as u32;
+        }
+        if pad_count > 2 {
+            return Err(invalid_base64());
+        }
+        ret.extend_from_slice(&n.to_be_bytes()[1..4 - pad_count]);
+    }
+    Ok(ret)
+}
+
+// The data might be secret, hence not included in error message.
+fn invalid_base64() -> NisporError {
+    NisporError::invalid_argument("Invalid base64 string".into())
+}
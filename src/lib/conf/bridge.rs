@@ -1,13 +1,70 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use rtnetlink::Handle;
 use serde::{Deserialize, Serialize};
 
-use crate::NisporError;
+use crate::{
+    netlink::{set_bridge_vlan_global_opts, set_bridge_vlan_opts},
+    BridgePortMulticastRouterType, BridgePortStpState, Iface, IfaceConf,
+    NisporError,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgeConf {
+    pub vlan_opts: Option<Vec<BridgeVlanOptsConf>>,
+    pub vlan_global_opts: Option<Vec<BridgeVlanGlobalOptsConf>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgePortConf {
+    pub vlan_opts: Option<Vec<BridgeVlanOptsConf>>,
+}
 
+/// Per-VLAN options of bridge or bridge port. The VLAN should be already
+/// added to the bridge or bridge port.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
-pub struct BridgeConf {}
+pub struct BridgeVlanOptsConf {
+    pub vid: Option<u16>,
+    pub vid_range: Option<(u16, u16)>,
+    pub stp_state: Option<BridgePortStpState>,
+    pub multicast_router: Option<BridgePortMulticastRouterType>,
+    /// Map VLAN to tunnel ID, requires `vlan_tunnel` enabled on bridge port.
+    /// For VLAN range, this is the tunnel ID of the first VLAN, the
+    /// following VLANs are mapped to consecutive tunnel IDs.
+    pub tunnel_id: Option<u32>,
+    /// Remove the existing VLAN to tunnel ID mapping.
+    #[serde(default)]
+    pub remove_tunnel_id: bool,
+    pub multicast_max_groups: Option<u32>,
+    pub neigh_suppress: Option<bool>,
+}
+
+/// Per-VLAN global options of bridge. The VLAN should be already added to
+/// the bridge.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgeVlanGlobalOptsConf {
+    pub vid: Option<u16>,
+    pub vid_range: Option<(u16, u16)>,
+    pub multicast_snooping: Option<bool>,
+    pub multicast_querier: Option<bool>,
+    pub multicast_igmp_version: Option<u8>,
+    pub multicast_mld_version: Option<u8>,
+    pub multicast_last_member_count: Option<u32>,
+    pub multicast_startup_query_count: Option<u32>,
+    pub multicast_last_member_interval: Option<u64>,
+    pub multicast_membership_interval: Option<u64>,
+    pub multicast_querier_interval: Option<u64>,
+    pub multicast_query_interval: Option<u64>,
+    pub multicast_query_response_interval: Option<u64>,
+    pub multicast_startup_query_interval: Option<u64>,
+    pub msti: Option<u16>,
+}
 
 impl BridgeConf {
     pub(crate) async fn create(
@@ -23,3 +80,45 @@ impl BridgeConf {
         }
     }
 }
+
+pub(crate) fn change_bridge_vlan_opts(
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in ifaces {
+        let cur_iface = match cur_ifaces.get(&iface.name) {
+            Some(i) => i,
+            None => continue,
+        };
+        if let Some(br_conf) = iface.bridge.as_ref() {
+            if let Some(opts) = br_conf.vlan_global_opts.as_ref() {
+                set_bridge_vlan_global_opts(cur_iface.index, opts)?;
+            }
+            if let Some(opts) = br_conf.vlan_opts.as_ref() {
+                set_bridge_vlan_opts(
+                    cur_iface.index,
+                    opts,
+                    cur_iface
+                        .bridge
+                        .as_ref()
+                        .and_then(|b| b.vlan_opts.as_deref()),
+                )?;
+            }
+        }
+        if let Some(opts) = iface
+            .bridge_port
+            .as_ref()
+            .and_then(|p| p.vlan_opts.as_ref())
+        {
+            set_bridge_vlan_opts(
+                cur_iface.index,
+                opts,
+                cur_iface
+                    .bridge_port
+                    .as_ref()
+                    .and_then(|p| p.vlan_opts.as_deref()),
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,385 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    mac::mac_str_to_raw,
+    netlink::{
+        devlink_set, gen_nested_nla, parse_as_u32,
+        DEVLINK_ATTR_ESWITCH_ENCAP_MODE, DEVLINK_ATTR_ESWITCH_INLINE_MODE,
+        DEVLINK_ATTR_ESWITCH_MODE, DEVLINK_ATTR_PARAM_NAME,
+        DEVLINK_ATTR_PARAM_TYPE, DEVLINK_ATTR_PARAM_VALUE_CMODE,
+        DEVLINK_ATTR_PARAM_VALUE_DATA, DEVLINK_ATTR_PORT_CONTROLLER_NUMBER,
+        DEVLINK_ATTR_PORT_FLAVOUR, DEVLINK_ATTR_PORT_FUNCTION,
+        DEVLINK_ATTR_PORT_INDEX, DEVLINK_ATTR_PORT_PCI_PF_NUMBER,
+        DEVLINK_ATTR_PORT_PCI_SF_NUMBER, DEVLINK_CMD_ESWITCH_SET,
+        DEVLINK_CMD_PARAM_SET, DEVLINK_CMD_PORT_DEL, DEVLINK_CMD_PORT_NEW,
+        DEVLINK_CMD_PORT_SET, DEVLINK_PORT_FN_ATTR_STATE,
+        DEVLINK_PORT_FUNCTION_ATTR_HW_ADDR,
+    },
+    query::get_devlink,
+    DevlinkDevice, DevlinkEswitchEncapMode, DevlinkEswitchInlineMode,
+    DevlinkParamCmode, DevlinkParamData, DevlinkParamType, DevlinkPort,
+    DevlinkPortFlavour, DevlinkPortFunctionState, NisporError,
+    SriovEswitchMode,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct DevlinkConf {
+    pub devices: Option<Vec<DevlinkDeviceConf>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct DevlinkDeviceConf {
+    pub bus_name: String,
+    pub dev_name: String,
+    pub eswitch_mode: Option<SriovEswitchMode>,
+    pub eswitch_inline_mode: Option<DevlinkEswitchInlineMode>,
+    pub eswitch_encap_mode: Option<DevlinkEswitchEncapMode>,
+    /// Only listed parameters will be changed. The `driverinit` parameters
+    /// only take effect after driver reload.
+    pub params: Option<Vec<DevlinkParamConf>>,
+    /// Only listed subfunctions will be created, changed or removed.
+    pub sfs: Option<Vec<DevlinkSfConf>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct DevlinkParamConf {
+    pub name: String,
+    pub cmode: DevlinkParamCmode,
+    pub value: DevlinkParamData,
+}
+
+/// Subfunction identified by PF number and SF number.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct DevlinkSfConf {
+    pub pf_number: u16,
+    pub sf_number: u32,
+    pub controller_number: Option<u32>,
+    pub hw_addr: Option<String>,
+    pub state: Option<DevlinkPortFunctionState>,
+    #[serde(default)]
+    pub remove: bool,
+}
+
+pub(crate) fn apply_devlink_conf(
+    devlink_conf: &DevlinkConf,
+) -> Result<(), NisporError> {
+    let dev_confs = match devlink_conf.devices.as_deref() {
+        Some(d) if !d.is_empty() => d,
+        _ => return Ok(()),
+    };
+    let cur_devlink = get_devlink()?;
+    for dev_conf in dev_confs {
+        match cur_devlink.devices.iter().find(|d| {
+            d.bus_name == dev_conf.bus_name && d.dev_name == dev_conf.dev_name
+        }) {
+            Some(cur_dev) => dev_conf.apply(cur_dev)?,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Devlink device {}/{} not found",
+                    dev_conf.bus_name, dev_conf.dev_name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+impl DevlinkDeviceConf {
+    fn apply(&self, cur: &DevlinkDevice) -> Result<(), NisporError> {
+        self.apply_eswitch(cur)?;
+        for param_conf in self.params.as_deref().unwrap_or_default() {
+            param_conf.apply(cur)?;
+        }
+        for sf_conf in self.sfs.as_deref().unwrap_or_default() {
+            sf_conf.apply(cur)?;
+        }
+        Ok(())
+    }
+
+    // Kernel changes the mode before inline and encap mode when all defined
+    // in single request.
+    fn apply_eswitch(&self, cur: &DevlinkDevice) -> Result<(), NisporError> {
+        let mut nlas = Vec::new();
+        if let Some(v) = self.eswitch_mode.as_ref() {
+            if cur.eswitch_mode.as_ref() != Some(v) {
+                nlas.push(DefaultNla::new(
+                    DEVLINK_ATTR_ESWITCH_MODE,
+                    u16::from(v.clone()).to_ne_bytes().to_vec(),
+                ));
+            }
+        }
+        if let Some(v) = self.eswitch_inline_mode.as_ref() {
+            if cur.eswitch_inline_mode.as_ref() != Some(v) {
+                nlas.push(DefaultNla::new(
+                    DEVLINK_ATTR_ESWITCH_INLINE_MODE,
+                    vec![u8::from(v.clone())],
+                ));
+            }
+        }
+        if let Some(v) = self.eswitch_encap_mode.as_ref() {
+            if cur.eswitch_encap_mode.as_ref() != Some(v) {
+                nlas.push(DefaultNla::new(
+                    DEVLINK_ATTR_ESWITCH_ENCAP_MODE,
+                    vec![u8::from(v.clone())],
+                ));
+            }
+        }
+        if !nlas.is_empty() {
+            devlink_set(
+                &cur.bus_name,
+                &cur.dev_name,
+                DEVLINK_CMD_ESWITCH_SET,
+                &nlas,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl DevlinkParamConf {
+    fn apply(&self, cur_dev: &DevlinkDevice) -> Result<(), NisporError> {
+        let cur_param = match cur_dev
+            .params
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .find(|p| p.name == self.name)
+        {
+            Some(p) => p,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Devlink device {} does not have parameter {}",
+                    cur_dev.handle(),
+                    self.name
+                )));
+            }
+        };
+        if cur_param
+            .values
+            .iter()
+            .any(|v| v.cmode == self.cmode && v.data == self.value)
+        {
+            return Ok(());
+        }
+
+        let mut nlas = vec![
+            DefaultNla::new(
+                DEVLINK_ATTR_PARAM_NAME,
+                nul_terminated(self.name.as_str()),
+            ),
+            DefaultNla::new(
+                DEVLINK_ATTR_PARAM_TYPE,
+                vec![u8::from(cur_param.param_type)],
+            ),
+            DefaultNla::new(
+                DEVLINK_ATTR_PARAM_VALUE_CMODE,
+                vec![u8::from(self.cmode)],
+            ),
+        ];
+        // Boolean is a flag attribute, false is represented by omitting it.
+        if let Some(data) = self.value_data(cur_param.param_type)? {
+            nlas.push(DefaultNla::new(DEVLINK_ATTR_PARAM_VALUE_DATA, data));
+        }
+        devlink_set(
+            &cur_dev.bus_name,
+            &cur_dev.dev_name,
+            DEVLINK_CMD_PARAM_SET,
+            &nlas,
+        )?;
+        Ok(())
+    }
+
+    fn value_data(
+        &self,
+        param_type: DevlinkParamType,
+    ) -> Result<Option<Vec<u8>>, NisporError> {
+        let out_of_range = || {
+            NisporError::invalid_argument(format!(
+                "Value {:?} is out of range for devlink parameter {} of \
+                type {:?}",
+                self.value, self.name, param_type
+            ))
+        };
+        Ok(match (param_type, &self.value) {
+            (DevlinkParamType::Bool, DevlinkParamData::Bool(v)) => {
+                if *v {
+                    Some(Vec::new())
+                } else {
+                    None
+                }
+            }
+            (DevlinkParamType::U8, DevlinkParamData::Number(v)) => Some(
+                u8::try_from(*v)
+                    .map_err(|_| out_of_range())?
+                    .to_ne_bytes()
+                    .to_vec(),
+            ),
+            (DevlinkParamType::U16, DevlinkParamData::Number(v)) => Some(
+                u16::try_from(*v)
+                    .map_err(|_| out_of_range())?
+                    .to_ne_bytes()
+                    .to_vec(),
+            ),
+            (DevlinkParamType::U32, DevlinkParamData::Number(v)) => Some(
+                u32::try_from(*v)
+                    .map_err(|_| out_of_range())?
+                    .to_ne_bytes()
+                    .to_vec(),
+            ),
+            (DevlinkParamType::U64, DevlinkParamData::Number(v)) => {
+                Some(v.to_ne_bytes().to_vec())
+            }
+            (DevlinkParamType::String, DevlinkParamData::String(v)) => {
+                Some(nul_terminated(v))
+            }
+            _ => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Value {:?} does not match devlink parameter {} of \
+                    type {:?}",
+                    self.value, self.name, param_type
+                )));
+            }
+        })
+    }
+}
+
+impl DevlinkSfConf {
+    fn is_match(&self, port: &DevlinkPort) -> bool {
+        port.flavour == DevlinkPortFlavour::Pcisf
+            && port.pf_number == Some(self.pf_number)
+            && port.sf_number == Some(self.sf_number)
+            && (self.controller_number.is_none()
+                || port.controller_number == self.controller_number)
+    }
+
+    fn apply(&self, cur_dev: &DevlinkDevice) -> Result<(), NisporError> {
+        let cur_port = cur_dev.ports.iter().find(|p| self.is_match(p));
+        if self.remove {
+            if let Some(cur_port) = cur_port {
+                devlink_set(
+                    &cur_dev.bus_name,
+                    &cur_dev.dev_name,
+                    DEVLINK_CMD_PORT_DEL,
+                    &[DefaultNla::new(
+                        DEVLINK_ATTR_PORT_INDEX,
+                        cur_port.index.to_ne_bytes().to_vec(),
+                    )],
+                )?;
+            }
+            return Ok(());
+        }
+
+        let port_index = match cur_port {
+            Some(p) => p.index,
+            None => self.create(cur_dev)?,
+        };
+
+        // The hardware address can only be changed when SF is inactive,
+        // hence set it before activating.
+        if let Some(hw_addr) = self.hw_addr.as_ref() {
+            if cur_port
+                .and_then(|p| p.function_hw_addr.as_ref())
+                .map(|a| a.to_lowercase())
+                != Some(hw_addr.to_lowercase())
+            {
+                set_port_function(
+                    cur_dev,
+                    port_index,
+                    DefaultNla::new(
+                        DEVLINK_PORT_FUNCTION_ATTR_HW_ADDR,
+                        mac_str_to_raw(hw_addr)?,
+                    ),
+                )?;
+            }
+        }
+        if let Some(state) = self.state.as_ref() {
+            if cur_port.and_then(|p| p.function_state.as_ref()) != Some(state) {
+                set_port_function(
+                    cur_dev,
+                    port_index,
+                    DefaultNla::new(
+                        DEVLINK_PORT_FN_ATTR_STATE,
+                        vec![u8::from(state.clone())],
+                    ),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    // Return the port index of newly created SF.
+    fn create(&self, cur_dev: &DevlinkDevice) -> Result<u32, NisporError> {
+        let mut nlas = vec![
+            DefaultNla::new(
+                DEVLINK_ATTR_PORT_FLAVOUR,
+                u16::from(DevlinkPortFlavour::Pcisf).to_ne_bytes().to_vec(),
+            ),
+            DefaultNla::new(
+                DEVLINK_ATTR_PORT_PCI_PF_NUMBER,
+                self.pf_number.to_ne_bytes().to_vec(),
+            ),
+            DefaultNla::new(
+                DEVLINK_ATTR_PORT_PCI_SF_NUMBER,
+                self.sf_number.to_ne_bytes().to_vec(),
+            ),
+        ];
+        if let Some(v) = self.controller_number {
+            nlas.push(DefaultNla::new(
+                DEVLINK_ATTR_PORT_CONTROLLER_NUMBER,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        for reply in devlink_set(
+            &cur_dev.bus_name,
+            &cur_dev.dev_name,
+            DEVLINK_CMD_PORT_NEW,
+            &nlas,
+        )? {
+            for nla in NlasIterator::new(reply.as_slice()).flatten() {
+                if nla.kind() == DEVLINK_ATTR_PORT_INDEX {
+                    return parse_as_u32(nla.value());
+                }
+            }
+        }
+        Err(NisporError::bug(format!(
+            "Kernel does not reply the port index of newly created SF \
+            {} on PF {} of devlink device {}",
+            self.sf_number,
+            self.pf_number,
+            cur_dev.handle()
+        )))
+    }
+}
+
+fn set_port_function(
+    cur_dev: &DevlinkDevice,
+    port_index: u32,
+    nla: DefaultNla,
+) -> Result<(), NisporError> {
+    devlink_set(
+        &cur_dev.bus_name,
+        &cur_dev.dev_name,
+        DEVLINK_CMD_PORT_SET,
+        &[
+            DefaultNla::new(
+                DEVLINK_ATTR_PORT_INDEX,
+                port_index.to_ne_bytes().to_vec(),
+            ),
+            gen_nested_nla(DEVLINK_ATTR_PORT_FUNCTION, &[nla]),
+        ],
+    )?;
+    Ok(())
+}
+
+fn nul_terminated(s: &str) -> Vec<u8> {
+    let mut ret = s.as_bytes().to_vec();
+    ret.push(0);
+    ret
+}
@@ -0,0 +1,617 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_utils::nla::DefaultNla;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netlink::{
+        ethtool_set, gen_ethtool_bitset_nla, ETHTOOL_A_COALESCE_PKT_RATE_HIGH,
+        ETHTOOL_A_COALESCE_PKT_RATE_LOW,
+        ETHTOOL_A_COALESCE_RATE_SAMPLE_INTERVAL,
+        ETHTOOL_A_COALESCE_RX_MAX_FRAMES,
+        ETHTOOL_A_COALESCE_RX_MAX_FRAMES_HIGH,
+        ETHTOOL_A_COALESCE_RX_MAX_FRAMES_IRQ,
+        ETHTOOL_A_COALESCE_RX_MAX_FRAMES_LOW, ETHTOOL_A_COALESCE_RX_USECS,
+        ETHTOOL_A_COALESCE_RX_USECS_HIGH, ETHTOOL_A_COALESCE_RX_USECS_IRQ,
+        ETHTOOL_A_COALESCE_RX_USECS_LOW, ETHTOOL_A_COALESCE_STATS_BLOCK_USECS,
+        ETHTOOL_A_COALESCE_TX_MAX_FRAMES,
+        ETHTOOL_A_COALESCE_TX_MAX_FRAMES_HIGH,
+        ETHTOOL_A_COALESCE_TX_MAX_FRAMES_IRQ,
+        ETHTOOL_A_COALESCE_TX_MAX_FRAMES_LOW, ETHTOOL_A_COALESCE_TX_USECS,
+        ETHTOOL_A_COALESCE_TX_USECS_HIGH, ETHTOOL_A_COALESCE_TX_USECS_IRQ,
+        ETHTOOL_A_COALESCE_TX_USECS_LOW, ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX,
+        ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX, ETHTOOL_A_FEATURES_WANTED,
+        ETHTOOL_A_LINKMODES_AUTONEG, ETHTOOL_A_LINKMODES_DUPLEX,
+        ETHTOOL_A_LINKMODES_OURS, ETHTOOL_A_LINKMODES_SPEED,
+        ETHTOOL_A_PAUSE_AUTONEG, ETHTOOL_A_PAUSE_RX, ETHTOOL_A_PAUSE_TX,
+        ETHTOOL_A_PRIVFLAGS_FLAGS, ETHTOOL_A_RINGS_RX,
+        ETHTOOL_A_RINGS_RX_JUMBO, ETHTOOL_A_RINGS_RX_MINI, ETHTOOL_A_RINGS_TX,
+        ETHTOOL_MSG_COALESCE_SET, ETHTOOL_MSG_FEATURES_SET,
+        ETHTOOL_MSG_LINKMODES_SET, ETHTOOL_MSG_PAUSE_SET,
+        ETHTOOL_MSG_PRIVFLAGS_SET, ETHTOOL_MSG_RINGS_SET,
+    },
+    query::{
+        rss_flow_type_from_name, rss_hash_field_names_to_bits,
+        set_rss_hash_fields, set_rss_indirection_table,
+    },
+    EthtoolInfo, EthtoolLinkModeDuplex, Iface, NisporError,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolConf {
+    pub pause: Option<EthtoolPauseConf>,
+    /// Only features listed in `changeable` of `EthtoolFeatureInfo` can be
+    /// changed.
+    pub features: Option<HashMap<String, bool>>,
+    pub coalesce: Option<EthtoolCoalesceConf>,
+    pub ring: Option<EthtoolRingConf>,
+    pub link_mode: Option<EthtoolLinkModeConf>,
+    pub rss: Option<EthtoolRssConf>,
+    /// Only private flags listed in `private_flags` of `EthtoolInfo` can be
+    /// changed.
+    pub private_flags: Option<HashMap<String, bool>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolPauseConf {
+    pub rx: Option<bool>,
+    pub tx: Option<bool>,
+    pub auto_negotiate: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolCoalesceConf {
+    pub pkt_rate_high: Option<u32>,
+    pub pkt_rate_low: Option<u32>,
+    pub rate_sample_interval: Option<u32>,
+    pub rx_max_frames: Option<u32>,
+    pub rx_max_frames_high: Option<u32>,
+    pub rx_max_frames_irq: Option<u32>,
+    pub rx_max_frames_low: Option<u32>,
+    pub rx_usecs: Option<u32>,
+    pub rx_usecs_high: Option<u32>,
+    pub rx_usecs_irq: Option<u32>,
+    pub rx_usecs_low: Option<u32>,
+    pub stats_block_usecs: Option<u32>,
+    pub tx_max_frames: Option<u32>,
+    pub tx_max_frames_high: Option<u32>,
+    pub tx_max_frames_irq: Option<u32>,
+    pub tx_max_frames_low: Option<u32>,
+    pub tx_usecs: Option<u32>,
+    pub tx_usecs_high: Option<u32>,
+    pub tx_usecs_irq: Option<u32>,
+    pub tx_usecs_low: Option<u32>,
+    pub use_adaptive_rx: Option<bool>,
+    pub use_adaptive_tx: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolRingConf {
+    pub rx: Option<u32>,
+    pub rx_jumbo: Option<u32>,
+    pub rx_mini: Option<u32>,
+    pub tx: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolLinkModeConf {
+    pub auto_negotiate: Option<bool>,
+    /// Advertised link modes, e.g. `1000baseT/Full`. Link modes not listed
+    /// will not be advertised.
+    pub ours: Option<Vec<String>>,
+    pub speed: Option<u32>,
+    pub duplex: Option<EthtoolLinkModeDuplex>,
+}
+
+/// RSS configuration of the default RSS context.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolRssConf {
+    /// Spread the indirection table equally to the first N receive queues.
+    /// Cannot be used with `indirection_table`.
+    pub equal: Option<u32>,
+    /// Size should be identical to current indirection table size.
+    pub indirection_table: Option<Vec<u32>>,
+    /// The packet fields used for hashing indexed by flow type, for example
+    /// `tcp4: [ip_src, ip_dst, l4_bytes_0_1, l4_bytes_2_3]`.
+    pub hash_fields: Option<HashMap<String, Vec<String>>>,
+}
+
+impl EthtoolConf {
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = cur_iface.ethtool.clone().unwrap_or_default();
+        if let Some(features) = self.features.as_ref() {
+            change_features(&cur_iface.name, features, &cur_info)?;
+        }
+        if let Some(private_flags) = self.private_flags.as_ref() {
+            change_private_flags(&cur_iface.name, private_flags, &cur_info)?;
+        }
+        if let Some(ring) = self.ring.as_ref() {
+            ring.change(&cur_iface.name, &cur_info)?;
+        }
+        if let Some(coalesce) = self.coalesce.as_ref() {
+            coalesce.change(&cur_iface.name, &cur_info)?;
+        }
+        if let Some(pause) = self.pause.as_ref() {
+            pause.change(&cur_iface.name, &cur_info)?;
+        }
+        if let Some(link_mode) = self.link_mode.as_ref() {
+            link_mode.change(&cur_iface.name, &cur_info)?;
+        }
+        if let Some(rss) = self.rss.as_ref() {
+            rss.change(&cur_iface.name, &cur_info)?;
+        }
+        Ok(())
+    }
+}
+
+fn change_features(
+    iface_name: &str,
+    features: &HashMap<String, bool>,
+    cur_info: &EthtoolInfo,
+) -> Result<(), NisporError> {
+    let cur_features = match cur_info.features.as_ref() {
+        Some(f) => f,
+        None => {
+            return Err(NisporError::invalid_argument(format!(
+                "Interface {iface_name} does not support ethtool features"
+            )));
+        }
+    };
+    let mut changed_features = Vec::new();
+    for (name, value) in features {
+        if let Some(cur_value) = cur_features.changeable.get(name) {
+            if cur_value != value {
+                changed_features.push((name.as_str(), *value));
+            }
+        } else if let Some(cur_value) = cur_features.fixed.get(name) {
+            if cur_value != value {
+                return Err(NisporError::invalid_argument(format!(
+                    "Ethtool feature {name} of interface {iface_name} is \
+                    fixed to {cur_value}"
+                )));
+            }
+        } else {
+            return Err(NisporError::invalid_argument(format!(
+                "Ethtool feature {name} is not supported by interface \
+                {iface_name}"
+            )));
+        }
+    }
+    if !changed_features.is_empty() {
+        changed_features.sort_unstable();
+        ethtool_set(
+            iface_name,
+            ETHTOOL_MSG_FEATURES_SET,
+            &[gen_ethtool_bitset_nla(
+                ETHTOOL_A_FEATURES_WANTED,
+                &changed_features,
+                false,
+            )],
+        )?;
+    }
+    Ok(())
+}
+
+fn change_private_flags(
+    iface_name: &str,
+    private_flags: &HashMap<String, bool>,
+    cur_info: &EthtoolInfo,
+) -> Result<(), NisporError> {
+    let cur_flags = match cur_info.private_flags.as_ref() {
+        Some(f) => f,
+        None => {
+            return Err(NisporError::invalid_argument(format!(
+                "Interface {iface_name} does not support ethtool private flags"
+            )));
+        }
+    };
+    let mut changed_flags = Vec::new();
+    for (name, value) in private_flags {
+        match cur_flags.get(name) {
+            Some(cur_value) => {
+                if cur_value != value {
+                    changed_flags.push((name.as_str(), *value));
+                }
+            }
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Ethtool private flag {name} is not supported by \
+                    interface {iface_name}"
+                )));
+            }
+        }
+    }
+    if !changed_flags.is_empty() {
+        changed_flags.sort_unstable();
+        ethtool_set(
+            iface_name,
+            ETHTOOL_MSG_PRIVFLAGS_SET,
+            &[gen_ethtool_bitset_nla(
+                ETHTOOL_A_PRIVFLAGS_FLAGS,
+                &changed_flags,
+                false,
+            )],
+        )?;
+    }
+    Ok(())
+}
+
+impl EthtoolRingConf {
+    fn change(
+        &self,
+        iface_name: &str,
+        cur_info: &EthtoolInfo,
+    ) -> Result<(), NisporError> {
+        let cur_ring = match cur_info.ring.as_ref() {
+            Some(r) => r,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {iface_name} does not support ethtool ring"
+                )));
+            }
+        };
+        let mut nlas = Vec::new();
+        for (prop, kind, des, cur, max) in [
+            (
+                "rx",
+                ETHTOOL_A_RINGS_RX,
+                self.rx,
+                cur_ring.rx,
+                cur_ring.rx_max,
+            ),
+            (
+                "rx_jumbo",
+                ETHTOOL_A_RINGS_RX_JUMBO,
+                self.rx_jumbo,
+                cur_ring.rx_jumbo,
+                cur_ring.rx_jumbo_max,
+            ),
+            (
+                "rx_mini",
+                ETHTOOL_A_RINGS_RX_MINI,
+                self.rx_mini,
+                cur_ring.rx_mini,
+                cur_ring.rx_mini_max,
+            ),
+            (
+                "tx",
+                ETHTOOL_A_RINGS_TX,
+                self.tx,
+                cur_ring.tx,
+                cur_ring.tx_max,
+            ),
+        ] {
+            if let Some(des) = des {
+                if Some(des) == cur {
+                    continue;
+                }
+                match max {
+                    Some(max) if des <= max => (),
+                    Some(max) => {
+                        return Err(NisporError::invalid_argument(format!(
+                            "Ethtool ring {prop} {des} of interface \
+                            {iface_name} exceeds the maximum {max}"
+                        )));
+                    }
+                    None => {
+                        return Err(NisporError::invalid_argument(format!(
+                            "Ethtool ring {prop} is not supported by \
+                            interface {iface_name}"
+                        )));
+                    }
+                }
+                nlas.push(DefaultNla::new(kind, des.to_ne_bytes().to_vec()));
+            }
+        }
+        if !nlas.is_empty() {
+            ethtool_set(iface_name, ETHTOOL_MSG_RINGS_SET, &nlas)?;
+        }
+        Ok(())
+    }
+}
+
+impl EthtoolCoalesceConf {
+    fn change(
+        &self,
+        iface_name: &str,
+        cur_info: &EthtoolInfo,
+    ) -> Result<(), NisporError> {
+        let cur = cur_info.coalesce.clone().unwrap_or_default();
+        let mut nlas = Vec::new();
+        for (kind, des, cur) in [
+            (ETHTOOL_A_COALESCE_RX_USECS, self.rx_usecs, cur.rx_usecs),
+            (
+                ETHTOOL_A_COALESCE_RX_MAX_FRAMES,
+                self.rx_max_frames,
+                cur.rx_max_frames,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_USECS_IRQ,
+                self.rx_usecs_irq,
+                cur.rx_usecs_irq,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_MAX_FRAMES_IRQ,
+                self.rx_max_frames_irq,
+                cur.rx_max_frames_irq,
+            ),
+            (ETHTOOL_A_COALESCE_TX_USECS, self.tx_usecs, cur.tx_usecs),
+            (
+                ETHTOOL_A_COALESCE_TX_MAX_FRAMES,
+                self.tx_max_frames,
+                cur.tx_max_frames,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_USECS_IRQ,
+                self.tx_usecs_irq,
+                cur.tx_usecs_irq,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_MAX_FRAMES_IRQ,
+                self.tx_max_frames_irq,
+                cur.tx_max_frames_irq,
+            ),
+            (
+                ETHTOOL_A_COALESCE_STATS_BLOCK_USECS,
+                self.stats_block_usecs,
+                cur.stats_block_usecs,
+            ),
+            (
+                ETHTOOL_A_COALESCE_PKT_RATE_LOW,
+                self.pkt_rate_low,
+                cur.pkt_rate_low,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_USECS_LOW,
+                self.rx_usecs_low,
+                cur.rx_usecs_low,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_MAX_FRAMES_LOW,
+                self.rx_max_frames_low,
+                cur.rx_max_frames_low,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_USECS_LOW,
+                self.tx_usecs_low,
+                cur.tx_usecs_low,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_MAX_FRAMES_LOW,
+                self.tx_max_frames_low,
+                cur.tx_max_frames_low,
+            ),
+            (
+                ETHTOOL_A_COALESCE_PKT_RATE_HIGH,
+                self.pkt_rate_high,
+                cur.pkt_rate_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_USECS_HIGH,
+                self.rx_usecs_high,
+                cur.rx_usecs_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_MAX_FRAMES_HIGH,
+                self.rx_max_frames_high,
+                cur.rx_max_frames_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_USECS_HIGH,
+                self.tx_usecs_high,
+                cur.tx_usecs_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_MAX_FRAMES_HIGH,
+                self.tx_max_frames_high,
+                cur.tx_max_frames_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RATE_SAMPLE_INTERVAL,
+                self.rate_sample_interval,
+                cur.rate_sample_interval,
+            ),
+        ] {
+            if let Some(v) = des {
+                if Some(v) != cur {
+                    nlas.push(DefaultNla::new(kind, v.to_ne_bytes().to_vec()));
+                }
+            }
+        }
+        for (kind, des, cur) in [
+            (
+                ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX,
+                self.use_adaptive_rx,
+                cur.use_adaptive_rx,
+            ),
+            (
+                ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX,
+                self.use_adaptive_tx,
+                cur.use_adaptive_tx,
+            ),
+        ] {
+            if let Some(v) = des {
+                if Some(v) != cur {
+                    nlas.push(DefaultNla::new(kind, vec![v.into()]));
+                }
+            }
+        }
+        if !nlas.is_empty() {
+            ethtool_set(iface_name, ETHTOOL_MSG_COALESCE_SET, &nlas)?;
+        }
+        Ok(())
+    }
+}
+
+impl EthtoolPauseConf {
+    fn change(
+        &self,
+        iface_name: &str,
+        cur_info: &EthtoolInfo,
+    ) -> Result<(), NisporError> {
+        let cur = cur_info.pause.as_ref();
+        let mut nlas = Vec::new();
+        for (kind, des, cur) in [
+            (
+                ETHTOOL_A_PAUSE_AUTONEG,
+                self.auto_negotiate,
+                cur.map(|p| p.auto_negotiate),
+            ),
+            (ETHTOOL_A_PAUSE_RX, self.rx, cur.map(|p| p.rx)),
+            (ETHTOOL_A_PAUSE_TX, self.tx, cur.map(|p| p.tx)),
+        ] {
+            if let Some(v) = des {
+                if Some(v) != cur {
+                    nlas.push(DefaultNla::new(kind, vec![v.into()]));
+                }
+            }
+        }
+        if !nlas.is_empty() {
+            ethtool_set(iface_name, ETHTOOL_MSG_PAUSE_SET, &nlas)?;
+        }
+        Ok(())
+    }
+}
+
+impl EthtoolLinkModeConf {
+    fn change(
+        &self,
+        iface_name: &str,
+        cur_info: &EthtoolInfo,
+    ) -> Result<(), NisporError> {
+        let cur = cur_info.link_mode.as_ref();
+        let mut nlas = Vec::new();
+        if let Some(v) = self.auto_negotiate {
+            if Some(v) != cur.map(|l| l.auto_negotiate) {
+                nlas.push(DefaultNla::new(
+                    ETHTOOL_A_LINKMODES_AUTONEG,
+                    vec![v.into()],
+                ));
+            }
+        }
+        if let Some(ours) = self.ours.as_ref() {
+            let mut des_modes: Vec<&str> =
+                ours.iter().map(String::as_str).collect();
+            des_modes.sort_unstable();
+            des_modes.dedup();
+            let mut cur_modes: Vec<&str> = cur
+                .map(|l| l.ours.iter().map(String::as_str).collect())
+                .unwrap_or_default();
+            cur_modes.sort_unstable();
+            if des_modes != cur_modes {
+                let bits: Vec<(&str, bool)> =
+                    des_modes.iter().map(|m| (*m, true)).collect();
+                nlas.push(gen_ethtool_bitset_nla(
+                    ETHTOOL_A_LINKMODES_OURS,
+                    &bits,
+                    true,
+                ));
+            }
+        }
+        if let Some(v) = self.speed {
+            if Some(v) != cur.map(|l| l.speed) {
+                nlas.push(DefaultNla::new(
+                    ETHTOOL_A_LINKMODES_SPEED,
+                    v.to_ne_bytes().to_vec(),
+                ));
+            }
+        }
+        if let Some(v) = self.duplex.as_ref() {
+            if Some(v) != cur.map(|l| &l.duplex) {
+                nlas.push(DefaultNla::new(
+                    ETHTOOL_A_LINKMODES_DUPLEX,
+                    vec![v.into()],
+                ));
+            }
+        }
+        if !nlas.is_empty() {
+            ethtool_set(iface_name, ETHTOOL_MSG_LINKMODES_SET, &nlas)?;
+        }
+        Ok(())
+    }
+}
+
+impl EthtoolRssConf {
+    fn change(
+        &self,
+        iface_name: &str,
+        cur_info: &EthtoolInfo,
+    ) -> Result<(), NisporError> {
+        let cur_rss = match cur_info
+            .rss
+            .as_ref()
+            .and_then(|r| r.iter().find(|i| i.context == 0))
+        {
+            Some(r) => r,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {iface_name} does not support ethtool RSS"
+                )));
+            }
+        };
+        let cur_table = &cur_rss.indirection_table;
+
+        let des_table = match (self.equal, self.indirection_table.as_ref()) {
+            (Some(_), Some(_)) => {
+                return Err(NisporError::invalid_argument(
+                    "The RSS equal and indirection_table cannot be used \
+                    together"
+                        .into(),
+                ));
+            }
+            (Some(0), None) => {
+                return Err(NisporError::invalid_argument(
+                    "The RSS equal should be bigger than 0".into(),
+                ));
+            }
+            (Some(n), None) => {
+                Some((0..cur_table.len() as u32).map(|i| i % n).collect())
+            }
+            (None, Some(table)) => {
+                if table.len() != cur_table.len() {
+                    return Err(NisporError::invalid_argument(format!(
+                        "The RSS indirection table size of interface \
+                        {iface_name} is {}, but {} entries defined",
+                        cur_table.len(),
+                        table.len()
+                    )));
+                }
+                Some(table.clone())
+            }
+            (None, None) => None,
+        };
+        if let Some(des_table) = des_table {
+            if &des_table != cur_table {
+                set_rss_indirection_table(iface_name, &des_table)?;
+            }
+        }
+
+        if let Some(hash_fields) = self.hash_fields.as_ref() {
+            let mut flows: Vec<&String> = hash_fields.keys().collect();
+            flows.sort_unstable();
+            for flow in flows {
+                let flow_type = rss_flow_type_from_name(flow)?;
+                let des_bits =
+                    rss_hash_field_names_to_bits(&hash_fields[flow])?;
+                let cur_bits = cur_rss
+                    .hash_fields
+                    .as_ref()
+                    .and_then(|f| f.get(flow.as_str()))
+                    .map(|f| rss_hash_field_names_to_bits(f))
+                    .transpose()?;
+                if cur_bits != Some(des_bits) {
+                    set_rss_hash_fields(iface_name, flow_type, des_bits)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use netlink_packet_route::link::InfoKind;
+use netlink_packet_utils::nla::DefaultNla;
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    iface::{change_iface_link_info, create_iface_with_link_info, is_changed},
+    ip_tunnel::to_info_data,
+};
+use crate::{
+    netlink::{
+        IFLA_GENEVE_COLLECT_METADATA, IFLA_GENEVE_DF, IFLA_GENEVE_ID,
+        IFLA_GENEVE_INNER_PROTO_INHERIT, IFLA_GENEVE_LABEL, IFLA_GENEVE_PORT,
+        IFLA_GENEVE_REMOTE, IFLA_GENEVE_REMOTE6, IFLA_GENEVE_TOS,
+        IFLA_GENEVE_TTL, IFLA_GENEVE_TTL_INHERIT, IFLA_GENEVE_UDP_CSUM,
+        IFLA_GENEVE_UDP_ZERO_CSUM6_RX, IFLA_GENEVE_UDP_ZERO_CSUM6_TX,
+    },
+    GeneveDf, GeneveInfo, Iface, NisporError,
+};
+
+const GENEVE_KIND: &str = "geneve";
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct GeneveConf {
+    /// Cannot be changed after creation.
+    pub vni: Option<u32>,
+    pub remote: Option<String>,
+    pub ttl: Option<u8>,
+    pub ttl_inherit: Option<bool>,
+    pub tos: Option<u8>,
+    pub df: Option<GeneveDf>,
+    /// Cannot be changed after creation.
+    pub dst_port: Option<u16>,
+    /// Cannot be changed after creation.
+    pub collect_metadata: Option<bool>,
+    /// Cannot be changed after creation.
+    pub udp_check_sum: Option<bool>,
+    /// Cannot be changed after creation.
+    pub udp6_zero_check_sum_tx: Option<bool>,
+    /// Cannot be changed after creation.
+    pub udp6_zero_check_sum_rx: Option<bool>,
+    pub label: Option<u32>,
+    /// Cannot be changed after creation.
+    pub inner_proto_inherit: Option<bool>,
+}
+
+impl From<&GeneveInfo> for GeneveConf {
+    fn from(info: &GeneveInfo) -> Self {
+        Self {
+            vni: Some(info.vni),
+            remote: info.remote.clone(),
+            ttl: Some(info.ttl),
+            ttl_inherit: Some(info.ttl_inherit),
+            tos: Some(info.tos),
+            df: Some(info.df.clone()),
+            dst_port: Some(info.dst_port),
+            collect_metadata: Some(info.collect_metadata),
+            udp_check_sum: Some(info.udp_check_sum),
+            udp6_zero_check_sum_tx: Some(info.udp6_zero_check_sum_tx),
+            udp6_zero_check_sum_rx: Some(info.udp6_zero_check_sum_rx),
+            label: Some(info.label),
+            inner_proto_inherit: Some(info.inner_proto_inherit),
+        }
+    }
+}
+
+impl GeneveConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+    ) -> Result<(), NisporError> {
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::Other(GENEVE_KIND.to_string()),
+            Some(to_info_data(&self.to_nlas()?)),
+            None,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new geneve '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+
+    // Kernel GENEVE driver starts from the existing configuration when
+    // changing, hence only changed properties are sent.
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_conf = match cur_iface.geneve.as_ref() {
+            Some(i) => GeneveConf::from(i),
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not GENEVE",
+                    cur_iface.name
+                )));
+            }
+        };
+        let changed_conf = self.gen_changed_conf(&cur_iface.name, &cur_conf)?;
+        if changed_conf == GeneveConf::default() {
+            return Ok(());
+        }
+        change_iface_link_info(
+            cur_iface.index,
+            InfoKind::Other(GENEVE_KIND.to_string()),
+            to_info_data(&changed_conf.to_nlas()?),
+        )
+    }
+
+    fn gen_changed_conf(
+        &self,
+        name: &str,
+        cur: &Self,
+    ) -> Result<Self, NisporError> {
+        let immutables = [
+            ("vni", is_changed(self.vni, cur.vni)),
+            ("dst_port", is_changed(self.dst_port, cur.dst_port)),
+            (
+                "collect_metadata",
+                is_changed(self.collect_metadata, cur.collect_metadata),
+            ),
+            (
+                "udp_check_sum",
+                is_changed(self.udp_check_sum, cur.udp_check_sum),
+            ),
+            (
+                "udp6_zero_check_sum_tx",
+                is_changed(
+                    self.udp6_zero_check_sum_tx,
+                    cur.udp6_zero_check_sum_tx,
+                ),
+            ),
+            (
+                "udp6_zero_check_sum_rx",
+                is_changed(
+                    self.udp6_zero_check_sum_rx,
+                    cur.udp6_zero_check_sum_rx,
+                ),
+            ),
+            (
+                "inner_proto_inherit",
+                is_changed(self.inner_proto_inherit, cur.inner_proto_inherit),
+            ),
+        ];
+        for (prop, changed) in immutables.iter() {
+            if *changed {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change {prop} of existing GENEVE interface \
+                    {name}, current {cur:?}"
+                )));
+            }
+        }
+        Ok(Self {
+            remote: self
+                .remote
+                .clone()
+                .filter(|v| Some(v) != cur.remote.as_ref()),
+            ttl: self.ttl.filter(|v| Some(*v) != cur.ttl),
+            ttl_inherit: self
+                .ttl_inherit
+                .filter(|v| Some(*v) != cur.ttl_inherit),
+            tos: self.tos.filter(|v| Some(*v) != cur.tos),
+            df: self.df.clone().filter(|v| Some(v) != cur.df.as_ref()),
+            label: self.label.filter(|v| Some(*v) != cur.label),
+            ..Default::default()
+        })
+    }
+
+    fn to_nlas(&self) -> Result<Vec<DefaultNla>, NisporError> {
+        let mut nlas = Vec::new();
+        if let Some(v) = self.vni {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_ID,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.remote.as_ref() {
+            nlas.push(match v.parse::<IpAddr>()? {
+                IpAddr::V4(ip) => {
+                    DefaultNla::new(IFLA_GENEVE_REMOTE, ip.octets().to_vec())
+                }
+                IpAddr::V6(ip) => {
+                    DefaultNla::new(IFLA_GENEVE_REMOTE6, ip.octets().to_vec())
+                }
+            });
+        }
+        if let Some(v) = self.ttl {
+            nlas.push(DefaultNla::new(IFLA_GENEVE_TTL, vec![v]));
+        }
+        if let Some(v) = self.ttl_inherit {
+            nlas.push(DefaultNla::new(IFLA_GENEVE_TTL_INHERIT, vec![v.into()]));
+        }
+        if let Some(v) = self.tos {
+            nlas.push(DefaultNla::new(IFLA_GENEVE_TOS, vec![v]));
+        }
+        if let Some(v) = self.df.as_ref() {
+            nlas.push(DefaultNla::new(IFLA_GENEVE_DF, vec![v.clone().into()]));
+        }
+        if let Some(v) = self.dst_port {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_PORT,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        // The IFLA_GENEVE_COLLECT_METADATA is NLA_FLAG in kernel
+        if self.collect_metadata == Some(true) {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_COLLECT_METADATA,
+                Vec::new(),
+            ));
+        }
+        if let Some(v) = self.udp_check_sum {
+            nlas.push(DefaultNla::new(IFLA_GENEVE_UDP_CSUM, vec![v.into()]));
+        }
+        if let Some(v) = self.udp6_zero_check_sum_tx {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_UDP_ZERO_CSUM6_TX,
+                vec![v.into()],
+            ));
+        }
+        if let Some(v) = self.udp6_zero_check_sum_rx {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_UDP_ZERO_CSUM6_RX,
+                vec![v.into()],
+            ));
+        }
+        if let Some(v) = self.label {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_LABEL,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        // The IFLA_GENEVE_INNER_PROTO_INHERIT is NLA_FLAG in kernel
+        if self.inner_proto_inherit == Some(true) {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_INNER_PROTO_INHERIT,
+                Vec::new(),
+            ));
+        }
+        Ok(nlas)
+    }
+}
@@ -0,0 +1,341 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::InfoKind;
+use netlink_packet_utils::nla::DefaultNla;
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    iface::{change_iface_link_info, create_iface_with_link_info},
+    ip_tunnel::{ip_to_bytes, to_info_data},
+};
+use crate::{
+    netlink::{
+        IFLA_GRE_COLLECT_METADATA, IFLA_GRE_ENCAP_DPORT, IFLA_GRE_ENCAP_FLAGS,
+        IFLA_GRE_ENCAP_LIMIT, IFLA_GRE_ENCAP_SPORT, IFLA_GRE_ENCAP_TYPE,
+        IFLA_GRE_ERSPAN_DIR, IFLA_GRE_ERSPAN_HWID, IFLA_GRE_ERSPAN_INDEX,
+        IFLA_GRE_ERSPAN_VER, IFLA_GRE_FLOWINFO, IFLA_GRE_FWMARK,
+        IFLA_GRE_IFLAGS, IFLA_GRE_IGNORE_DF, IFLA_GRE_IKEY, IFLA_GRE_LINK,
+        IFLA_GRE_LOCAL, IFLA_GRE_OFLAGS, IFLA_GRE_OKEY, IFLA_GRE_PMTUDISC,
+        IFLA_GRE_REMOTE, IFLA_GRE_TOS, IFLA_GRE_TTL,
+    },
+    GreErspanDir, GreInfo, Iface, IfaceType, NisporError, TunnelEncapType,
+};
+
+const GRE_KEY: u16 = 0x2000;
+
+/// Shared by interface type `gre`, `gretap`, `ip6gre`, `ip6gretap`,
+/// `erspan` and `ip6erspan`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct GreConf {
+    /// The interface tunnel packets are bound to.
+    pub link: Option<String>,
+    pub local: Option<String>,
+    pub remote: Option<String>,
+    /// Setting `ikey` will also set the key flag in `iflags`.
+    pub ikey: Option<u32>,
+    /// Setting `okey` will also set the key flag in `oflags`.
+    pub okey: Option<u32>,
+    pub iflags: Option<u16>,
+    pub oflags: Option<u16>,
+    pub ttl: Option<u8>,
+    pub tos: Option<u8>,
+    pub pmtudisc: Option<bool>,
+    /// IPv6 only
+    pub encap_limit: Option<u8>,
+    /// IPv6 only
+    pub flowinfo: Option<u32>,
+    pub encap_type: Option<TunnelEncapType>,
+    pub encap_flags: Option<u16>,
+    pub encap_sport: Option<u16>,
+    pub encap_dport: Option<u16>,
+    pub collect_metadata: Option<bool>,
+    pub ignore_df: Option<bool>,
+    pub fwmark: Option<u32>,
+    pub erspan_ver: Option<u8>,
+    pub erspan_index: Option<u32>,
+    pub erspan_dir: Option<GreErspanDir>,
+    pub erspan_hwid: Option<u16>,
+}
+
+impl From<&GreInfo> for GreConf {
+    fn from(info: &GreInfo) -> Self {
+        Self {
+            link: info.link.clone(),
+            local: info.local.clone(),
+            remote: info.remote.clone(),
+            ikey: Some(info.ikey),
+            okey: Some(info.okey),
+            iflags: Some(info.iflags),
+            oflags: Some(info.oflags),
+            ttl: Some(info.ttl),
+            tos: info.tos,
+            pmtudisc: info.pmtudisc,
+            encap_limit: info.encap_limit,
+            flowinfo: info.flowinfo,
+            encap_type: Some(info.encap_type.clone()),
+            encap_flags: Some(info.encap_flags),
+            encap_sport: Some(info.encap_sport),
+            encap_dport: Some(info.encap_dport),
+            collect_metadata: Some(info.collect_metadata),
+            ignore_df: info.ignore_df,
+            fwmark: Some(info.fwmark),
+            erspan_ver: info.erspan_ver,
+            erspan_index: info.erspan_index,
+            erspan_dir: info.erspan_dir.clone(),
+            erspan_hwid: info.erspan_hwid,
+        }
+    }
+}
+
+impl GreConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        iface_type: &IfaceType,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let link_index = match self.link.as_ref() {
+            Some(link) => match cur_iface_name_2_index.get(link) {
+                Some(i) => Some(*i),
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Link interface {link} for {iface_type} tunnel \
+                        {name} not found"
+                    )));
+                }
+            },
+            None => None,
+        };
+        // Merge with empty configuration to set the key flags
+        let conf = self.merge(&GreConf::default());
+        match create_iface_with_link_info(
+            handle,
+            name,
+            gre_info_kind(iface_type)?,
+            Some(to_info_data(&conf.to_nlas(link_index)?)),
+            None,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new {} '{}': {}",
+                iface_type, &name, e
+            ))),
+        }
+    }
+
+    // Kernel GRE driver is rebuilding the whole tunnel parameters from the
+    // netlink request when changing, hence we should send out the full
+    // configuration merged with current one.
+    pub(crate) fn change(
+        &self,
+        cur_iface: &Iface,
+        cur_ifaces: &HashMap<String, Iface>,
+    ) -> Result<(), NisporError> {
+        let cur_conf = match cur_iface.gre.as_ref() {
+            Some(i) => GreConf::from(i),
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not GRE tunnel",
+                    cur_iface.name
+                )));
+            }
+        };
+        let new_conf = self.merge(&cur_conf);
+        if new_conf == cur_conf {
+            return Ok(());
+        }
+        let link_index = match new_conf.link.as_ref() {
+            Some(link) => match cur_ifaces.get(link) {
+                Some(i) => Some(i.index),
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Link interface {} for GRE tunnel {} not found",
+                        link, cur_iface.name
+                    )));
+                }
+            },
+            None => None,
+        };
+        change_iface_link_info(
+            cur_iface.index,
+            gre_info_kind(&cur_iface.iface_type)?,
+            to_info_data(&new_conf.to_nlas(link_index)?),
+        )
+    }
+
+    fn merge(&self, cur: &Self) -> Self {
+        let mut iflags = self.iflags.or(cur.iflags);
+        if self.ikey.is_some() && self.iflags.is_none() {
+            iflags = Some(iflags.unwrap_or_default() | GRE_KEY);
+        }
+        let mut oflags = self.oflags.or(cur.oflags);
+        if self.okey.is_some() && self.oflags.is_none() {
+            oflags = Some(oflags.unwrap_or_default() | GRE_KEY);
+        }
+        Self {
+            link: self.link.clone().or_else(|| cur.link.clone()),
+            local: self.local.clone().or_else(|| cur.local.clone()),
+            remote: self.remote.clone().or_else(|| cur.remote.clone()),
+            ikey: self.ikey.or(cur.ikey),
+            okey: self.okey.or(cur.okey),
+            iflags,
+            oflags,
+            ttl: self.ttl.or(cur.ttl),
+            tos: self.tos.or(cur.tos),
+            pmtudisc: self.pmtudisc.or(cur.pmtudisc),
+            encap_limit: self.encap_limit.or(cur.encap_limit),
+            flowinfo: self.flowinfo.or(cur.flowinfo),
+            encap_type: self
+                .encap_type
+                .clone()
+                .or_else(|| cur.encap_type.clone()),
+            encap_flags: self.encap_flags.or(cur.encap_flags),
+            encap_sport: self.encap_sport.or(cur.encap_sport),
+            encap_dport: self.encap_dport.or(cur.encap_dport),
+            collect_metadata: self.collect_metadata.or(cur.collect_metadata),
+            ignore_df: self.ignore_df.or(cur.ignore_df),
+            fwmark: self.fwmark.or(cur.fwmark),
+            erspan_ver: self.erspan_ver.or(cur.erspan_ver),
+            erspan_index: self.erspan_index.or(cur.erspan_index),
+            erspan_dir: self
+                .erspan_dir
+                .clone()
+                .or_else(|| cur.erspan_dir.clone()),
+            erspan_hwid: self.erspan_hwid.or(cur.erspan_hwid),
+        }
+    }
+
+    fn to_nlas(
+        &self,
+        link_index: Option<u32>,
+    ) -> Result<Vec<DefaultNla>, NisporError> {
+        let mut nlas = Vec::new();
+        if let Some(v) = link_index {
+            nlas.push(DefaultNla::new(IFLA_GRE_LINK, v.to_ne_bytes().to_vec()));
+        }
+        if let Some(v) = self.local.as_ref() {
+            nlas.push(DefaultNla::new(IFLA_GRE_LOCAL, ip_to_bytes(v)?));
+        }
+        if let Some(v) = self.remote.as_ref() {
+            nlas.push(DefaultNla::new(IFLA_GRE_REMOTE, ip_to_bytes(v)?));
+        }
+        if let Some(v) = self.ikey {
+            nlas.push(DefaultNla::new(IFLA_GRE_IKEY, v.to_be_bytes().to_vec()));
+        }
+        if let Some(v) = self.okey {
+            nlas.push(DefaultNla::new(IFLA_GRE_OKEY, v.to_be_bytes().to_vec()));
+        }
+        if let Some(v) = self.iflags {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_IFLAGS,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.oflags {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_OFLAGS,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.ttl {
+            nlas.push(DefaultNla::new(IFLA_GRE_TTL, vec![v]));
+        }
+        if let Some(v) = self.tos {
+            nlas.push(DefaultNla::new(IFLA_GRE_TOS, vec![v]));
+        }
+        if let Some(v) = self.pmtudisc {
+            nlas.push(DefaultNla::new(IFLA_GRE_PMTUDISC, vec![v.into()]));
+        }
+        if let Some(v) = self.encap_limit {
+            nlas.push(DefaultNla::new(IFLA_GRE_ENCAP_LIMIT, vec![v]));
+        }
+        if let Some(v) = self.flowinfo {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_FLOWINFO,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.encap_type.as_ref() {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_ENCAP_TYPE,
+                u16::from(v.clone()).to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.encap_flags {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_ENCAP_FLAGS,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.encap_sport {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_ENCAP_SPORT,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.encap_dport {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_ENCAP_DPORT,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        // The IFLA_GRE_COLLECT_METADATA is NLA_FLAG in kernel
+        if self.collect_metadata == Some(true) {
+            nlas.push(DefaultNla::new(IFLA_GRE_COLLECT_METADATA, Vec::new()));
+        }
+        if let Some(v) = self.ignore_df {
+            nlas.push(DefaultNla::new(IFLA_GRE_IGNORE_DF, vec![v.into()]));
+        }
+        if let Some(v) = self.fwmark {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_FWMARK,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.erspan_ver {
+            nlas.push(DefaultNla::new(IFLA_GRE_ERSPAN_VER, vec![v]));
+        }
+        if let Some(v) = self.erspan_index {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_ERSPAN_INDEX,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.erspan_dir.as_ref() {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_ERSPAN_DIR,
+                vec![v.clone().into()],
+            ));
+        }
+        if let Some(v) = self.erspan_hwid {
+            nlas.push(DefaultNla::new(
+                IFLA_GRE_ERSPAN_HWID,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        Ok(nlas)
+    }
+}
+
+fn gre_info_kind(iface_type: &IfaceType) -> Result<InfoKind, NisporError> {
+    Ok(match iface_type {
+        IfaceType::Gre => InfoKind::GreTun,
+        IfaceType::GreTap => InfoKind::GreTap,
+        IfaceType::Ip6Gre => InfoKind::GreTun6,
+        IfaceType::Ip6GreTap => InfoKind::GreTap6,
+        IfaceType::Erspan => InfoKind::Other("erspan".to_string()),
+        IfaceType::Ip6Erspan => InfoKind::Other("ip6erspan".to_string()),
+        _ => {
+            return Err(NisporError::invalid_argument(format!(
+                "Interface type {iface_type} is not GRE tunnel"
+            )));
+        }
+    })
+}
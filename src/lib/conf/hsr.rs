@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::{self, InfoData, InfoHsr, InfoKind};
+use netlink_packet_utils::nla::DefaultNla;
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{create_iface_with_link_info, is_changed};
+use crate::{
+    netlink::IFLA_HSR_INTERLINK, HsrInfo, HsrProtocol, Iface, NisporError,
+};
+
+/// Configuration for both HSR and PRP interfaces.
+/// Kernel does not support changing HSR interface after creation.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct HsrConf {
+    /// Required when creating new interface.
+    pub port1: Option<String>,
+    /// Required when creating new interface.
+    pub port2: Option<String>,
+    /// Interlink port of HSR-SAN or PRP-SAN RedBox, Linux kernel 6.11+.
+    pub interlink: Option<String>,
+    /// The last byte of supervision frame multicast address.
+    pub multicast_spec: Option<u8>,
+    /// HSR version, 0 for HSRv0 and 1 for HSRv1. Only used when creating
+    /// new interface as kernel does not report it.
+    pub version: Option<u8>,
+    pub protocol: Option<HsrProtocol>,
+}
+
+impl HsrConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let mut nlas = vec![
+            InfoHsr::Port1(get_port_index(
+                self.port1.as_deref(),
+                "port1",
+                name,
+                cur_iface_name_2_index,
+            )?),
+            InfoHsr::Port2(get_port_index(
+                self.port2.as_deref(),
+                "port2",
+                name,
+                cur_iface_name_2_index,
+            )?),
+        ];
+        if self.interlink.is_some() {
+            let index = get_port_index(
+                self.interlink.as_deref(),
+                "interlink",
+                name,
+                cur_iface_name_2_index,
+            )?;
+            nlas.push(InfoHsr::Other(DefaultNla::new(
+                IFLA_HSR_INTERLINK,
+                index.to_ne_bytes().to_vec(),
+            )));
+        }
+        if let Some(v) = self.multicast_spec {
+            nlas.push(InfoHsr::MulticastSpec(v));
+        }
+        if let Some(v) = self.version {
+            nlas.push(InfoHsr::Version(v));
+        }
+        if let Some(v) = self.protocol {
+            nlas.push(InfoHsr::Protocol(link::HsrProtocol::from(u8::from(v))));
+        }
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::Hsr,
+            Some(InfoData::Hsr(nlas)),
+            None,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new hsr '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+
+    // Kernel HSR driver does not support changelink, hence we only verify
+    // whether desired configuration matches current one.
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.hsr.as_ref() {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not HSR",
+                    cur_iface.name
+                )));
+            }
+        };
+        let cur = HsrConf::from(cur_info);
+        let props = [
+            ("port1", is_changed(self.port1.as_ref(), cur.port1.as_ref())),
+            ("port2", is_changed(self.port2.as_ref(), cur.port2.as_ref())),
+            (
+                "interlink",
+                is_changed(self.interlink.as_ref(), cur.interlink.as_ref()),
+            ),
+            (
+                "multicast_spec",
+                is_changed(self.multicast_spec, cur.multicast_spec),
+            ),
+            ("protocol", is_changed(self.protocol, cur.protocol)),
+        ];
+        for (prop, changed) in props.iter() {
+            if *changed {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change {} of existing HSR interface {}, \
+                    current {:?}",
+                    prop, cur_iface.name, cur_info
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&HsrInfo> for HsrConf {
+    fn from(info: &HsrInfo) -> Self {
+        Self {
+            port1: info.port1.clone(),
+            port2: info.port2.clone(),
+            interlink: info.interlink.clone(),
+            multicast_spec: Some(info.multicast_spec),
+            // Kernel does not report HSR version
+            version: None,
+            protocol: Some(info.protocol),
+        }
+    }
+}
+
+fn get_port_index(
+    port: Option<&str>,
+    prop: &str,
+    name: &str,
+    cur_iface_name_2_index: &HashMap<String, u32>,
+) -> Result<u32, NisporError> {
+    let port = match port {
+        Some(p) => p,
+        None => {
+            return Err(NisporError::invalid_argument(format!(
+                "No {prop} defined for new HSR interface {name}"
+            )));
+        }
+    };
+    match cur_iface_name_2_index.get(port) {
+        Some(i) => Ok(*i),
+        None => Err(NisporError::invalid_argument(format!(
+            "Port {port} for HSR interface {name} not found"
+        ))),
+    }
+}
@@ -2,12 +2,25 @@
 
 use std::collections::HashMap;
 
+use netlink_packet_route::link::{
+    InfoData, InfoKind, LinkAttribute, LinkInfo, LinkMessage,
+};
+use netlink_packet_utils::Emitable;
+use netlink_sys::constants::NETLINK_ROUTE;
 use serde::{Deserialize, Serialize};
 
-use super::{super::mac::mac_str_to_raw, inter_ifaces::change_ifaces};
+use super::{
+    super::{
+        mac::mac_str_to_raw,
+        netlink::{nl_request, NLM_F_ACK},
+    },
+    inter_ifaces::change_ifaces,
+};
 use crate::{
-    BondConf, BridgeConf, Iface, IfaceState, IfaceType, IpConf, NisporError,
-    VethConf, VlanConf,
+    BondConf, BridgeConf, BridgePortConf, EthtoolConf, GeneveConf, GreConf,
+    HsrConf, Iface, IfaceState, IfaceType, IpConf, IpTunnelConf, IpVlanConf,
+    IpoibConf, MacSecConf, MacVlanConf, MacVtapConf, NisporError, SriovConf,
+    TunConf, VethConf, VlanConf, VrfConf, VxlanConf, WireguardConf, XfrmConf,
 };
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
@@ -24,8 +37,31 @@ pub struct IfaceConf {
     pub mac_address: Option<String>,
     pub veth: Option<VethConf>,
     pub bridge: Option<BridgeConf>,
+    pub bridge_port: Option<BridgePortConf>,
     pub vlan: Option<VlanConf>,
     pub bond: Option<BondConf>,
+    pub vxlan: Option<VxlanConf>,
+    pub mac_vlan: Option<MacVlanConf>,
+    pub mac_vtap: Option<MacVtapConf>,
+    pub vrf: Option<VrfConf>,
+    pub tun: Option<TunConf>,
+    pub macsec: Option<MacSecConf>,
+    pub ip_vlan: Option<IpVlanConf>,
+    pub ip_vtap: Option<IpVlanConf>,
+    /// For interface type `gre`, `gretap`, `ip6gre`, `ip6gretap`,
+    /// `erspan` and `ip6erspan`.
+    pub gre: Option<GreConf>,
+    /// For interface type `ipip`, `sit` and `ip6tnl`.
+    pub ip_tunnel: Option<IpTunnelConf>,
+    pub geneve: Option<GeneveConf>,
+    pub wireguard: Option<WireguardConf>,
+    /// For both HSR and PRP interfaces.
+    pub hsr: Option<HsrConf>,
+    pub xfrm: Option<XfrmConf>,
+    pub ipoib: Option<IpoibConf>,
+    pub ethtool: Option<EthtoolConf>,
+    /// SR-IOV configuration of PF
+    pub sriov: Option<SriovConf>,
 }
 
 impl IfaceConf {
@@ -41,6 +77,8 @@ impl IfaceConf {
     }
 }
 
+const RTM_NEWLINK: u16 = 16;
+
 fn default_iface_state_in_conf() -> IfaceState {
     IfaceState::Up
 }
@@ -72,3 +110,72 @@ pub(crate) async fn change_iface_mac(
         .await?;
     Ok(())
 }
+
+// The rust-rtnetlink only provide creation helper for a few interface types,
+// this function creates interface with specified link info data.
+pub(crate) async fn create_iface_with_link_info(
+    handle: &rtnetlink::Handle,
+    name: &str,
+    kind: InfoKind,
+    data: Option<InfoData>,
+    base_iface_index: Option<u32>,
+) -> Result<(), rtnetlink::Error> {
+    let mut req = handle.link().add();
+    let mutator = req.message_mut();
+    let mut link_info = vec![LinkInfo::Kind(kind)];
+    if let Some(data) = data {
+        link_info.push(LinkInfo::Data(data));
+    }
+    mutator.attributes.push(LinkAttribute::LinkInfo(link_info));
+    mutator
+        .attributes
+        .push(LinkAttribute::IfName(name.to_string()));
+    if let Some(base_iface_index) = base_iface_index {
+        mutator
+            .attributes
+            .push(LinkAttribute::Link(base_iface_index));
+    }
+    req.execute().await
+}
+
+// Create interface holding no type specific data, like dummy, ifb, nlmon.
+pub(crate) async fn create_simple_iface(
+    handle: &rtnetlink::Handle,
+    name: &str,
+    kind: InfoKind,
+) -> Result<(), NisporError> {
+    let kind_str = kind.to_string();
+    if let Err(e) =
+        create_iface_with_link_info(handle, name, kind, None, None).await
+    {
+        return Err(NisporError::bug(format!(
+            "Failed to create new {kind_str} '{name}': {e}"
+        )));
+    }
+    Ok(())
+}
+
+// The RTM_SETLINK does not support changing link info data, we need to send
+// RTM_NEWLINK against existing interface without NLM_F_CREATE which is always
+// set by rust-rtnetlink.
+pub(crate) fn change_iface_link_info(
+    index: u32,
+    kind: InfoKind,
+    data: InfoData,
+) -> Result<(), NisporError> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = index;
+    nl_msg.attributes.push(LinkAttribute::LinkInfo(vec![
+        LinkInfo::Kind(kind),
+        LinkInfo::Data(data),
+    ]));
+    let mut buffer = vec![0; nl_msg.buffer_len()];
+    nl_msg.emit(&mut buffer);
+    nl_request(NETLINK_ROUTE, RTM_NEWLINK, NLM_F_ACK, &buffer)?;
+    Ok(())
+}
+
+// Whether the desired property is defined and differs from current one.
+pub(crate) fn is_changed<T: PartialEq>(des: Option<T>, cur: Option<T>) -> bool {
+    des.is_some() && des != cur
+}
@@ -2,11 +2,14 @@
 
 use std::collections::HashMap;
 
+use netlink_packet_route::link::InfoKind;
 use rtnetlink::new_connection;
 
 use super::{
-    iface::{change_iface_mac, change_iface_state},
+    bridge::change_bridge_vlan_opts,
+    iface::{change_iface_mac, change_iface_state, create_simple_iface},
     ip::change_ips,
+    vrf::change_vrfs,
 };
 use crate::{
     BondConf, BridgeConf, Iface, IfaceConf, IfaceState, IfaceType, NisporError,
@@ -74,16 +77,247 @@ pub(crate) async fn create_ifaces(
                     }
                 }
             }
-            Some(_) => {
+            Some(IfaceType::Vxlan) => {
+                if let Some(vxlan_conf) = &iface.vxlan {
+                    vxlan_conf
+                        .create(&handle, &iface.name, cur_iface_name_2_index)
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No vxlan configuration defined for new VXLAN \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::MacSec) => {
+                if let Some(macsec_conf) = &iface.macsec {
+                    macsec_conf
+                        .create(&handle, &iface.name, cur_iface_name_2_index)
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No macsec configuration defined for new MACsec \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::Tun) => {
+                if let Some(tun_conf) = &iface.tun {
+                    tun_conf.create(&iface.name)?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No tun configuration defined for new TUN \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::Vrf) => {
+                if let Some(vrf_conf) = &iface.vrf {
+                    vrf_conf.create(&handle, &iface.name).await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No vrf configuration defined for new VRF \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::MacVlan) => {
+                if let Some(mac_vlan_conf) = &iface.mac_vlan {
+                    mac_vlan_conf
+                        .create(&handle, &iface.name, cur_iface_name_2_index)
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No mac_vlan configuration defined for new MAC VLAN \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::MacVtap) => {
+                if let Some(mac_vtap_conf) = &iface.mac_vtap {
+                    mac_vtap_conf
+                        .create(&handle, &iface.name, cur_iface_name_2_index)
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No mac_vtap configuration defined for new MAC VTAP \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::IpVlan) => {
+                if let Some(ip_vlan_conf) = &iface.ip_vlan {
+                    ip_vlan_conf
+                        .create(
+                            &handle,
+                            &iface.name,
+                            &IfaceType::IpVlan,
+                            cur_iface_name_2_index,
+                        )
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No ip_vlan configuration defined for new IP VLAN \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::IpVtap) => {
+                if let Some(ip_vtap_conf) = &iface.ip_vtap {
+                    ip_vtap_conf
+                        .create(
+                            &handle,
+                            &iface.name,
+                            &IfaceType::IpVtap,
+                            cur_iface_name_2_index,
+                        )
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No ip_vtap configuration defined for new IP VTAP \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(
+                ref iface_type @ (IfaceType::Gre
+                | IfaceType::GreTap
+                | IfaceType::Ip6Gre
+                | IfaceType::Ip6GreTap
+                | IfaceType::Erspan
+                | IfaceType::Ip6Erspan),
+            ) => {
+                if let Some(gre_conf) = &iface.gre {
+                    gre_conf
+                        .create(
+                            &handle,
+                            &iface.name,
+                            iface_type,
+                            cur_iface_name_2_index,
+                        )
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No gre configuration defined for new {} \
+                        interface {}",
+                        iface_type, iface.name
+                    )));
+                }
+            }
+            Some(
+                ref iface_type @ (IfaceType::IpIp
+                | IfaceType::Sit
+                | IfaceType::Ip6Tnl),
+            ) => {
+                if let Some(ip_tunnel_conf) = &iface.ip_tunnel {
+                    ip_tunnel_conf
+                        .create(
+                            &handle,
+                            &iface.name,
+                            iface_type,
+                            cur_iface_name_2_index,
+                        )
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No ip_tunnel configuration defined for new {} \
+                        interface {}",
+                        iface_type, iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::Hsr) => {
+                if let Some(hsr_conf) = &iface.hsr {
+                    hsr_conf
+                        .create(&handle, &iface.name, cur_iface_name_2_index)
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No hsr configuration defined for new HSR \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::Xfrm) => {
+                if let Some(xfrm_conf) = &iface.xfrm {
+                    xfrm_conf
+                        .create(&handle, &iface.name, cur_iface_name_2_index)
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No xfrm configuration defined for new XFRM \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::Ipoib) => {
+                if let Some(ipoib_conf) = &iface.ipoib {
+                    ipoib_conf
+                        .create(&handle, &iface.name, cur_iface_name_2_index)
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No ipoib configuration defined for new IPoIB \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::Geneve) => {
+                if let Some(geneve_conf) = &iface.geneve {
+                    geneve_conf.create(&handle, &iface.name).await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No geneve configuration defined for new GENEVE \
+                        interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::Dummy) => {
+                create_simple_iface(&handle, &iface.name, InfoKind::Dummy)
+                    .await?;
+            }
+            Some(IfaceType::Ifb) => {
+                create_simple_iface(&handle, &iface.name, InfoKind::Ifb)
+                    .await?;
+            }
+            Some(IfaceType::Wireguard) => {
+                create_simple_iface(&handle, &iface.name, InfoKind::Wireguard)
+                    .await?;
+            }
+            Some(IfaceType::Nlmon) => {
+                create_simple_iface(&handle, &iface.name, InfoKind::Nlmon)
+                    .await?;
+            }
+            Some(IfaceType::Vcan) => {
+                create_simple_iface(
+                    &handle,
+                    &iface.name,
+                    InfoKind::Other("vcan".to_string()),
+                )
+                .await?;
+            }
+            Some(ref iface_type) => {
                 return Err(NisporError::invalid_argument(format!(
-                    "Cannot create unsupported interface {:?}",
-                    &iface
+                    "Cannot create unsupported {} interface {}",
+                    iface_type, iface.name
                 )));
             }
             None => {
                 return Err(NisporError::invalid_argument(format!(
-                    "No interface type defined for new interface {:?}",
-                    &iface
+                    "No interface type defined for new interface {}",
+                    iface.name
                 )));
             }
         }
@@ -102,12 +336,98 @@ pub(crate) async fn change_ifaces(
         log::debug!("Changing interface {}", iface.name);
     }
     change_ifaces_mac(&handle, ifaces, cur_ifaces).await?;
+    change_ifaces_link_info(ifaces, cur_ifaces)?;
+    change_ifaces_ethtool(ifaces, cur_ifaces)?;
+    change_ifaces_sriov(ifaces, cur_ifaces)?;
+    change_vrfs(&handle, ifaces, cur_ifaces).await?;
     change_ifaces_controller(&handle, ifaces, cur_ifaces).await?;
     change_ifaces_state(&handle, ifaces, cur_ifaces).await?;
+    change_bridge_vlan_opts(ifaces, cur_ifaces)?;
     change_ips(&handle, ifaces, cur_ifaces).await?;
     Ok(())
 }
 
+fn change_ifaces_link_info(
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in ifaces {
+        if let Some(cur_iface) = cur_ifaces.get(&iface.name) {
+            if let Some(vxlan_conf) = iface.vxlan.as_ref() {
+                vxlan_conf.change(cur_iface, cur_ifaces)?;
+            }
+            if let Some(mac_vlan_conf) = iface.mac_vlan.as_ref() {
+                mac_vlan_conf.change(cur_iface)?;
+            }
+            if let Some(mac_vtap_conf) = iface.mac_vtap.as_ref() {
+                mac_vtap_conf.change(cur_iface)?;
+            }
+            if let Some(tun_conf) = iface.tun.as_ref() {
+                tun_conf.change(cur_iface)?;
+            }
+            if let Some(macsec_conf) = iface.macsec.as_ref() {
+                macsec_conf.change(cur_iface)?;
+            }
+            if let Some(ip_vlan_conf) = iface.ip_vlan.as_ref() {
+                ip_vlan_conf.change(cur_iface)?;
+            }
+            if let Some(ip_vtap_conf) = iface.ip_vtap.as_ref() {
+                ip_vtap_conf.change(cur_iface)?;
+            }
+            if let Some(gre_conf) = iface.gre.as_ref() {
+                gre_conf.change(cur_iface, cur_ifaces)?;
+            }
+            if let Some(ip_tunnel_conf) = iface.ip_tunnel.as_ref() {
+                ip_tunnel_conf.change(cur_iface, cur_ifaces)?;
+            }
+            if let Some(geneve_conf) = iface.geneve.as_ref() {
+                geneve_conf.change(cur_iface)?;
+            }
+            if let Some(wireguard_conf) = iface.wireguard.as_ref() {
+                wireguard_conf.change(cur_iface)?;
+            }
+            if let Some(hsr_conf) = iface.hsr.as_ref() {
+                hsr_conf.change(cur_iface)?;
+            }
+            if let Some(xfrm_conf) = iface.xfrm.as_ref() {
+                xfrm_conf.change(cur_iface, cur_ifaces)?;
+            }
+            if let Some(ipoib_conf) = iface.ipoib.as_ref() {
+                ipoib_conf.change(cur_iface)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn change_ifaces_ethtool(
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in ifaces {
+        if let (Some(ethtool_conf), Some(cur_iface)) =
+            (iface.ethtool.as_ref(), cur_ifaces.get(&iface.name))
+        {
+            ethtool_conf.change(cur_iface)?;
+        }
+    }
+    Ok(())
+}
+
+fn change_ifaces_sriov(
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in ifaces {
+        if let (Some(sriov_conf), Some(cur_iface)) =
+            (iface.sriov.as_ref(), cur_ifaces.get(&iface.name))
+        {
+            sriov_conf.change(cur_iface)?;
+        }
+    }
+    Ok(())
+}
+
 async fn change_ifaces_state(
     handle: &rtnetlink::Handle,
     ifaces: &[&IfaceConf],
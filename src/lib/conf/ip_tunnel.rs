@@ -0,0 +1,360 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use netlink_packet_route::link::{InfoData, InfoKind};
+use netlink_packet_utils::{nla::DefaultNla, Emitable};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{change_iface_link_info, create_iface_with_link_info};
+use crate::{
+    netlink::{
+        IFLA_IPTUN_6RD_PREFIX, IFLA_IPTUN_6RD_PREFIXLEN,
+        IFLA_IPTUN_6RD_RELAY_PREFIX, IFLA_IPTUN_6RD_RELAY_PREFIXLEN,
+        IFLA_IPTUN_COLLECT_METADATA, IFLA_IPTUN_ENCAP_DPORT,
+        IFLA_IPTUN_ENCAP_FLAGS, IFLA_IPTUN_ENCAP_LIMIT, IFLA_IPTUN_ENCAP_SPORT,
+        IFLA_IPTUN_ENCAP_TYPE, IFLA_IPTUN_FLAGS, IFLA_IPTUN_FLOWINFO,
+        IFLA_IPTUN_FWMARK, IFLA_IPTUN_LINK, IFLA_IPTUN_LOCAL,
+        IFLA_IPTUN_PMTUDISC, IFLA_IPTUN_PROTO, IFLA_IPTUN_REMOTE,
+        IFLA_IPTUN_TOS, IFLA_IPTUN_TTL, IP6_FLOWINFO_FLOWLABEL_MASK,
+        IP6_FLOWINFO_TCLASS_SHIFT,
+    },
+    query::parse_ip_net_addr_str,
+    Iface, IfaceType, IpTunnelInfo, NisporError, TunnelEncapType,
+};
+
+/// Shared by interface type `ipip`, `sit` and `ip6tnl`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpTunnelConf {
+    /// The interface tunnel packets are bound to.
+    pub link: Option<String>,
+    pub local: Option<String>,
+    pub remote: Option<String>,
+    pub ttl: Option<u8>,
+    /// ipip and sit only
+    pub tos: Option<u8>,
+    /// ip6tnl only
+    pub tclass: Option<u8>,
+    /// ip6tnl only
+    pub flowlabel: Option<u32>,
+    /// ip6tnl only
+    pub encap_limit: Option<u8>,
+    /// The `IP6_TNL_F_*` flags of ip6tnl or `SIT_ISATAP` flag of sit.
+    pub flags: Option<u32>,
+    /// Inner IP protocol number, 0 means any.
+    pub proto: Option<u8>,
+    pub pmtudisc: Option<bool>,
+    /// IPv6 rapid deployment prefix of sit, e.g. `2001:db8::/32`.
+    pub ip6rd_prefix: Option<String>,
+    /// IPv6 rapid deployment relay prefix of sit, e.g. `192.0.2.0/24`.
+    pub ip6rd_relay_prefix: Option<String>,
+    pub encap_type: Option<TunnelEncapType>,
+    pub encap_flags: Option<u16>,
+    pub encap_sport: Option<u16>,
+    pub encap_dport: Option<u16>,
+    pub collect_metadata: Option<bool>,
+    pub fwmark: Option<u32>,
+}
+
+impl From<&IpTunnelInfo> for IpTunnelConf {
+    fn from(info: &IpTunnelInfo) -> Self {
+        Self {
+            link: info.link.clone(),
+            local: info.local.clone(),
+            remote: info.remote.clone(),
+            ttl: Some(info.ttl),
+            tos: info.tos,
+            tclass: info.tclass,
+            flowlabel: info.flowlabel,
+            encap_limit: info.encap_limit,
+            flags: info.flags,
+            proto: info.proto,
+            pmtudisc: info.pmtudisc,
+            ip6rd_prefix: info.ip6rd_prefix.clone(),
+            ip6rd_relay_prefix: info.ip6rd_relay_prefix.clone(),
+            encap_type: Some(info.encap_type.clone()),
+            encap_flags: Some(info.encap_flags),
+            encap_sport: Some(info.encap_sport),
+            encap_dport: Some(info.encap_dport),
+            collect_metadata: Some(info.collect_metadata),
+            fwmark: Some(info.fwmark),
+        }
+    }
+}
+
+impl IpTunnelConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        iface_type: &IfaceType,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let link_index = match self.link.as_ref() {
+            Some(link) => match cur_iface_name_2_index.get(link) {
+                Some(i) => Some(*i),
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Link interface {link} for {iface_type} tunnel \
+                        {name} not found"
+                    )));
+                }
+            },
+            None => None,
+        };
+        match create_iface_with_link_info(
+            handle,
+            name,
+            ip_tunnel_info_kind(iface_type)?,
+            Some(to_info_data(&self.to_nlas(iface_type, link_index)?)),
+            None,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new {} '{}': {}",
+                iface_type, &name, e
+            ))),
+        }
+    }
+
+    // Kernel IP tunnel drivers are rebuilding the whole tunnel parameters
+    // from the netlink request when changing, hence we should send out the
+    // full configuration merged with current one.
+    pub(crate) fn change(
+        &self,
+        cur_iface: &Iface,
+        cur_ifaces: &HashMap<String, Iface>,
+    ) -> Result<(), NisporError> {
+        let cur_conf = match cur_iface.ip_tunnel.as_ref() {
+            Some(i) => IpTunnelConf::from(i),
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not ipip, sit or ip6tnl tunnel",
+                    cur_iface.name
+                )));
+            }
+        };
+        let new_conf = self.merge(&cur_conf);
+        if new_conf == cur_conf {
+            return Ok(());
+        }
+        let link_index = match new_conf.link.as_ref() {
+            Some(link) => match cur_ifaces.get(link) {
+                Some(i) => Some(i.index),
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Link interface {} for tunnel {} not found",
+                        link, cur_iface.name
+                    )));
+                }
+            },
+            None => None,
+        };
+        change_iface_link_info(
+            cur_iface.index,
+            ip_tunnel_info_kind(&cur_iface.iface_type)?,
+            to_info_data(&new_conf.to_nlas(&cur_iface.iface_type, link_index)?),
+        )
+    }
+
+    fn merge(&self, cur: &Self) -> Self {
+        Self {
+            link: self.link.clone().or_else(|| cur.link.clone()),
+            local: self.local.clone().or_else(|| cur.local.clone()),
+            remote: self.remote.clone().or_else(|| cur.remote.clone()),
+            ttl: self.ttl.or(cur.ttl),
+            tos: self.tos.or(cur.tos),
+            tclass: self.tclass.or(cur.tclass),
+            flowlabel: self.flowlabel.or(cur.flowlabel),
+            encap_limit: self.encap_limit.or(cur.encap_limit),
+            flags: self.flags.or(cur.flags),
+            proto: self.proto.or(cur.proto),
+            pmtudisc: self.pmtudisc.or(cur.pmtudisc),
+            ip6rd_prefix: self
+                .ip6rd_prefix
+                .clone()
+                .or_else(|| cur.ip6rd_prefix.clone()),
+            ip6rd_relay_prefix: self
+                .ip6rd_relay_prefix
+                .clone()
+                .or_else(|| cur.ip6rd_relay_prefix.clone()),
+            encap_type: self
+                .encap_type
+                .clone()
+                .or_else(|| cur.encap_type.clone()),
+            encap_flags: self.encap_flags.or(cur.encap_flags),
+            encap_sport: self.encap_sport.or(cur.encap_sport),
+            encap_dport: self.encap_dport.or(cur.encap_dport),
+            collect_metadata: self.collect_metadata.or(cur.collect_metadata),
+            fwmark: self.fwmark.or(cur.fwmark),
+        }
+    }
+
+    fn to_nlas(
+        &self,
+        iface_type: &IfaceType,
+        link_index: Option<u32>,
+    ) -> Result<Vec<DefaultNla>, NisporError> {
+        let mut nlas = Vec::new();
+        if let Some(v) = link_index {
+            nlas.push(DefaultNla::new(
+                IFLA_IPTUN_LINK,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.local.as_ref() {
+            nlas.push(DefaultNla::new(IFLA_IPTUN_LOCAL, ip_to_bytes(v)?));
+        }
+        if let Some(v) = self.remote.as_ref() {
+            nlas.push(DefaultNla::new(IFLA_IPTUN_REMOTE, ip_to_bytes(v)?));
+        }
+        if let Some(v) = self.ttl {
+            nlas.push(DefaultNla::new(IFLA_IPTUN_TTL, vec![v]));
+        }
+        if let Some(v) = self.tos {
+            nlas.push(DefaultNla::new(IFLA_IPTUN_TOS, vec![v]));
+        }
+        if self.tclass.is_some() || self.flowlabel.is_some() {
+            let flowinfo = (u32::from(self.tclass.unwrap_or_default())
+                << IP6_FLOWINFO_TCLASS_SHIFT)
+                | (self.flowlabel.unwrap_or_default()
+                    & IP6_FLOWINFO_FLOWLABEL_MASK);
+            nlas.push(DefaultNla::new(
+                IFLA_IPTUN_FLOWINFO,
+                flowinfo.to_be_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.encap_limit {
+            nlas.push(DefaultNla::new(IFLA_IPTUN_ENCAP_LIMIT, vec![v]));
+        }
+        if let Some(v) = self.flags {
+            // The sit is using 16 bits flags
+            nlas.push(DefaultNla::new(
+                IFLA_IPTUN_FLAGS,
+                if iface_type == &IfaceType::Sit {
+                    (v as u16).to_ne_bytes().to_vec()
+                } else {
+                    v.to_ne_bytes().to_vec()
+                },
+            ));
+        }
+        if let Some(v) = self.proto {
+            nlas.push(DefaultNla::new(IFLA_IPTUN_PROTO, vec![v]));
+        }
+        if let Some(v) = self.pmtudisc {
+            nlas.push(DefaultNla::new(IFLA_IPTUN_PMTUDISC, vec![v.into()]));
+        }
+        if let Some(v) = self.ip6rd_prefix.as_ref() {
+            let (ip, prefix_len) = parse_ip_net_addr_str(v)?;
+            match ip {
+                IpAddr::V6(ip) => {
+                    nlas.push(DefaultNla::new(
+                        IFLA_IPTUN_6RD_PREFIX,
+                        ip.octets().to_vec(),
+                    ));
+                    nlas.push(DefaultNla::new(
+                        IFLA_IPTUN_6RD_PREFIXLEN,
+                        u16::from(prefix_len).to_ne_bytes().to_vec(),
+                    ));
+                }
+                IpAddr::V4(_) => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "The ip6rd_prefix should be IPv6 network, but got \
+                        {v}"
+                    )));
+                }
+            }
+        }
+        if let Some(v) = self.ip6rd_relay_prefix.as_ref() {
+            let (ip, prefix_len) = parse_ip_net_addr_str(v)?;
+            match ip {
+                IpAddr::V4(ip) => {
+                    nlas.push(DefaultNla::new(
+                        IFLA_IPTUN_6RD_RELAY_PREFIX,
+                        ip.octets().to_vec(),
+                    ));
+                    nlas.push(DefaultNla::new(
+                        IFLA_IPTUN_6RD_RELAY_PREFIXLEN,
+                        u16::from(prefix_len).to_ne_bytes().to_vec(),
+                    ));
+                }
+                IpAddr::V6(_) => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "The ip6rd_relay_prefix should be IPv4 network, but \
+                        got {v}"
+                    )));
+                }
+            }
+        }
+        if let Some(v) = self.encap_type.as_ref() {
+            nlas.push(DefaultNla::new(
+                IFLA_IPTUN_ENCAP_TYPE,
+                u16::from(v.clone()).to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.encap_flags {
+            nlas.push(DefaultNla::new(
+                IFLA_IPTUN_ENCAP_FLAGS,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.encap_sport {
+            nlas.push(DefaultNla::new(
+                IFLA_IPTUN_ENCAP_SPORT,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        if let Some(v) = self.encap_dport {
+            nlas.push(DefaultNla::new(
+                IFLA_IPTUN_ENCAP_DPORT,
+                v.to_be_bytes().to_vec(),
+            ));
+        }
+        // The IFLA_IPTUN_COLLECT_METADATA is NLA_FLAG in kernel
+        if self.collect_metadata == Some(true) {
+            nlas.push(DefaultNla::new(IFLA_IPTUN_COLLECT_METADATA, Vec::new()));
+        }
+        if let Some(v) = self.fwmark {
+            nlas.push(DefaultNla::new(
+                IFLA_IPTUN_FWMARK,
+                v.to_ne_bytes().to_vec(),
+            ));
+        }
+        Ok(nlas)
+    }
+}
+
+pub(crate) fn ip_to_bytes(ip: &str) -> Result<Vec<u8>, NisporError> {
+    Ok(match ip.parse::<IpAddr>()? {
+        IpAddr::V4(i) => i.octets().to_vec(),
+        IpAddr::V6(i) => i.octets().to_vec(),
+    })
+}
+
+fn ip_tunnel_info_kind(
+    iface_type: &IfaceType,
+) -> Result<InfoKind, NisporError> {
+    Ok(match iface_type {
+        IfaceType::IpIp => InfoKind::IpTun,
+        IfaceType::Sit => InfoKind::SitTun,
+        IfaceType::Ip6Tnl => InfoKind::Other("ip6tnl".to_string()),
+        _ => {
+            return Err(NisporError::invalid_argument(format!(
+                "Interface type {iface_type} is not ipip, sit or ip6tnl"
+            )));
+        }
+    })
+}
+
+// The netlink-packet-route does not support attributes of IP, GRE and GENEVE
+// tunnels, hence we emit the raw bytes.
+pub(crate) fn to_info_data(nlas: &[DefaultNla]) -> InfoData {
+    let mut raw = vec![0u8; nlas.buffer_len()];
+    nlas.emit(&mut raw);
+    InfoData::Other(raw)
+}
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::{InfoData, InfoIpVlan, InfoKind};
+use netlink_packet_utils::{
+    nla::{DefaultNla, Nla},
+    Emitable,
+};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{change_iface_link_info, create_iface_with_link_info};
+use crate::{
+    Iface, IfaceType, IpVlanFlag, IpVlanInfo, IpVlanMode, NisporError,
+};
+
+const IPVTAP_KIND: &str = "ipvtap";
+
+/// Configuration for both IP VLAN and IP VTAP interfaces.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpVlanConf {
+    /// Required when creating new interface, cannot be changed afterwards.
+    pub base_iface: Option<String>,
+    pub mode: Option<IpVlanMode>,
+    pub flags: Option<IpVlanFlag>,
+}
+
+impl IpVlanConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        iface_type: &IfaceType,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let type_name = ip_vlan_type_name(iface_type);
+        let base_iface = match self.base_iface.as_ref() {
+            Some(b) => b,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "No base interface defined for new {type_name} {name}"
+                )));
+            }
+        };
+        let base_iface_index = match cur_iface_name_2_index.get(base_iface) {
+            Some(i) => *i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Base interface {base_iface} for {type_name} {name} \
+                    not found"
+                )));
+            }
+        };
+        let mut nlas = Vec::new();
+        if let Some(mode) = self.mode.as_ref() {
+            nlas.push(InfoIpVlan::Mode(mode.clone().into()));
+        }
+        if let Some(flags) = self.flags.as_ref() {
+            nlas.push(InfoIpVlan::Flags(flags.clone().into()));
+        }
+        let (kind, data) = gen_kind_and_data(iface_type, nlas);
+        match create_iface_with_link_info(
+            handle,
+            name,
+            kind,
+            Some(data),
+            Some(base_iface_index),
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new {} '{}': {}",
+                iface_type, &name, e
+            ))),
+        }
+    }
+
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.iface_type {
+            IfaceType::IpVlan => cur_iface.ip_vlan.as_ref(),
+            IfaceType::IpVtap => cur_iface.ip_vtap.as_ref(),
+            _ => None,
+        };
+        let cur_info = match cur_info {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not IP VLAN or IP VTAP",
+                    cur_iface.name
+                )));
+            }
+        };
+        let nlas = self.gen_change_nlas(&cur_iface.name, cur_info)?;
+        if !nlas.is_empty() {
+            let (kind, data) = gen_kind_and_data(&cur_iface.iface_type, nlas);
+            change_iface_link_info(cur_iface.index, kind, data)?;
+        }
+        Ok(())
+    }
+
+    fn gen_change_nlas(
+        &self,
+        name: &str,
+        cur_info: &IpVlanInfo,
+    ) -> Result<Vec<InfoIpVlan>, NisporError> {
+        if let Some(base_iface) = self.base_iface.as_ref() {
+            if base_iface != &cur_info.base_iface {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change base interface of {} from {} to {}",
+                    name, cur_info.base_iface, base_iface
+                )));
+            }
+        }
+        let mut nlas = Vec::new();
+        if let Some(mode) = self.mode.as_ref() {
+            if mode != &cur_info.mode {
+                nlas.push(InfoIpVlan::Mode(mode.clone().into()));
+            }
+        }
+        if let Some(flags) = self.flags.as_ref() {
+            if flags != &cur_info.flags {
+                nlas.push(InfoIpVlan::Flags(flags.clone().into()));
+            }
+        }
+        Ok(nlas)
+    }
+}
+
+fn ip_vlan_type_name(iface_type: &IfaceType) -> &'static str {
+    if iface_type == &IfaceType::IpVtap {
+        "IP VTAP"
+    } else {
+        "IP VLAN"
+    }
+}
+
+// The netlink-packet-route does not support IP VTAP, but it is sharing the
+// same netlink attributes with IP VLAN.
+fn gen_kind_and_data(
+    iface_type: &IfaceType,
+    nlas: Vec<InfoIpVlan>,
+) -> (InfoKind, InfoData) {
+    if iface_type == &IfaceType::IpVtap {
+        let nlas: Vec<DefaultNla> = nlas
+            .iter()
+            .map(|nla| {
+                let mut value = vec![0u8; nla.value_len()];
+                nla.emit_value(&mut value);
+                DefaultNla::new(nla.kind(), value)
+            })
+            .collect();
+        let mut raw = vec![0u8; nlas.as_slice().buffer_len()];
+        nlas.as_slice().emit(&mut raw);
+        (
+            InfoKind::Other(IPVTAP_KIND.to_string()),
+            InfoData::Other(raw),
+        )
+    } else {
+        (InfoKind::IpVlan, InfoData::IpVlan(nlas))
+    }
+}
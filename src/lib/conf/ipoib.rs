@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::{InfoData, InfoIpoib, InfoKind};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{change_iface_link_info, create_iface_with_link_info};
+use crate::{Iface, IpoibInfo, IpoibMode, NisporError};
+
+/// Configuration for IPoIB child interface.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpoibConf {
+    /// Required when creating new interface, cannot be changed afterwards.
+    pub base_iface: Option<String>,
+    /// Partition key. Use the P_Key of base interface if not defined.
+    /// Cannot be changed after creation.
+    pub pkey: Option<u16>,
+    pub mode: Option<IpoibMode>,
+    /// Allow user space multicast
+    pub umcast: Option<bool>,
+}
+
+impl IpoibConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let base_iface_index = match self
+            .base_iface
+            .as_ref()
+            .and_then(|b| cur_iface_name_2_index.get(b))
+        {
+            Some(i) => *i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Base interface {:?} for IPoIB {} not found",
+                    self.base_iface, name
+                )));
+            }
+        };
+        let mut nlas = Vec::new();
+        if let Some(v) = self.pkey {
+            nlas.push(InfoIpoib::Pkey(v));
+        }
+        nlas.extend(self.to_mutable_nlas());
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::Ipoib,
+            Some(InfoData::Ipoib(nlas)),
+            Some(base_iface_index),
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new ipoib '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.ipoib.as_ref() {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not IPoIB",
+                    cur_iface.name
+                )));
+            }
+        };
+        if let Some(base_iface) = self.base_iface.as_ref() {
+            if Some(base_iface) != cur_info.base_iface.as_ref() {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change base interface of IPoIB {} from {:?} \
+                    to {}",
+                    cur_iface.name, cur_info.base_iface, base_iface
+                )));
+            }
+        }
+        if let Some(pkey) = self.pkey {
+            if pkey != cur_info.pkey {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change pkey of IPoIB {} from {:#x} to {:#x}",
+                    cur_iface.name, cur_info.pkey, pkey
+                )));
+            }
+        }
+        let cur_nlas = IpoibConf::from(cur_info).to_mutable_nlas();
+        let changed_nlas: Vec<InfoIpoib> = self
+            .to_mutable_nlas()
+            .into_iter()
+            .filter(|nla| !cur_nlas.contains(nla))
+            .collect();
+        if !changed_nlas.is_empty() {
+            change_iface_link_info(
+                cur_iface.index,
+                InfoKind::Ipoib,
+                InfoData::Ipoib(changed_nlas),
+            )?;
+        }
+        Ok(())
+    }
+
+    // Kernel IPoIB driver only allows changing these properties
+    fn to_mutable_nlas(&self) -> Vec<InfoIpoib> {
+        let mut nlas = Vec::new();
+        if let Some(v) = self.mode {
+            nlas.push(InfoIpoib::Mode(v.into()));
+        }
+        if let Some(v) = self.umcast {
+            nlas.push(InfoIpoib::UmCast(v.into()));
+        }
+        nlas
+    }
+}
+
+impl From<&IpoibInfo> for IpoibConf {
+    fn from(info: &IpoibInfo) -> Self {
+        Self {
+            base_iface: info.base_iface.clone(),
+            pkey: Some(info.pkey),
+            mode: Some(info.mode),
+            umcast: Some(info.umcast),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::{InfoData, InfoKind, InfoMacVlan};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{change_iface_link_info, create_iface_with_link_info};
+use crate::{
+    mac::mac_str_to_raw, Iface, MacVlanInfo, MacVlanMode, NisporError,
+};
+
+const MACVLAN_FLAG_NOPROMISC: u16 = 1;
+
+const MACVLAN_MACADDR_ADD: u32 = 0;
+const MACVLAN_MACADDR_DEL: u32 = 1;
+const MACVLAN_MACADDR_FLUSH: u32 = 2;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacVlanConf {
+    /// Required when creating new interface, cannot be changed afterwards.
+    pub base_iface: Option<String>,
+    pub mode: Option<MacVlanMode>,
+    /// Do not put the base interface into promiscuous mode.
+    pub nopromisc: Option<bool>,
+    /// Add MAC addresses to the allowed source MAC address list of `source`
+    /// mode.
+    pub add_allowed_mac_addresses: Option<Vec<String>>,
+    /// Remove MAC addresses from the allowed source MAC address list of
+    /// `source` mode.
+    pub del_allowed_mac_addresses: Option<Vec<String>>,
+    /// Remove all MAC addresses from the allowed source MAC address list of
+    /// `source` mode before adding `add_allowed_mac_addresses`.
+    #[serde(default)]
+    pub flush_allowed_mac_addresses: bool,
+}
+
+impl MacVlanConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let (base_iface_index, nlas) =
+            self.gen_create_nlas(name, "MAC VLAN", cur_iface_name_2_index)?;
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::MacVlan,
+            Some(InfoData::MacVlan(nlas)),
+            Some(base_iface_index),
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new macvlan '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.mac_vlan.as_ref() {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not MAC VLAN",
+                    cur_iface.name
+                )));
+            }
+        };
+        for nlas in self.gen_change_nlas(&cur_iface.name, cur_info)? {
+            change_iface_link_info(
+                cur_iface.index,
+                InfoKind::MacVlan,
+                InfoData::MacVlan(nlas),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn gen_create_nlas(
+        &self,
+        name: &str,
+        type_name: &str,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(u32, Vec<InfoMacVlan>), NisporError> {
+        let base_iface = match self.base_iface.as_ref() {
+            Some(b) => b,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "No base interface defined for new {type_name} {name}"
+                )));
+            }
+        };
+        let base_iface_index = match cur_iface_name_2_index.get(base_iface) {
+            Some(i) => *i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Base interface {base_iface} for {type_name} {name} \
+                    not found"
+                )));
+            }
+        };
+        let mut nlas = Vec::new();
+        if let Some(mode) = self.mode.as_ref() {
+            nlas.push(InfoMacVlan::Mode(mode.clone().into()));
+        }
+        if self.nopromisc == Some(true) {
+            nlas.push(InfoMacVlan::Flags(MACVLAN_FLAG_NOPROMISC));
+        }
+        Ok((base_iface_index, nlas))
+    }
+
+    // Kernel only allows single source MAC address operation in each
+    // request, hence return a list of requests.
+    pub(crate) fn gen_change_nlas(
+        &self,
+        name: &str,
+        cur_info: &MacVlanInfo,
+    ) -> Result<Vec<Vec<InfoMacVlan>>, NisporError> {
+        if let Some(base_iface) = self.base_iface.as_ref() {
+            if base_iface != &cur_info.base_iface {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change base interface of {} from {} to {}",
+                    name, cur_info.base_iface, base_iface
+                )));
+            }
+        }
+        let mut ret = Vec::new();
+
+        let mut mode_nlas = Vec::new();
+        let mode = self.mode.as_ref().unwrap_or(&cur_info.mode);
+        if mode != &cur_info.mode {
+            mode_nlas.push(InfoMacVlan::Mode(mode.clone().into()));
+        }
+        if let Some(nopromisc) = self.nopromisc {
+            let flags = if nopromisc {
+                cur_info.flags | MACVLAN_FLAG_NOPROMISC
+            } else {
+                cur_info.flags & !MACVLAN_FLAG_NOPROMISC
+            };
+            if flags != cur_info.flags {
+                mode_nlas.push(InfoMacVlan::Flags(flags));
+            }
+        }
+        if !mode_nlas.is_empty() {
+            ret.push(mode_nlas);
+        }
+
+        if !self.flush_allowed_mac_addresses
+            && self.add_allowed_mac_addresses.is_none()
+            && self.del_allowed_mac_addresses.is_none()
+        {
+            return Ok(ret);
+        }
+        if mode != &MacVlanMode::Source {
+            return Err(NisporError::invalid_argument(format!(
+                "Allowed MAC addresses are only supported by source mode, \
+                but {name} is in {mode:?} mode"
+            )));
+        }
+
+        // Changing to source mode from other mode has empty MAC list
+        let mut cur_macs = Vec::new();
+        if cur_info.mode == MacVlanMode::Source {
+            for mac in cur_info.allowed_mac_addresses.as_deref().unwrap_or(&[])
+            {
+                cur_macs.push(mac_str_to_raw(mac)?);
+            }
+        }
+        if self.flush_allowed_mac_addresses && !cur_macs.is_empty() {
+            ret.push(vec![InfoMacVlan::MacAddrMode(MACVLAN_MACADDR_FLUSH)]);
+            cur_macs.clear();
+        }
+        for mac in self.del_allowed_mac_addresses.as_deref().unwrap_or(&[]) {
+            let mac = mac_str_to_raw(mac)?;
+            if let Some(pos) = cur_macs.iter().position(|m| m == &mac) {
+                cur_macs.remove(pos);
+                ret.push(vec![
+                    InfoMacVlan::MacAddrMode(MACVLAN_MACADDR_DEL),
+                    InfoMacVlan::MacAddr(to_mac_bytes(&mac)?),
+                ]);
+            }
+        }
+        for mac in self.add_allowed_mac_addresses.as_deref().unwrap_or(&[]) {
+            let mac = mac_str_to_raw(mac)?;
+            if !cur_macs.contains(&mac) {
+                ret.push(vec![
+                    InfoMacVlan::MacAddrMode(MACVLAN_MACADDR_ADD),
+                    InfoMacVlan::MacAddr(to_mac_bytes(&mac)?),
+                ]);
+                cur_macs.push(mac);
+            }
+        }
+        Ok(ret)
+    }
+}
+
+fn to_mac_bytes(mac: &[u8]) -> Result<[u8; 6], NisporError> {
+    let mut ret = [0u8; 6];
+    if mac.len() != ret.len() {
+        return Err(NisporError::invalid_argument(format!(
+            "Invalid ethernet MAC address {mac:?}"
+        )));
+    }
+    ret.copy_from_slice(mac);
+    Ok(ret)
+}
@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::{
+    InfoData, InfoKind, InfoMacVlan, InfoMacVtap,
+};
+use netlink_packet_utils::nla::{DefaultNla, Nla};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{change_iface_link_info, create_iface_with_link_info};
+use crate::{
+    Iface, MacVlanConf, MacVlanInfo, MacVlanMode, MacVtapMode, NisporError,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacVtapConf {
+    /// Required when creating new interface, cannot be changed afterwards.
+    pub base_iface: Option<String>,
+    pub mode: Option<MacVtapMode>,
+    /// Do not put the base interface into promiscuous mode.
+    pub nopromisc: Option<bool>,
+    /// Add MAC addresses to the allowed source MAC address list of `source`
+    /// mode.
+    pub add_allowed_mac_addresses: Option<Vec<String>>,
+    /// Remove MAC addresses from the allowed source MAC address list of
+    /// `source` mode.
+    pub del_allowed_mac_addresses: Option<Vec<String>>,
+    /// Remove all MAC addresses from the allowed source MAC address list of
+    /// `source` mode before adding `add_allowed_mac_addresses`.
+    #[serde(default)]
+    pub flush_allowed_mac_addresses: bool,
+}
+
+impl From<&MacVtapConf> for MacVlanConf {
+    fn from(d: &MacVtapConf) -> Self {
+        Self {
+            base_iface: d.base_iface.clone(),
+            mode: d.mode.clone().map(MacVlanMode::from),
+            nopromisc: d.nopromisc,
+            add_allowed_mac_addresses: d.add_allowed_mac_addresses.clone(),
+            del_allowed_mac_addresses: d.del_allowed_mac_addresses.clone(),
+            flush_allowed_mac_addresses: d.flush_allowed_mac_addresses,
+        }
+    }
+}
+
+impl MacVtapConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let (base_iface_index, nlas) = MacVlanConf::from(self)
+            .gen_create_nlas(name, "MAC VTAP", cur_iface_name_2_index)?;
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::MacVtap,
+            Some(InfoData::MacVtap(to_mac_vtap_nlas(&nlas))),
+            Some(base_iface_index),
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new macvtap '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.mac_vtap.as_ref() {
+            Some(i) => MacVlanInfo::from(i.clone()),
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not MAC VTAP",
+                    cur_iface.name
+                )));
+            }
+        };
+        for nlas in MacVlanConf::from(self)
+            .gen_change_nlas(&cur_iface.name, &cur_info)?
+        {
+            change_iface_link_info(
+                cur_iface.index,
+                InfoKind::MacVtap,
+                InfoData::MacVtap(to_mac_vtap_nlas(&nlas)),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The MAC VTAP is sharing the same netlink attributes with MAC VLAN.
+fn to_mac_vtap_nlas(nlas: &[InfoMacVlan]) -> Vec<InfoMacVtap> {
+    nlas.iter()
+        .map(|nla| {
+            let mut value = vec![0u8; nla.value_len()];
+            nla.emit_value(&mut value);
+            InfoMacVtap::Other(DefaultNla::new(nla.kind(), value))
+        })
+        .collect()
+}
@@ -0,0 +1,521 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::{self, InfoData, InfoKind, InfoMacSec};
+use netlink_packet_utils::nla::DefaultNla;
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{change_iface_link_info, create_iface_with_link_info};
+use crate::{
+    netlink::{
+        macsec_rx_sc_request, macsec_sa_request, MACSEC_CMD_ADD_RXSA,
+        MACSEC_CMD_ADD_RXSC, MACSEC_CMD_ADD_TXSA, MACSEC_CMD_DEL_RXSA,
+        MACSEC_CMD_DEL_RXSC, MACSEC_CMD_DEL_TXSA, MACSEC_CMD_UPD_RXSA,
+        MACSEC_CMD_UPD_RXSC, MACSEC_CMD_UPD_TXSA, MACSEC_KEYID_LEN,
+        MACSEC_SA_ATTR_ACTIVE, MACSEC_SA_ATTR_AN, MACSEC_SA_ATTR_KEY,
+        MACSEC_SA_ATTR_KEYID, MACSEC_SA_ATTR_PN, MACSEC_SA_ATTR_SALT,
+        MACSEC_SA_ATTR_SSCI,
+    },
+    Iface, MacSecCipherId, MacSecInfo, MacSecOffload, MacSecRxScInfo,
+    MacSecSaInfo, MacSecValidate, NisporError,
+};
+
+const MACSEC_MAX_AN: u8 = 3;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacSecConf {
+    /// Required when creating new interface, cannot be changed afterwards.
+    pub base_iface: Option<String>,
+    /// Cannot be changed after creation.
+    pub sci: Option<u64>,
+    /// Cannot be changed after creation.
+    pub port: Option<u16>,
+    /// Cannot be changed after creation.
+    pub cipher: Option<MacSecCipherId>,
+    /// Cannot be changed after creation.
+    pub icv_len: Option<u8>,
+    pub encoding_sa: Option<u8>,
+    pub encrypt: Option<bool>,
+    pub protect: Option<bool>,
+    pub send_sci: Option<bool>,
+    pub end_station: Option<bool>,
+    pub scb: Option<bool>,
+    pub replay_protect: Option<bool>,
+    pub window: Option<u32>,
+    pub validate: Option<MacSecValidate>,
+    pub offload: Option<MacSecOffload>,
+    pub tx_sa: Option<Vec<MacSecSaConf>>,
+    pub rx_sc: Option<Vec<MacSecRxScConf>>,
+}
+
+/// MACsec Secure Association.
+/// Kernel does not support changing the key of existing SA, please use
+/// different `key_id` to replace the SA with new key.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacSecSaConf {
+    /// Association number, 0 to 3.
+    pub an: u8,
+    pub pn: Option<u64>,
+    pub active: Option<bool>,
+    /// Hex string of key identifier, up to 128 bits. Required when creating
+    /// new SA.
+    pub key_id: Option<String>,
+    /// Hex string of the key. Required when creating new SA.
+    #[serde(skip_serializing)]
+    pub key: Option<String>,
+    /// Short Secure Channel Identifier, required by XPN cipher.
+    pub ssci: Option<u32>,
+    /// Hex string of 96 bits salt, required by XPN cipher.
+    #[serde(skip_serializing)]
+    pub salt: Option<String>,
+    #[serde(default)]
+    pub remove: bool,
+}
+
+impl std::fmt::Debug for MacSecSaConf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MacSecSaConf")
+            .field("an", &self.an)
+            .field("pn", &self.pn)
+            .field("active", &self.active)
+            .field("key_id", &self.key_id)
+            .field("key", &self.key.as_ref().map(|_| "<hidden>"))
+            .field("ssci", &self.ssci)
+            .field("salt", &self.salt.as_ref().map(|_| "<hidden>"))
+            .field("remove", &self.remove)
+            .finish()
+    }
+}
+
+/// MACsec receive Secure Channel.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacSecRxScConf {
+    pub sci: u64,
+    pub active: Option<bool>,
+    pub sa: Option<Vec<MacSecSaConf>>,
+    #[serde(default)]
+    pub remove: bool,
+}
+
+impl MacSecConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let base_iface_index = match self
+            .base_iface
+            .as_ref()
+            .and_then(|b| cur_iface_name_2_index.get(b))
+        {
+            Some(i) => *i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Base interface {:?} for MACsec {} not found",
+                    self.base_iface, name
+                )));
+            }
+        };
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::MacSec,
+            Some(InfoData::MacSec(self.to_nlas())),
+            Some(base_iface_index),
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new macsec '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.macsec.as_ref() {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not MACsec",
+                    cur_iface.name
+                )));
+            }
+        };
+        if let Some(base_iface) = self.base_iface.as_ref() {
+            if Some(base_iface) != cur_info.base_iface.as_ref() {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change base interface of MACsec {} from {:?} \
+                    to {}",
+                    cur_iface.name, cur_info.base_iface, base_iface
+                )));
+            }
+        }
+        let cur_nlas = MacSecConf::from(cur_info).to_nlas();
+        let mut changed_nlas = Vec::new();
+        for nla in self.to_nlas() {
+            if cur_nlas.contains(&nla) {
+                continue;
+            }
+            if matches!(
+                nla,
+                InfoMacSec::Sci(_)
+                    | InfoMacSec::Port(_)
+                    | InfoMacSec::CipherSuite(_)
+                    | InfoMacSec::IcvLen(_)
+            ) {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change {:?} of existing MACsec interface {}",
+                    nla, cur_iface.name
+                )));
+            }
+            changed_nlas.push(nla);
+        }
+        if !changed_nlas.is_empty() {
+            change_iface_link_info(
+                cur_iface.index,
+                InfoKind::MacSec,
+                InfoData::MacSec(changed_nlas),
+            )?;
+        }
+
+        let is_xpn = self.cipher.unwrap_or(cur_info.cipher).is_xpn();
+        if let Some(sas) = self.tx_sa.as_ref() {
+            apply_sas_conf(
+                cur_iface.index,
+                None,
+                sas,
+                cur_info.tx_sa.as_deref().unwrap_or(&[]),
+                is_xpn,
+            )?;
+        }
+        if let Some(scs) = self.rx_sc.as_ref() {
+            apply_rx_scs_conf(
+                cur_iface.index,
+                scs,
+                cur_info.rx_sc.as_deref().unwrap_or(&[]),
+                is_xpn,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn to_nlas(&self) -> Vec<InfoMacSec> {
+        let mut nlas = Vec::new();
+        if let Some(v) = self.sci {
+            nlas.push(InfoMacSec::Sci(v));
+        }
+        if let Some(v) = self.port {
+            nlas.push(InfoMacSec::Port(v));
+        }
+        if let Some(v) = self.cipher {
+            nlas.push(InfoMacSec::CipherSuite(link::MacSecCipherId::from(
+                u64::from(v),
+            )));
+        }
+        if let Some(v) = self.icv_len {
+            nlas.push(InfoMacSec::IcvLen(v));
+        }
+        if let Some(v) = self.encoding_sa {
+            nlas.push(InfoMacSec::EncodingSa(v));
+        }
+        if let Some(v) = self.encrypt {
+            nlas.push(InfoMacSec::Encrypt(v.into()));
+        }
+        if let Some(v) = self.protect {
+            nlas.push(InfoMacSec::Protect(v.into()));
+        }
+        if let Some(v) = self.send_sci {
+            nlas.push(InfoMacSec::IncSci(v.into()));
+        }
+        if let Some(v) = self.end_station {
+            nlas.push(InfoMacSec::Es(v.into()));
+        }
+        if let Some(v) = self.scb {
+            nlas.push(InfoMacSec::Scb(v.into()));
+        }
+        if let Some(v) = self.replay_protect {
+            nlas.push(InfoMacSec::ReplayProtect(v.into()));
+        }
+        if let Some(v) = self.window {
+            nlas.push(InfoMacSec::Window(v));
+        }
+        if let Some(v) = self.validate {
+            nlas.push(InfoMacSec::Validation(link::MacSecValidate::from(
+                u8::from(v),
+            )));
+        }
+        if let Some(v) = self.offload {
+            nlas.push(InfoMacSec::Offload(link::MacSecOffload::from(
+                u8::from(v),
+            )));
+        }
+        nlas
+    }
+}
+
+impl From<&MacSecInfo> for MacSecConf {
+    fn from(info: &MacSecInfo) -> Self {
+        Self {
+            base_iface: info.base_iface.clone(),
+            sci: Some(info.sci),
+            port: Some(info.port),
+            cipher: Some(info.cipher),
+            icv_len: Some(info.icv_len),
+            encoding_sa: Some(info.encoding_sa),
+            encrypt: Some(info.encrypt),
+            protect: Some(info.protect),
+            send_sci: Some(info.send_sci),
+            end_station: Some(info.end_station),
+            scb: Some(info.scb),
+            replay_protect: Some(info.replay_protect),
+            window: Some(info.window),
+            validate: Some(info.validate),
+            offload: Some(info.offload),
+            tx_sa: None,
+            rx_sc: None,
+        }
+    }
+}
+
+fn apply_rx_scs_conf(
+    iface_index: u32,
+    scs: &[MacSecRxScConf],
+    cur_scs: &[MacSecRxScInfo],
+    is_xpn: bool,
+) -> Result<(), NisporError> {
+    for sc in scs {
+        let cur_sc = cur_scs.iter().find(|s| s.sci == sc.sci);
+        if sc.remove {
+            if cur_sc.is_some() {
+                macsec_rx_sc_request(
+                    MACSEC_CMD_DEL_RXSC,
+                    iface_index,
+                    sc.sci,
+                    None,
+                )?;
+            }
+            continue;
+        }
+        match cur_sc {
+            Some(cur_sc) => {
+                if sc.active.is_some() && sc.active != Some(cur_sc.active) {
+                    macsec_rx_sc_request(
+                        MACSEC_CMD_UPD_RXSC,
+                        iface_index,
+                        sc.sci,
+                        sc.active,
+                    )?;
+                }
+            }
+            None => {
+                macsec_rx_sc_request(
+                    MACSEC_CMD_ADD_RXSC,
+                    iface_index,
+                    sc.sci,
+                    sc.active,
+                )?;
+            }
+        }
+        if let Some(sas) = sc.sa.as_ref() {
+            apply_sas_conf(
+                iface_index,
+                Some(sc.sci),
+                sas,
+                cur_sc.map(|s| s.sa.as_slice()).unwrap_or(&[]),
+                is_xpn,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// The `rx_sci` should be None for TX SA.
+fn apply_sas_conf(
+    iface_index: u32,
+    rx_sci: Option<u64>,
+    sas: &[MacSecSaConf],
+    cur_sas: &[MacSecSaInfo],
+    is_xpn: bool,
+) -> Result<(), NisporError> {
+    let (add_cmd, upd_cmd) = if rx_sci.is_some() {
+        (MACSEC_CMD_ADD_RXSA, MACSEC_CMD_UPD_RXSA)
+    } else {
+        (MACSEC_CMD_ADD_TXSA, MACSEC_CMD_UPD_TXSA)
+    };
+    for sa in sas {
+        if sa.an > MACSEC_MAX_AN {
+            return Err(NisporError::invalid_argument(format!(
+                "Invalid MACsec association number {}, should be 0 to {}",
+                sa.an, MACSEC_MAX_AN
+            )));
+        }
+        let an_nla = DefaultNla::new(MACSEC_SA_ATTR_AN, vec![sa.an]);
+        let mut cur_sa = cur_sas.iter().find(|s| s.an == sa.an);
+        let key_id = match sa.key_id.as_deref() {
+            Some(k) => Some(parse_key_id(k)?),
+            None => None,
+        };
+        if let (Some(cur), Some(key_id)) = (cur_sa, key_id.as_ref()) {
+            if &parse_key_id(&cur.key_id)? != key_id {
+                // Kernel cannot change key of existing SA
+                del_sa(iface_index, rx_sci, cur, &an_nla)?;
+                cur_sa = None;
+            }
+        }
+        if sa.remove {
+            if let Some(cur) = cur_sa {
+                del_sa(iface_index, rx_sci, cur, &an_nla)?;
+            }
+            continue;
+        }
+        let mut nlas = vec![an_nla];
+        match cur_sa {
+            Some(cur_sa) => {
+                if let Some(pn) = sa.pn.filter(|pn| *pn != cur_sa.pn) {
+                    nlas.push(gen_pn_nla(pn, is_xpn)?);
+                }
+                if let Some(active) =
+                    sa.active.filter(|active| *active != cur_sa.active)
+                {
+                    nlas.push(DefaultNla::new(
+                        MACSEC_SA_ATTR_ACTIVE,
+                        vec![active.into()],
+                    ));
+                }
+                if nlas.len() > 1 {
+                    macsec_sa_request(upd_cmd, iface_index, rx_sci, &nlas)?;
+                }
+            }
+            None => {
+                let (key_id, key) = match (key_id, sa.key.as_deref()) {
+                    (Some(key_id), Some(key)) => (key_id, parse_hex(key)?),
+                    _ => {
+                        return Err(NisporError::invalid_argument(format!(
+                            "Both key_id and key are required for new \
+                            MACsec SA {}",
+                            sa.an
+                        )));
+                    }
+                };
+                nlas.push(DefaultNla::new(MACSEC_SA_ATTR_KEYID, key_id));
+                nlas.push(DefaultNla::new(MACSEC_SA_ATTR_KEY, key));
+                if let Some(pn) = sa.pn {
+                    nlas.push(gen_pn_nla(pn, is_xpn)?);
+                }
+                if let Some(active) = sa.active {
+                    nlas.push(DefaultNla::new(
+                        MACSEC_SA_ATTR_ACTIVE,
+                        vec![active.into()],
+                    ));
+                }
+                if let Some(ssci) = sa.ssci {
+                    nlas.push(DefaultNla::new(
+                        MACSEC_SA_ATTR_SSCI,
+                        ssci.to_ne_bytes().to_vec(),
+                    ));
+                }
+                if let Some(salt) = sa.salt.as_deref() {
+                    nlas.push(DefaultNla::new(
+                        MACSEC_SA_ATTR_SALT,
+                        parse_hex(salt)?,
+                    ));
+                }
+                macsec_sa_request(add_cmd, iface_index, rx_sci, &nlas)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Kernel refuses to delete active SA with EBUSY, hence deactivate it first.
+fn del_sa(
+    iface_index: u32,
+    rx_sci: Option<u64>,
+    cur_sa: &MacSecSaInfo,
+    an_nla: &DefaultNla,
+) -> Result<(), NisporError> {
+    let (del_cmd, upd_cmd) = if rx_sci.is_some() {
+        (MACSEC_CMD_DEL_RXSA, MACSEC_CMD_UPD_RXSA)
+    } else {
+        (MACSEC_CMD_DEL_TXSA, MACSEC_CMD_UPD_TXSA)
+    };
+    if cur_sa.active {
+        macsec_sa_request(
+            upd_cmd,
+            iface_index,
+            rx_sci,
+            &[
+                an_nla.clone(),
+                DefaultNla::new(MACSEC_SA_ATTR_ACTIVE, vec![0]),
+            ],
+        )?;
+    }
+    macsec_sa_request(
+        del_cmd,
+        iface_index,
+        rx_sci,
+        std::slice::from_ref(an_nla),
+    )
+}
+
+// Non-XPN cipher is using 32 bits packet number
+fn gen_pn_nla(pn: u64, is_xpn: bool) -> Result<DefaultNla, NisporError> {
+    if is_xpn {
+        Ok(DefaultNla::new(
+            MACSEC_SA_ATTR_PN,
+            pn.to_ne_bytes().to_vec(),
+        ))
+    } else {
+        match u32::try_from(pn) {
+            Ok(pn) => Ok(DefaultNla::new(
+                MACSEC_SA_ATTR_PN,
+                pn.to_ne_bytes().to_vec(),
+            )),
+            Err(_) => Err(NisporError::invalid_argument(format!(
+                "MACsec packet number {pn} is too big for non-XPN cipher"
+            ))),
+        }
+    }
+}
+
+fn parse_key_id(key_id: &str) -> Result<Vec<u8>, NisporError> {
+    let mut ret = parse_hex(key_id)?;
+    if ret.len() > MACSEC_KEYID_LEN {
+        return Err(NisporError::invalid_argument(format!(
+            "MACsec key ID should not be longer than {MACSEC_KEYID_LEN} bytes"
+        )));
+    }
+    ret.resize(MACSEC_KEYID_LEN, 0);
+    Ok(ret)
+}
+
+fn parse_hex(hex: &str) -> Result<Vec<u8>, NisporError> {
+    if hex.len() % 2 != 0 {
+        return Err(NisporError::invalid_argument(
+            "Hex string should have even length".into(),
+        ));
+    }
+    let mut ret = Vec::new();
+    for i in (0..hex.len()).step_by(2) {
+        match hex
+            .get(i..i + 2)
+            .and_then(|s| u8::from_str_radix(s, 16).ok())
+        {
+            Some(b) => ret.push(b),
+            None => {
+                return Err(NisporError::invalid_argument(
+                    "Invalid hex string".into(),
+                ));
+            }
+        }
+    }
+    Ok(ret)
+}
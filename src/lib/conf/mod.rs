@@ -2,22 +2,67 @@
 
 mod bond;
 mod bridge;
+mod devlink;
+mod ethtool;
+mod geneve;
+mod gre;
+mod hsr;
 mod iface;
 mod inter_ifaces;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
+mod ipoib;
+mod mac_vlan;
+mod mac_vtap;
+mod macsec;
 mod route;
+mod route_rule;
+mod sriov;
+mod tun;
 mod veth;
 mod vlan;
+mod vrf;
+mod vxlan;
+mod wireguard;
+mod xfrm;
 
 pub use self::bond::BondConf;
-pub use self::bridge::BridgeConf;
+pub use self::bridge::{
+    BridgeConf, BridgePortConf, BridgeVlanGlobalOptsConf, BridgeVlanOptsConf,
+};
+pub use self::devlink::{
+    DevlinkConf, DevlinkDeviceConf, DevlinkParamConf, DevlinkSfConf,
+};
+pub use self::ethtool::{
+    EthtoolCoalesceConf, EthtoolConf, EthtoolLinkModeConf, EthtoolPauseConf,
+    EthtoolRingConf, EthtoolRssConf,
+};
+pub use self::geneve::GeneveConf;
+pub use self::gre::GreConf;
+pub use self::hsr::HsrConf;
 pub use self::iface::IfaceConf;
 pub use self::ip::{IpAddrConf, IpConf};
+pub use self::ip_tunnel::IpTunnelConf;
+pub use self::ip_vlan::IpVlanConf;
+pub use self::ipoib::IpoibConf;
+pub use self::mac_vlan::MacVlanConf;
+pub use self::mac_vtap::MacVtapConf;
+pub use self::macsec::{MacSecConf, MacSecRxScConf, MacSecSaConf};
 pub use self::route::RouteConf;
+pub use self::route_rule::RouteRuleConf;
+pub use self::sriov::{SriovConf, VfConf};
+pub use self::tun::TunConf;
 pub use self::veth::VethConf;
 pub use self::vlan::VlanConf;
+pub use self::vrf::VrfConf;
+pub use self::vxlan::VxlanConf;
+pub use self::wireguard::{WireguardConf, WireguardPeerConf};
+pub use self::xfrm::XfrmConf;
 
+pub(crate) use self::devlink::apply_devlink_conf;
 pub(crate) use self::inter_ifaces::{
     change_ifaces, create_ifaces, delete_ifaces,
 };
 pub(crate) use self::route::apply_routes_conf;
+pub(crate) use self::route_rule::apply_route_rules_conf;
@@ -10,9 +10,14 @@ use netlink_packet_route::{
 
 use serde::{Deserialize, Serialize};
 
-use super::super::query::{parse_ip_addr_str, parse_ip_net_addr_str};
+use super::{
+    super::query::{parse_ip_addr_str, parse_ip_net_addr_str},
+    vrf::{get_vrf_table_ids, vrf_name_to_table_id},
+};
 use crate::{NisporError, RouteProtocol};
 
+const RT_TABLE_COMPAT: u8 = 252;
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct RouteConf {
@@ -23,6 +28,8 @@ pub struct RouteConf {
     pub via: Option<String>,
     pub metric: Option<u32>,
     pub table: Option<u8>,
+    /// Place the route into the route table of specified VRF interface.
+    pub vrf: Option<String>,
     pub protocol: Option<RouteProtocol>,
 }
 
@@ -32,8 +39,17 @@ pub(crate) async fn apply_routes_conf(
 ) -> Result<(), NisporError> {
     let (connection, handle, _) = rtnetlink::new_connection()?;
     tokio::spawn(connection);
+    let vrf_table_ids = if routes.iter().any(|r| r.vrf.is_some()) {
+        get_vrf_table_ids().await?
+    } else {
+        HashMap::new()
+    };
     for route in routes {
-        apply_route_conf(&handle, route, iface_name_2_index).await?;
+        let vrf_table = match route.vrf.as_deref() {
+            Some(vrf) => Some(vrf_name_to_table_id(&vrf_table_ids, vrf)?),
+            None => None,
+        };
+        apply_route_conf(&handle, route, iface_name_2_index, vrf_table).await?;
     }
     Ok(())
 }
@@ -42,6 +58,7 @@ async fn apply_route_conf(
     handle: &rtnetlink::Handle,
     route: &RouteConf,
     iface_name_2_index: &HashMap<String, u32>,
+    vrf_table: Option<u32>,
 ) -> Result<(), NisporError> {
     let mut nl_msg = RouteMessage::default();
     nl_msg.header.kind = rt::RouteType::Unicast;
@@ -71,6 +88,20 @@ async fn apply_route_conf(
     if let Some(t) = route.table.as_ref() {
         nl_msg.header.table = *t;
     }
+    if let Some(vrf_table) = vrf_table {
+        if route.table.is_some()
+            && route.table.map(u32::from) != Some(vrf_table)
+        {
+            return Err(NisporError::invalid_argument(format!(
+                "Route table {:?} is conflicting with the route table {} \
+                of VRF {:?}",
+                route.table, vrf_table, route.vrf
+            )));
+        }
+        // The header only holds 8 bits table ID, use RTA_TABLE instead
+        nl_msg.header.table = RT_TABLE_COMPAT;
+        nl_msg.attributes.push(RouteAttribute::Table(vrf_table));
+    }
     if let Some(m) = route.metric.as_ref() {
         nl_msg.attributes.push(RouteAttribute::Priority(*m));
     }
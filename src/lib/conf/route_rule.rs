@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use netlink_packet_route::{
+    route::RouteHeader,
+    rule::{self, RuleAttribute, RuleMessage},
+};
+use serde::{Deserialize, Serialize};
+
+use super::vrf::{get_vrf_table_ids, vrf_name_to_table_id};
+use crate::{query::parse_ip_net_addr_str, AddressFamily, NisporError};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RouteRuleConf {
+    #[serde(default)]
+    pub remove: bool,
+    /// Required when neither `src` nor `dst` is defined.
+    pub address_family: Option<AddressFamily>,
+    pub priority: Option<u32>,
+    pub src: Option<String>,
+    pub dst: Option<String>,
+    pub iif: Option<String>,
+    pub oif: Option<String>,
+    pub fw_mark: Option<u32>,
+    pub fw_mask: Option<u32>,
+    pub table: Option<u32>,
+    /// Lookup the route table of specified VRF interface.
+    pub vrf: Option<String>,
+    pub l3mdev: Option<bool>,
+}
+
+pub(crate) async fn apply_route_rules_conf(
+    rules: &[RouteRuleConf],
+) -> Result<(), NisporError> {
+    let (connection, handle, _) = rtnetlink::new_connection()?;
+    tokio::spawn(connection);
+    let vrf_table_ids = if rules.iter().any(|r| r.vrf.is_some()) {
+        get_vrf_table_ids().await?
+    } else {
+        Default::default()
+    };
+    for rule in rules {
+        let table = match rule.vrf.as_deref() {
+            Some(vrf) => {
+                let vrf_table = vrf_name_to_table_id(&vrf_table_ids, vrf)?;
+                if rule.table.is_some() && rule.table != Some(vrf_table) {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Route rule table {:?} is conflicting with the \
+                        route table {} of VRF {}",
+                        rule.table, vrf_table, vrf
+                    )));
+                }
+                Some(vrf_table)
+            }
+            None => rule.table,
+        };
+        apply_route_rule_conf(&handle, rule, table).await?;
+    }
+    Ok(())
+}
+
+async fn apply_route_rule_conf(
+    handle: &rtnetlink::Handle,
+    rule: &RouteRuleConf,
+    table: Option<u32>,
+) -> Result<(), NisporError> {
+    let mut nl_msg = RuleMessage::default();
+    nl_msg.header.action = rule::RuleAction::ToTable;
+    nl_msg.header.table = RouteHeader::RT_TABLE_UNSPEC;
+    let mut family = rule.address_family.clone();
+    for (addr, is_dst) in
+        [(rule.src.as_deref(), false), (rule.dst.as_deref(), true)]
+    {
+        let addr = match addr {
+            Some(a) => a,
+            None => continue,
+        };
+        let (ip, prefix_len) = parse_ip_net_addr_str(addr)?;
+        let addr_family = match ip {
+            IpAddr::V4(_) => AddressFamily::IPv4,
+            IpAddr::V6(_) => AddressFamily::IPv6,
+        };
+        if let Some(f) = family.as_ref() {
+            if f != &addr_family {
+                return Err(NisporError::invalid_argument(format!(
+                    "Address {addr} is not matching route rule address \
+                    family {f:?}"
+                )));
+            }
+        }
+        family = Some(addr_family);
+        if is_dst {
+            nl_msg.header.dst_len = prefix_len;
+            nl_msg.attributes.push(RuleAttribute::Destination(ip));
+        } else {
+            nl_msg.header.src_len = prefix_len;
+            nl_msg.attributes.push(RuleAttribute::Source(ip));
+        }
+    }
+    match family {
+        Some(f) => nl_msg.header.family = f.into(),
+        None => {
+            return Err(NisporError::invalid_argument(
+                "The address_family is required for route rule without \
+                src or dst"
+                    .into(),
+            ));
+        }
+    }
+    if let Some(t) = table {
+        if t <= u8::MAX as u32 {
+            nl_msg.header.table = t as u8;
+        }
+        nl_msg.attributes.push(RuleAttribute::Table(t));
+    }
+    if let Some(p) = rule.priority {
+        nl_msg.attributes.push(RuleAttribute::Priority(p));
+    }
+    if let Some(iif) = rule.iif.as_ref() {
+        nl_msg
+            .attributes
+            .push(RuleAttribute::Iifname(iif.to_string()));
+    }
+    if let Some(oif) = rule.oif.as_ref() {
+        nl_msg
+            .attributes
+            .push(RuleAttribute::Oifname(oif.to_string()));
+    }
+    if let Some(m) = rule.fw_mark {
+        nl_msg.attributes.push(RuleAttribute::FwMark(m));
+    }
+    if let Some(m) = rule.fw_mask {
+        nl_msg.attributes.push(RuleAttribute::FwMask(m));
+    }
+    if let Some(l) = rule.l3mdev {
+        nl_msg.attributes.push(RuleAttribute::L3MDev(l));
+    }
+    if rule.remove {
+        if let Err(e) = handle.rule().del(nl_msg).execute().await {
+            if let rtnetlink::Error::NetlinkError(ref e) = e {
+                if e.raw_code() == -libc::ENOENT {
+                    return Ok(());
+                }
+            }
+            return Err(e.into());
+        }
+    } else {
+        let mut req = handle.rule().add();
+        *req.message_mut() = nl_msg;
+        if let Err(e) = req.execute().await {
+            if let rtnetlink::Error::NetlinkError(ref e) = e {
+                if e.raw_code() == -libc::EEXIST {
+                    return Ok(());
+                }
+            }
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
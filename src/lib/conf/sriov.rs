@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use netlink_packet_route::link::{
+    self, LinkAttribute, LinkMessage, LinkVfInfo, VfInfoLinkState, VfInfoMac,
+    VfInfoRate, VfInfoRssQueryEn, VfInfoSpoofCheck, VfInfoTrust, VfInfoVlan,
+    VfVlan, VfVlanInfo,
+};
+use netlink_packet_utils::Emitable;
+use netlink_sys::constants::NETLINK_ROUTE;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    mac::mac_str_to_raw,
+    netlink::{nl_request, NLM_F_ACK},
+    Iface, NisporError, VfInfo, VfLinkState, VlanProtocol,
+};
+
+const RTM_SETLINK: u16 = 19;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct SriovConf {
+    /// Number of VFs to enable, changing it will remove all existing VFs
+    /// first.
+    pub total_vfs: Option<u32>,
+    /// Only listed VFs and properties will be changed.
+    pub vfs: Option<Vec<VfConf>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct VfConf {
+    pub id: u32,
+    pub mac: Option<String>,
+    /// 0 disables VLAN filter
+    pub vlan_id: Option<u32>,
+    pub qos: Option<u32>,
+    pub vlan_proto: Option<VlanProtocol>,
+    /// Min Bandwidth in Mbps
+    pub min_tx_rate: Option<u32>,
+    /// Max Bandwidth in Mbps, 0 disables throttling
+    pub max_tx_rate: Option<u32>,
+    pub spoof_check: Option<bool>,
+    pub trust: Option<bool>,
+    pub link_state: Option<VfLinkState>,
+    pub query_rss: Option<bool>,
+}
+
+impl SriovConf {
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.sriov.as_ref() {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} does not support SR-IOV",
+                    cur_iface.name
+                )));
+            }
+        };
+        let mut cur_vfs = cur_info.vfs.as_slice();
+        let mut total_vfs = cur_vfs.len() as u32;
+        if let Some(des_total_vfs) = self.total_vfs {
+            if des_total_vfs != total_vfs {
+                set_total_vfs(&cur_iface.name, total_vfs, des_total_vfs)?;
+                total_vfs = des_total_vfs;
+                // Newly created VFs are using driver default settings
+                cur_vfs = &[];
+            }
+        }
+
+        let mut vf_nlas = Vec::new();
+        for vf_conf in self.vfs.as_deref().unwrap_or_default() {
+            if vf_conf.id >= total_vfs {
+                return Err(NisporError::invalid_argument(format!(
+                    "VF {} does not exist, interface {} only has {} VFs",
+                    vf_conf.id, cur_iface.name, total_vfs
+                )));
+            }
+            let nlas = vf_conf.to_changed_nlas(
+                cur_vfs.iter().find(|vf| vf.id == vf_conf.id),
+            )?;
+            if !nlas.is_empty() {
+                vf_nlas.push(LinkVfInfo(nlas));
+            }
+        }
+        if !vf_nlas.is_empty() {
+            set_vf_info_list(cur_iface.index, vf_nlas)?;
+        }
+        Ok(())
+    }
+}
+
+impl VfConf {
+    // When `cur` is None, all defined properties are included.
+    fn to_changed_nlas(
+        &self,
+        cur: Option<&VfInfo>,
+    ) -> Result<Vec<link::VfInfo>, NisporError> {
+        let mut nlas = Vec::new();
+        let id = self.id;
+
+        if let Some(mac) = self.mac.as_ref() {
+            if cur.map(|c| c.mac.to_lowercase()) != Some(mac.to_lowercase()) {
+                nlas.push(link::VfInfo::Mac(VfInfoMac::new(
+                    id,
+                    &mac_str_to_raw(mac)?,
+                )));
+            }
+        }
+
+        if self.vlan_id.is_some()
+            || self.qos.is_some()
+            || self.vlan_proto.is_some()
+        {
+            let cur_vlan = cur.map(|c| (c.vlan_id, c.qos, c.vlan_proto));
+            let des_vlan = (
+                self.vlan_id
+                    .or_else(|| cur.map(|c| c.vlan_id))
+                    .unwrap_or_default(),
+                self.qos.or_else(|| cur.map(|c| c.qos)).unwrap_or_default(),
+                self.vlan_proto
+                    .or_else(|| cur.map(|c| c.vlan_proto))
+                    .unwrap_or_default(),
+            );
+            if cur_vlan != Some(des_vlan) {
+                let (vlan_id, qos, vlan_proto) = des_vlan;
+                // Legacy IFLA_VF_VLAN resets VLAN protocol to 802.1Q
+                if vlan_proto != VlanProtocol::Ieee8021Q
+                    || cur
+                        .map(|c| c.vlan_proto != VlanProtocol::Ieee8021Q)
+                        .unwrap_or_default()
+                {
+                    nlas.push(link::VfInfo::VlanList(vec![VfVlan::Info(
+                        VfVlanInfo::new(
+                            id,
+                            vlan_id,
+                            qos,
+                            vlan_proto_to_link(vlan_proto)?,
+                        ),
+                    )]));
+                } else {
+                    nlas.push(link::VfInfo::Vlan(VfInfoVlan::new(
+                        id, vlan_id, qos,
+                    )));
+                }
+            }
+        }
+
+        if self.min_tx_rate.is_some() || self.max_tx_rate.is_some() {
+            let cur_rate = cur.map(|c| (c.min_tx_rate, c.max_tx_rate));
+            let des_rate = (
+                self.min_tx_rate
+                    .or_else(|| cur.map(|c| c.min_tx_rate))
+                    .unwrap_or_default(),
+                self.max_tx_rate
+                    .or_else(|| cur.map(|c| c.max_tx_rate))
+                    .unwrap_or_default(),
+            );
+            if cur_rate != Some(des_rate) {
+                nlas.push(link::VfInfo::Rate(VfInfoRate::new(
+                    id, des_rate.0, des_rate.1,
+                )));
+            }
+        }
+
+        if let Some(v) = self.spoof_check {
+            if cur.map(|c| c.spoof_check) != Some(v) {
+                nlas.push(link::VfInfo::SpoofCheck(VfInfoSpoofCheck::new(
+                    id, v,
+                )));
+            }
+        }
+        if let Some(v) = self.trust {
+            if cur.map(|c| c.trust) != Some(v) {
+                nlas.push(link::VfInfo::Trust(VfInfoTrust::new(id, v)));
+            }
+        }
+        if let Some(v) = self.link_state.as_ref() {
+            if cur.map(|c| &c.link_state) != Some(v) {
+                nlas.push(link::VfInfo::LinkState(VfInfoLinkState::new(
+                    id,
+                    vf_link_state_to_link(v)?,
+                )));
+            }
+        }
+        if let Some(v) = self.query_rss {
+            if cur.map(|c| c.query_rss) != Some(v) {
+                nlas.push(link::VfInfo::RssQueryEn(VfInfoRssQueryEn::new(
+                    id, v,
+                )));
+            }
+        }
+        Ok(nlas)
+    }
+}
+
+fn vlan_proto_to_link(
+    proto: VlanProtocol,
+) -> Result<link::VlanProtocol, NisporError> {
+    match proto {
+        VlanProtocol::Ieee8021Q => Ok(link::VlanProtocol::Ieee8021Q),
+        VlanProtocol::Ieee8021AD => Ok(link::VlanProtocol::Ieee8021Ad),
+        _ => Err(NisporError::invalid_argument(format!(
+            "Unsupported VF VLAN protocol {proto:?}"
+        ))),
+    }
+}
+
+fn vf_link_state_to_link(
+    state: &VfLinkState,
+) -> Result<link::VfLinkState, NisporError> {
+    match state {
+        VfLinkState::Auto => Ok(link::VfLinkState::Auto),
+        VfLinkState::Enable => Ok(link::VfLinkState::Enable),
+        VfLinkState::Disable => Ok(link::VfLinkState::Disable),
+        _ => Err(NisporError::invalid_argument(format!(
+            "Unsupported VF link state {state:?}"
+        ))),
+    }
+}
+
+// Kernel refuses to change VF count from non-zero to another non-zero value,
+// hence we remove all VFs first.
+fn set_total_vfs(
+    iface_name: &str,
+    cur_total_vfs: u32,
+    total_vfs: u32,
+) -> Result<(), NisporError> {
+    let device_path = format!("/sys/class/net/{iface_name}/device");
+    let max_vfs =
+        std::fs::read_to_string(format!("{device_path}/sriov_totalvfs"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok());
+    if let Some(max_vfs) = max_vfs {
+        if total_vfs > max_vfs {
+            return Err(NisporError::invalid_argument(format!(
+                "Interface {iface_name} only support up to {max_vfs} VFs, \
+                but {total_vfs} requested"
+            )));
+        }
+    }
+    let numvfs_path = format!("{device_path}/sriov_numvfs");
+    if cur_total_vfs != 0 && total_vfs != 0 {
+        write_sysfs(&numvfs_path, 0)?;
+    }
+    write_sysfs(&numvfs_path, total_vfs)
+}
+
+fn write_sysfs(path: &str, value: u32) -> Result<(), NisporError> {
+    std::fs::write(path, format!("{value}")).map_err(|e| {
+        let msg = format!("Failed to write {value} to {path}: {e}");
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            NisporError::permission_deny(msg)
+        } else {
+            NisporError::bug(msg)
+        }
+    })
+}
+
+fn set_vf_info_list(
+    pf_index: u32,
+    vf_nlas: Vec<LinkVfInfo>,
+) -> Result<(), NisporError> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = pf_index;
+    nl_msg.attributes.push(LinkAttribute::VfInfoList(vf_nlas));
+    let mut buffer = vec![0; nl_msg.buffer_len()];
+    nl_msg.emit(&mut buffer);
+    nl_request(NETLINK_ROUTE, RTM_SETLINK, NLM_F_ACK, &buffer)?;
+    Ok(())
+}
@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::{File, OpenOptions};
+use std::os::unix::io::AsRawFd;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Iface, NisporError, TunInfo, TunMode};
+
+const TUN_DEV_PATH: &str = "/dev/net/tun";
+
+const TUNSETIFF: u64 = 0x400454ca;
+const TUNSETPERSIST: u64 = 0x400454cb;
+const TUNSETOWNER: u64 = 0x400454cc;
+const TUNSETGROUP: u64 = 0x400454ce;
+const TUNGETIFF: u64 = 0x800454d2;
+
+const IFF_TUN: i16 = 0x0001;
+const IFF_TAP: i16 = 0x0002;
+const IFF_MULTI_QUEUE: i16 = 0x0100;
+const IFF_NO_PI: i16 = 0x1000;
+const IFF_VNET_HDR: i16 = 0x4000;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TunConf {
+    /// Required when creating new interface, cannot be changed afterwards.
+    pub mode: Option<TunMode>,
+    /// User ID allowed to attach the device.
+    pub owner: Option<u32>,
+    /// Group ID allowed to attach the device.
+    pub group: Option<u32>,
+    /// Include packet information header, default is false.
+    pub pi: Option<bool>,
+    pub vnet_hdr: Option<bool>,
+    /// Cannot be changed after creation.
+    pub multi_queue: Option<bool>,
+}
+
+// The `struct ifreq` with only `ifr_flags` of the union used.
+#[repr(C)]
+struct TunIfReq {
+    ifr_name: [u8; libc::IFNAMSIZ],
+    ifr_flags: i16,
+    _padding: [u8; 22],
+}
+
+impl TunConf {
+    // Kernel does not support creating TUN/TAP via netlink, use the
+    // /dev/net/tun ioctl like `ip tuntap` does.
+    pub(crate) fn create(&self, name: &str) -> Result<(), NisporError> {
+        let mode = match self.mode.as_ref() {
+            Some(m) => m,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "No mode defined for new TUN interface {name}"
+                )));
+            }
+        };
+        let fd = attach_tun(name, self.gen_flags(mode, None)?)?;
+        self.set_owner_group(&fd, name)?;
+        tun_ioctl(&fd, TUNSETPERSIST, 1, name)
+    }
+
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.tun.as_ref() {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not TUN",
+                    cur_iface.name
+                )));
+            }
+        };
+        if let Some(mode) = self.mode.as_ref() {
+            if mode != &cur_info.mode {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change mode of TUN interface {} from {:?} to {:?}",
+                    cur_iface.name, cur_info.mode, mode
+                )));
+            }
+        }
+        if let Some(multi_queue) = self.multi_queue {
+            if multi_queue != cur_info.multi_queue {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change multi_queue of TUN interface {} to {}",
+                    cur_iface.name, multi_queue
+                )));
+            }
+        }
+        if self.is_changed(cur_info) {
+            // Attaching to existing device will apply new flags
+            let flags = self.gen_flags(&cur_info.mode, Some(cur_info))?;
+            let fd = attach_tun(&cur_iface.name, flags)?;
+            // Kernel silently keeps the old flags when attaching to
+            // multi-queue device with other queues attached.
+            let new_flags = get_tun_flags(&fd, &cur_iface.name)?;
+            if new_flags & (IFF_NO_PI | IFF_VNET_HDR)
+                != flags & (IFF_NO_PI | IFF_VNET_HDR)
+            {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change pi or vnet_hdr of TUN interface {} \
+                    while it is in use",
+                    cur_iface.name
+                )));
+            }
+            self.set_owner_group(&fd, &cur_iface.name)?;
+        }
+        Ok(())
+    }
+
+    fn is_changed(&self, cur_info: &TunInfo) -> bool {
+        (self.owner.is_some() && self.owner != cur_info.owner)
+            || (self.group.is_some() && self.group != cur_info.group)
+            || self.pi.map(|p| p != cur_info.pi).unwrap_or_default()
+            || self
+                .vnet_hdr
+                .map(|v| v != cur_info.vnet_hdr)
+                .unwrap_or_default()
+    }
+
+    fn gen_flags(
+        &self,
+        mode: &TunMode,
+        cur_info: Option<&TunInfo>,
+    ) -> Result<i16, NisporError> {
+        let mut flags = match mode {
+            TunMode::Tun => IFF_TUN,
+            TunMode::Tap => IFF_TAP,
+            _ => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Unsupported TUN mode {mode:?}"
+                )));
+            }
+        };
+        let pi = self
+            .pi
+            .or_else(|| cur_info.map(|i| i.pi))
+            .unwrap_or_default();
+        if !pi {
+            flags |= IFF_NO_PI;
+        }
+        if self
+            .vnet_hdr
+            .or_else(|| cur_info.map(|i| i.vnet_hdr))
+            .unwrap_or_default()
+        {
+            flags |= IFF_VNET_HDR;
+        }
+        if self
+            .multi_queue
+            .or_else(|| cur_info.map(|i| i.multi_queue))
+            .unwrap_or_default()
+        {
+            flags |= IFF_MULTI_QUEUE;
+        }
+        Ok(flags)
+    }
+
+    fn set_owner_group(
+        &self,
+        fd: &File,
+        name: &str,
+    ) -> Result<(), NisporError> {
+        if let Some(owner) = self.owner {
+            tun_ioctl(fd, TUNSETOWNER, owner as usize, name)?;
+        }
+        if let Some(group) = self.group {
+            tun_ioctl(fd, TUNSETGROUP, group as usize, name)?;
+        }
+        Ok(())
+    }
+}
+
+fn attach_tun(name: &str, flags: i16) -> Result<File, NisporError> {
+    if name.len() >= libc::IFNAMSIZ {
+        return Err(NisporError::invalid_argument(format!(
+            "Interface name {name} is too long"
+        )));
+    }
+    let fd = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(TUN_DEV_PATH)
+        .map_err(|e| {
+            NisporError::bug(format!("Failed to open {TUN_DEV_PATH}: {e}"))
+        })?;
+    let mut ifr = TunIfReq {
+        ifr_name: [0; libc::IFNAMSIZ],
+        ifr_flags: flags,
+        _padding: [0; 22],
+    };
+    for (i, c) in name.as_bytes().iter().enumerate() {
+        ifr.ifr_name[i] = *c;
+    }
+    let rc = unsafe {
+        libc::ioctl(fd.as_raw_fd(), TUNSETIFF as _, &mut ifr as *mut TunIfReq)
+    };
+    if rc < 0 {
+        return Err(tun_ioctl_error("TUNSETIFF", name));
+    }
+    Ok(fd)
+}
+
+fn get_tun_flags(fd: &File, name: &str) -> Result<i16, NisporError> {
+    let mut ifr = TunIfReq {
+        ifr_name: [0; libc::IFNAMSIZ],
+        ifr_flags: 0,
+        _padding: [0; 22],
+    };
+    let rc = unsafe {
+        libc::ioctl(fd.as_raw_fd(), TUNGETIFF as _, &mut ifr as *mut TunIfReq)
+    };
+    if rc < 0 {
+        return Err(tun_ioctl_error("TUNGETIFF", name));
+    }
+    Ok(ifr.ifr_flags)
+}
+
+fn tun_ioctl(
+    fd: &File,
+    request: u64,
+    value: usize,
+    name: &str,
+) -> Result<(), NisporError> {
+    let rc = unsafe { libc::ioctl(fd.as_raw_fd(), request as _, value) };
+    if rc < 0 {
+        let request_name = match request {
+            TUNSETPERSIST => "TUNSETPERSIST",
+            TUNSETOWNER => "TUNSETOWNER",
+            TUNSETGROUP => "TUNSETGROUP",
+            _ => "unknown",
+        };
+        return Err(tun_ioctl_error(request_name, name));
+    }
+    Ok(())
+}
+
+fn tun_ioctl_error(request_name: &str, name: &str) -> NisporError {
+    let e = std::io::Error::last_os_error();
+    let msg = format!("Failed to {request_name} on TUN interface {name}: {e}");
+    if e.raw_os_error() == Some(libc::EPERM) {
+        NisporError::permission_deny(msg)
+    } else if e.raw_os_error() == Some(libc::EBUSY) {
+        // Single queue TUN device is attached by other process
+        NisporError::invalid_argument(format!(
+            "TUN interface {name} is in use by other process: {msg}"
+        ))
+    } else {
+        NisporError::bug(msg)
+    }
+}
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::{
+    link::{InfoData, InfoKind, InfoVrf},
+    route::RouteHeader,
+    rule::{RuleAction, RuleAttribute, RuleMessage},
+};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::create_iface_with_link_info;
+use crate::{
+    query::{get_ifaces, get_route_rules},
+    AddressFamily, Iface, IfaceConf, NisporError,
+};
+
+// Same as the one created by kernel on first VRF creation
+const L3MDEV_RULE_PRIORITY: u32 = 1000;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct VrfConf {
+    /// Route table ID used by this VRF, cannot be changed after creation.
+    pub table_id: u32,
+    /// Install the l3mdev route rule for IPv4 and IPv6 if not exists.
+    #[serde(default)]
+    pub l3mdev_rule: bool,
+}
+
+impl VrfConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+    ) -> Result<(), NisporError> {
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::Vrf,
+            Some(InfoData::Vrf(vec![InfoVrf::TableId(self.table_id)])),
+            None,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new vrf '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+}
+
+pub(crate) async fn change_vrfs(
+    handle: &Handle,
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    let mut need_l3mdev_rule = false;
+    for iface in ifaces {
+        let vrf_conf = match iface.vrf.as_ref() {
+            Some(v) => v,
+            None => continue,
+        };
+        if let Some(cur_iface) = cur_ifaces.get(&iface.name) {
+            match cur_iface.vrf.as_ref() {
+                Some(cur_vrf_info) => {
+                    if cur_vrf_info.table_id != vrf_conf.table_id {
+                        return Err(NisporError::invalid_argument(format!(
+                            "Cannot change route table ID of VRF {} \
+                            from {} to {}",
+                            iface.name,
+                            cur_vrf_info.table_id,
+                            vrf_conf.table_id
+                        )));
+                    }
+                }
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Interface {} is not VRF",
+                        iface.name
+                    )));
+                }
+            }
+        }
+        if vrf_conf.l3mdev_rule {
+            need_l3mdev_rule = true;
+        }
+    }
+    if need_l3mdev_rule {
+        add_l3mdev_rules(handle).await?;
+    }
+    Ok(())
+}
+
+async fn add_l3mdev_rules(handle: &Handle) -> Result<(), NisporError> {
+    let cur_rules = get_route_rules().await?;
+    for family in [AddressFamily::IPv4, AddressFamily::IPv6] {
+        if cur_rules
+            .iter()
+            .any(|r| r.address_family == family && r.l3mdev == Some(true))
+        {
+            continue;
+        }
+        log::debug!("Adding l3mdev route rule for {:?}", family);
+        let mut nl_msg = RuleMessage::default();
+        nl_msg.header.family = family.into();
+        nl_msg.header.action = RuleAction::ToTable;
+        // The l3mdev rule cannot have route table defined
+        nl_msg.header.table = RouteHeader::RT_TABLE_UNSPEC;
+        nl_msg
+            .attributes
+            .push(RuleAttribute::Priority(L3MDEV_RULE_PRIORITY));
+        nl_msg.attributes.push(RuleAttribute::L3MDev(true));
+        let mut req = handle.rule().add();
+        *req.message_mut() = nl_msg;
+        req.execute().await?;
+    }
+    Ok(())
+}
+
+// Route table ID of VRF interfaces indexed by interface name
+pub(crate) async fn get_vrf_table_ids(
+) -> Result<HashMap<String, u32>, NisporError> {
+    Ok(get_ifaces(None)
+        .await?
+        .into_values()
+        .filter_map(|iface| iface.vrf.map(|v| (iface.name, v.table_id)))
+        .collect())
+}
+
+pub(crate) fn vrf_name_to_table_id(
+    vrf_table_ids: &HashMap<String, u32>,
+    vrf: &str,
+) -> Result<u32, NisporError> {
+    match vrf_table_ids.get(vrf) {
+        Some(t) => Ok(*t),
+        None => Err(NisporError::invalid_argument(format!(
+            "VRF interface {vrf} not found"
+        ))),
+    }
+}
@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use netlink_packet_route::link::{InfoData, InfoKind, InfoVxlan};
+use netlink_packet_utils::nla::DefaultNla;
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{change_iface_link_info, create_iface_with_link_info};
+use crate::{Iface, NisporError, VxlanInfo};
+
+const IFLA_VXLAN_TTL_INHERIT: u16 = 26;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct VxlanConf {
+    pub vxlan_id: Option<u32>,
+    pub base_iface: Option<String>,
+    pub remote: Option<String>,
+    pub local: Option<String>,
+    pub ttl: Option<u8>,
+    pub tos: Option<u8>,
+    pub learning: Option<bool>,
+    pub ageing: Option<u32>,
+    pub max_address: Option<u32>,
+    pub src_port_min: Option<u16>,
+    pub src_port_max: Option<u16>,
+    pub proxy: Option<bool>,
+    pub rsc: Option<bool>,
+    pub l2miss: Option<bool>,
+    pub l3miss: Option<bool>,
+    pub dst_port: Option<u16>,
+    pub udp_check_sum: Option<bool>,
+    pub udp6_zero_check_sum_tx: Option<bool>,
+    pub udp6_zero_check_sum_rx: Option<bool>,
+    pub remote_check_sum_tx: Option<bool>,
+    pub remote_check_sum_rx: Option<bool>,
+    pub gbp: Option<bool>,
+    pub remote_check_sum_no_partial: Option<bool>,
+    pub collect_metadata: Option<bool>,
+    pub label: Option<u32>,
+    pub gpe: Option<bool>,
+    pub ttl_inherit: Option<bool>,
+    pub df: Option<u8>,
+}
+
+impl From<&VxlanInfo> for VxlanConf {
+    fn from(info: &VxlanInfo) -> Self {
+        Self {
+            vxlan_id: Some(info.vxlan_id),
+            base_iface: if info.base_iface.is_empty() {
+                None
+            } else {
+                Some(info.base_iface.clone())
+            },
+            remote: if info.remote.is_empty() {
+                None
+            } else {
+                Some(info.remote.clone())
+            },
+            local: if info.local.is_empty() {
+                None
+            } else {
+                Some(info.local.clone())
+            },
+            ttl: Some(info.ttl),
+            tos: Some(info.tos),
+            learning: Some(info.learning),
+            ageing: Some(info.ageing),
+            max_address: Some(info.max_address),
+            src_port_min: Some(info.src_port_min),
+            src_port_max: Some(info.src_port_max),
+            proxy: Some(info.proxy),
+            rsc: Some(info.rsc),
+            l2miss: Some(info.l2miss),
+            l3miss: Some(info.l3miss),
+            dst_port: Some(info.dst_port),
+            udp_check_sum: Some(info.udp_check_sum),
+            udp6_zero_check_sum_tx: Some(info.udp6_zero_check_sum_tx),
+            udp6_zero_check_sum_rx: Some(info.udp6_zero_check_sum_rx),
+            remote_check_sum_tx: Some(info.remote_check_sum_tx),
+            remote_check_sum_rx: Some(info.remote_check_sum_rx),
+            gbp: Some(info.gbp),
+            remote_check_sum_no_partial: Some(info.remote_check_sum_no_partial),
+            collect_metadata: Some(info.collect_metadata),
+            label: Some(info.label),
+            gpe: Some(info.gpe),
+            ttl_inherit: Some(info.ttl_inherit),
+            df: Some(info.df),
+        }
+    }
+}
+
+impl VxlanConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let base_iface_index = match self.base_iface.as_ref() {
+            Some(base_iface) => match cur_iface_name_2_index.get(base_iface) {
+                Some(i) => Some(*i),
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Base interface {base_iface} for VXLAN {name} \
+                            not found"
+                    )));
+                }
+            },
+            None => None,
+        };
+        let nlas = to_kernel_nlas(self.to_nlas(base_iface_index)?);
+
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::Vxlan,
+            Some(InfoData::Vxlan(nlas)),
+            None,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new vxlan '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+
+    pub(crate) fn change(
+        &self,
+        cur_iface: &Iface,
+        cur_ifaces: &HashMap<String, Iface>,
+    ) -> Result<(), NisporError> {
+        let cur_vxlan_conf = match cur_iface.vxlan.as_ref() {
+            Some(i) => VxlanConf::from(i),
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not VXLAN",
+                    cur_iface.name
+                )));
+            }
+        };
+        let base_iface_index = match self.base_iface.as_ref() {
+            Some(base_iface) => match cur_ifaces.get(base_iface) {
+                Some(i) => Some(i.index),
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Base interface {} for VXLAN {} not found",
+                        base_iface, cur_iface.name
+                    )));
+                }
+            },
+            None => None,
+        };
+        let cur_base_iface_index = cur_vxlan_conf
+            .base_iface
+            .as_ref()
+            .and_then(|n| cur_ifaces.get(n))
+            .map(|i| i.index);
+
+        let cur_nlas = cur_vxlan_conf.to_nlas(cur_base_iface_index)?;
+        let mut changed_nlas = Vec::new();
+        for nla in self.to_nlas(base_iface_index)? {
+            if cur_nlas.contains(&nla) {
+                continue;
+            }
+            if !is_mutable(&nla) {
+                return Err(NisporError::invalid_argument(format!(
+                    "Cannot change {:?} of existing VXLAN interface {}, \
+                    current {:?}",
+                    nla, cur_iface.name, cur_vxlan_conf
+                )));
+            }
+            changed_nlas.push(nla);
+        }
+        if changed_nlas.is_empty() {
+            return Ok(());
+        }
+        change_iface_link_info(
+            cur_iface.index,
+            InfoKind::Vxlan,
+            InfoData::Vxlan(to_kernel_nlas(changed_nlas)),
+        )
+    }
+
+    fn to_nlas(
+        &self,
+        base_iface_index: Option<u32>,
+    ) -> Result<Vec<InfoVxlan>, NisporError> {
+        let mut nlas = Vec::new();
+        if let Some(v) = self.vxlan_id {
+            nlas.push(InfoVxlan::Id(v));
+        }
+        if let Some(v) = base_iface_index {
+            nlas.push(InfoVxlan::Link(v));
+        }
+        if let Some(v) = self.remote.as_ref() {
+            nlas.push(match v.parse::<IpAddr>()? {
+                IpAddr::V4(ip) => InfoVxlan::Group(ip.octets().to_vec()),
+                IpAddr::V6(ip) => InfoVxlan::Group6(ip.octets().to_vec()),
+            });
+        }
+        if let Some(v) = self.local.as_ref() {
+            nlas.push(match v.parse::<IpAddr>()? {
+                IpAddr::V4(ip) => InfoVxlan::Local(ip.octets().to_vec()),
+                IpAddr::V6(ip) => InfoVxlan::Local6(ip.octets().to_vec()),
+            });
+        }
+        if let Some(v) = self.ttl {
+            nlas.push(InfoVxlan::Ttl(v));
+        }
+        if let Some(v) = self.tos {
+            nlas.push(InfoVxlan::Tos(v));
+        }
+        if let Some(v) = self.learning {
+            nlas.push(InfoVxlan::Learning(v));
+        }
+        if let Some(v) = self.ageing {
+            nlas.push(InfoVxlan::Ageing(v));
+        }
+        if let Some(v) = self.max_address {
+            nlas.push(InfoVxlan::Limit(v));
+        }
+        match (self.src_port_min, self.src_port_max) {
+            (Some(min), Some(max)) => {
+                nlas.push(InfoVxlan::PortRange((min, max)))
+            }
+            (None, None) => (),
+            _ => {
+                return Err(NisporError::invalid_argument(
+                    "The src_port_min and src_port_max of VXLAN should be \
+                    defined together"
+                        .into(),
+                ));
+            }
+        }
+        if let Some(v) = self.proxy {
+            nlas.push(InfoVxlan::Proxy(v));
+        }
+        if let Some(v) = self.rsc {
+            nlas.push(InfoVxlan::Rsc(v));
+        }
+        if let Some(v) = self.l2miss {
+            nlas.push(InfoVxlan::L2Miss(v));
+        }
+        if let Some(v) = self.l3miss {
+            nlas.push(InfoVxlan::L3Miss(v));
+        }
+        if let Some(v) = self.dst_port {
+            nlas.push(InfoVxlan::Port(v));
+        }
+        if let Some(v) = self.udp_check_sum {
+            nlas.push(InfoVxlan::UDPCsum(v));
+        }
+        if let Some(v) = self.udp6_zero_check_sum_tx {
+            nlas.push(InfoVxlan::UDPZeroCsumTX(v));
+        }
+        if let Some(v) = self.udp6_zero_check_sum_rx {
+            nlas.push(InfoVxlan::UDPZeroCsumRX(v));
+        }
+        if let Some(v) = self.remote_check_sum_tx {
+            nlas.push(InfoVxlan::RemCsumTX(v));
+        }
+        if let Some(v) = self.remote_check_sum_rx {
+            nlas.push(InfoVxlan::RemCsumRX(v));
+        }
+        if let Some(v) = self.gbp {
+            nlas.push(InfoVxlan::Gbp(v));
+        }
+        if let Some(v) = self.remote_check_sum_no_partial {
+            nlas.push(InfoVxlan::RemCsumNoPartial(v));
+        }
+        if let Some(v) = self.collect_metadata {
+            nlas.push(InfoVxlan::CollectMetadata(v));
+        }
+        if let Some(v) = self.label {
+            nlas.push(InfoVxlan::Label(v));
+        }
+        if let Some(v) = self.gpe {
+            nlas.push(InfoVxlan::Gpe(v));
+        }
+        if let Some(v) = self.ttl_inherit {
+            nlas.push(InfoVxlan::TtlInherit(v));
+        }
+        if let Some(v) = self.df {
+            nlas.push(InfoVxlan::Df(v));
+        }
+        Ok(nlas)
+    }
+}
+
+// Kernel VXLAN driver only allows changing these properties
+fn is_mutable(nla: &InfoVxlan) -> bool {
+    matches!(
+        nla,
+        InfoVxlan::Link(_)
+            | InfoVxlan::Group(_)
+            | InfoVxlan::Group6(_)
+            | InfoVxlan::Local(_)
+            | InfoVxlan::Local6(_)
+            | InfoVxlan::Ttl(_)
+            | InfoVxlan::Tos(_)
+            | InfoVxlan::Label(_)
+            | InfoVxlan::Learning(_)
+            | InfoVxlan::Ageing(_)
+            | InfoVxlan::Df(_)
+    )
+}
+
+// Some VXLAN options are NLA_FLAG in kernel, the existence of them means
+// enabled.
+fn to_kernel_nlas(nlas: Vec<InfoVxlan>) -> Vec<InfoVxlan> {
+    nlas.into_iter()
+        .filter_map(|nla| match nla {
+            InfoVxlan::Gbp(false)
+            | InfoVxlan::Gpe(false)
+            | InfoVxlan::RemCsumNoPartial(false)
+            | InfoVxlan::TtlInherit(false) => None,
+            InfoVxlan::TtlInherit(true) => Some(InfoVxlan::Other(
+                DefaultNla::new(IFLA_VXLAN_TTL_INHERIT, Vec::new()),
+            )),
+            _ => Some(nla),
+        })
+        .collect()
+}
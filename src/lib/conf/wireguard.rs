@@ -0,0 +1,270 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use netlink_packet_utils::nla::DefaultNla;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    base64::{base64_decode, base64_encode},
+    netlink::{
+        allowed_ip_to_nlas, endpoint_to_sockaddr, gen_nested_nla,
+        wireguard_set_device, WGDEVICE_A_FWMARK, WGDEVICE_A_LISTEN_PORT,
+        WGDEVICE_A_PEERS, WGDEVICE_A_PRIVATE_KEY, WGPEER_A_ALLOWEDIPS,
+        WGPEER_A_ENDPOINT, WGPEER_A_FLAGS,
+        WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL, WGPEER_A_PRESHARED_KEY,
+        WGPEER_A_PUBLIC_KEY, WGPEER_F_REMOVE_ME, WGPEER_F_REPLACE_ALLOWEDIPS,
+        WG_KEY_LEN,
+    },
+    query::parse_ip_net_addr_str,
+    Iface, NisporError, WireguardPeerInfo,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct WireguardConf {
+    /// Base64 encoded private key. Never included in query result, hence
+    /// always applied when defined.
+    #[serde(skip_serializing)]
+    pub private_key: Option<String>,
+    pub listen_port: Option<u16>,
+    pub fwmark: Option<u32>,
+    pub peers: Option<Vec<WireguardPeerConf>>,
+    /// Remove existing peers not mentioned in `peers`.
+    #[serde(default)]
+    pub replace_peers: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct WireguardPeerConf {
+    /// Base64 encoded public key
+    pub public_key: String,
+    /// Base64 encoded preshared key. Never included in query result, hence
+    /// always applied when defined.
+    #[serde(skip_serializing)]
+    pub preshared_key: Option<String>,
+    /// In the format of `192.0.2.1:51820` or `[2001:db8::1]:51820`
+    pub endpoint: Option<String>,
+    /// Replace existing allowed IPs of this peer when defined.
+    pub allowed_ips: Option<Vec<String>>,
+    /// Persistent keepalive interval in seconds, 0 means disabled.
+    pub persistent_keepalive: Option<u16>,
+    #[serde(default)]
+    pub remove: bool,
+}
+
+// Hide the keys as the `IfaceConf` might be logged.
+impl std::fmt::Debug for WireguardConf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WireguardConf")
+            .field(
+                "private_key",
+                &self.private_key.as_ref().map(|_| "<hidden>"),
+            )
+            .field("listen_port", &self.listen_port)
+            .field("fwmark", &self.fwmark)
+            .field("peers", &self.peers)
+            .field("replace_peers", &self.replace_peers)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for WireguardPeerConf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WireguardPeerConf")
+            .field("public_key", &self.public_key)
+            .field(
+                "preshared_key",
+                &self.preshared_key.as_ref().map(|_| "<hidden>"),
+            )
+            .field("endpoint", &self.endpoint)
+            .field("allowed_ips", &self.allowed_ips)
+            .field("persistent_keepalive", &self.persistent_keepalive)
+            .field("remove", &self.remove)
+            .finish()
+    }
+}
+
+impl WireguardConf {
+    pub(crate) fn change(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.wireguard.as_ref() {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not WireGuard",
+                    cur_iface.name
+                )));
+            }
+        };
+        let mut nlas = Vec::new();
+        if let Some(key) = self.private_key.as_ref() {
+            nlas.push(DefaultNla::new(WGDEVICE_A_PRIVATE_KEY, parse_key(key)?));
+        }
+        if let Some(v) = self.listen_port {
+            if v != cur_info.listen_port {
+                nlas.push(DefaultNla::new(
+                    WGDEVICE_A_LISTEN_PORT,
+                    v.to_ne_bytes().to_vec(),
+                ));
+            }
+        }
+        if let Some(v) = self.fwmark {
+            if v != cur_info.fwmark {
+                nlas.push(DefaultNla::new(
+                    WGDEVICE_A_FWMARK,
+                    v.to_ne_bytes().to_vec(),
+                ));
+            }
+        }
+
+        let mut peer_nlas = Vec::new();
+        let mut des_pub_keys = Vec::new();
+        for peer in self.peers.as_deref().unwrap_or(&[]) {
+            let pub_key = parse_key(&peer.public_key)?;
+            let pub_key_str = base64_encode(&pub_key);
+            let cur_peer =
+                cur_info.peers.iter().find(|p| p.public_key == pub_key_str);
+            if peer.remove {
+                if cur_peer.is_some() {
+                    peer_nlas.push(gen_remove_peer_nla(pub_key));
+                }
+                continue;
+            }
+            des_pub_keys.push(pub_key_str);
+            if let Some(nlas) = peer.gen_nlas(pub_key, cur_peer)? {
+                peer_nlas.push(nlas);
+            }
+        }
+        if self.replace_peers {
+            for cur_peer in cur_info.peers.iter() {
+                if !des_pub_keys.contains(&cur_peer.public_key) {
+                    peer_nlas.push(gen_remove_peer_nla(parse_key(
+                        &cur_peer.public_key,
+                    )?));
+                }
+            }
+        }
+        if !peer_nlas.is_empty() {
+            nlas.push(gen_nested_nla(WGDEVICE_A_PEERS, &peer_nlas));
+        }
+
+        if !nlas.is_empty() {
+            wireguard_set_device(cur_iface.index, &nlas)?;
+        }
+        Ok(())
+    }
+}
+
+impl WireguardPeerConf {
+    // Return None if nothing changed.
+    fn gen_nlas(
+        &self,
+        pub_key: Vec<u8>,
+        cur_peer: Option<&WireguardPeerInfo>,
+    ) -> Result<Option<DefaultNla>, NisporError> {
+        let mut nlas = Vec::new();
+        if let Some(key) = self.preshared_key.as_ref() {
+            nlas.push(DefaultNla::new(WGPEER_A_PRESHARED_KEY, parse_key(key)?));
+        }
+        if let Some(endpoint) = self.endpoint.as_ref() {
+            let addr = endpoint.parse::<SocketAddr>().map_err(|e| {
+                NisporError::invalid_argument(format!(
+                    "Invalid WireGuard peer endpoint {endpoint}: {e}"
+                ))
+            })?;
+            if cur_peer.and_then(|p| p.endpoint.as_ref())
+                != Some(&addr.to_string())
+            {
+                nlas.push(DefaultNla::new(
+                    WGPEER_A_ENDPOINT,
+                    endpoint_to_sockaddr(&addr),
+                ));
+            }
+        }
+        if let Some(v) = self.persistent_keepalive {
+            if cur_peer.map(|p| p.persistent_keepalive) != Some(v) {
+                nlas.push(DefaultNla::new(
+                    WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL,
+                    v.to_ne_bytes().to_vec(),
+                ));
+            }
+        }
+        if let Some(allowed_ips) = self.allowed_ips.as_ref() {
+            let mut des_ips = Vec::new();
+            for ip in allowed_ips {
+                let (ip, prefix_len) = parse_ip_net_addr_str(ip)?;
+                des_ips.push((mask_ip(&ip, prefix_len), prefix_len));
+            }
+            let mut des_ip_strs: Vec<String> = des_ips
+                .iter()
+                .map(|(ip, prefix_len)| format!("{ip}/{prefix_len}"))
+                .collect();
+            des_ip_strs.sort_unstable();
+            let mut cur_ip_strs =
+                cur_peer.map(|p| p.allowed_ips.clone()).unwrap_or_default();
+            cur_ip_strs.sort_unstable();
+            if des_ip_strs != cur_ip_strs {
+                nlas.push(DefaultNla::new(
+                    WGPEER_A_FLAGS,
+                    WGPEER_F_REPLACE_ALLOWEDIPS.to_ne_bytes().to_vec(),
+                ));
+                let ip_nlas: Vec<DefaultNla> = des_ips
+                    .iter()
+                    .map(|(ip, prefix_len)| {
+                        gen_nested_nla(0, &allowed_ip_to_nlas(ip, *prefix_len))
+                    })
+                    .collect();
+                nlas.push(gen_nested_nla(WGPEER_A_ALLOWEDIPS, &ip_nlas));
+            }
+        }
+        if nlas.is_empty() && cur_peer.is_some() {
+            return Ok(None);
+        }
+        nlas.insert(0, DefaultNla::new(WGPEER_A_PUBLIC_KEY, pub_key));
+        Ok(Some(gen_nested_nla(0, &nlas)))
+    }
+}
+
+fn gen_remove_peer_nla(pub_key: Vec<u8>) -> DefaultNla {
+    gen_nested_nla(
+        0,
+        &[
+            DefaultNla::new(WGPEER_A_PUBLIC_KEY, pub_key),
+            DefaultNla::new(
+                WGPEER_A_FLAGS,
+                WGPEER_F_REMOVE_ME.to_ne_bytes().to_vec(),
+            ),
+        ],
+    )
+}
+
+// The key might be private, hence not included in error message.
+fn parse_key(key: &str) -> Result<Vec<u8>, NisporError> {
+    let raw = base64_decode(key)?;
+    if raw.len() != WG_KEY_LEN {
+        return Err(NisporError::invalid_argument(format!(
+            "Invalid WireGuard key: expecting {WG_KEY_LEN} bytes, got {}",
+            raw.len()
+        )));
+    }
+    Ok(raw)
+}
+
+// Kernel is storing the network address of allowed IP.
+fn mask_ip(ip: &IpAddr, prefix_len: u8) -> IpAddr {
+    match ip {
+        IpAddr::V4(i) => {
+            let mask = u32::MAX
+                .checked_shl(32 - u32::from(prefix_len.min(32)))
+                .unwrap_or(0);
+            IpAddr::V4(Ipv4Addr::from(u32::from(*i) & mask))
+        }
+        IpAddr::V6(i) => {
+            let mask = u128::MAX
+                .checked_shl(128 - u32::from(prefix_len.min(128)))
+                .unwrap_or(0);
+            IpAddr::V6(Ipv6Addr::from(u128::from(*i) & mask))
+        }
+    }
+}
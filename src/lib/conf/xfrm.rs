@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::{InfoData, InfoKind, InfoXfrm};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use super::iface::{change_iface_link_info, create_iface_with_link_info};
+use crate::{Iface, NisporError};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct XfrmConf {
+    /// The underlying interface, optional.
+    pub base_iface: Option<String>,
+    /// The `if_id` used by XFRM policies and states to select this
+    /// interface. Required when creating new interface, cannot be 0.
+    pub iface_id: Option<u32>,
+}
+
+impl XfrmConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        cur_iface_name_2_index: &HashMap<String, u32>,
+    ) -> Result<(), NisporError> {
+        let iface_id = match self.iface_id {
+            Some(i) if i != 0 => i,
+            _ => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Non-zero iface_id is required for new XFRM \
+                    interface {name}"
+                )));
+            }
+        };
+        let mut nlas = vec![InfoXfrm::IfId(iface_id)];
+        if let Some(base_iface) = self.base_iface.as_ref() {
+            match cur_iface_name_2_index.get(base_iface) {
+                Some(i) => nlas.push(InfoXfrm::Link(*i)),
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Base interface {base_iface} for XFRM {name} \
+                        not found"
+                    )));
+                }
+            }
+        }
+        match create_iface_with_link_info(
+            handle,
+            name,
+            InfoKind::Xfrm,
+            Some(InfoData::Xfrm(nlas)),
+            None,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new xfrm '{}': {}",
+                &name, e
+            ))),
+        }
+    }
+
+    // Kernel XFRM interface driver resets unspecified properties to 0 when
+    // changing, hence we should send out the full configuration merged with
+    // current one.
+    pub(crate) fn change(
+        &self,
+        cur_iface: &Iface,
+        cur_ifaces: &HashMap<String, Iface>,
+    ) -> Result<(), NisporError> {
+        let cur_info = match cur_iface.xfrm.as_ref() {
+            Some(i) => i,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Interface {} is not XFRM",
+                    cur_iface.name
+                )));
+            }
+        };
+        let iface_id = self.iface_id.unwrap_or(cur_info.iface_id);
+        let base_iface = self
+            .base_iface
+            .as_deref()
+            .unwrap_or(cur_info.base_iface.as_str());
+        if iface_id == cur_info.iface_id && base_iface == cur_info.base_iface {
+            return Ok(());
+        }
+        if iface_id == 0 {
+            return Err(NisporError::invalid_argument(format!(
+                "The iface_id of XFRM interface {} cannot be 0",
+                cur_iface.name
+            )));
+        }
+        let mut nlas = vec![InfoXfrm::IfId(iface_id)];
+        // Kernel use 0 as base interface index when no underlying interface
+        if !base_iface.is_empty() && base_iface != "0" {
+            match cur_ifaces.get(base_iface) {
+                Some(i) => nlas.push(InfoXfrm::Link(i.index)),
+                None => {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Base interface {} for XFRM {} not found",
+                        base_iface, cur_iface.name
+                    )));
+                }
+            }
+        }
+        change_iface_link_info(
+            cur_iface.index,
+            InfoKind::Xfrm,
+            InfoData::Xfrm(nlas),
+        )
+    }
+}
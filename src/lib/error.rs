@@ -93,6 +93,7 @@ impl std::convert::From<EthtoolError> for NisporError {
     }
 }
 
+#[allow(clippy::incompatible_msrv)]
 impl std::convert::From<std::ffi::FromBytesWithNulError> for NisporError {
     fn from(e: std::ffi::FromBytesWithNulError) -> Self {
         NisporError {
@@ -10,14 +10,20 @@ pub struct NetStateIfaceFilter {
     pub iface_name: Option<String>,
     /// Include IP Address information. By default: true
     pub include_ip_address: bool,
-    /// Include SR-IOV VF information or not. By default: true
+    /// Include SR-IOV VF information and e-switch mode or not.
+    /// By default: true
     pub include_sriov_vf_info: bool,
     /// Include Bridge VLAN information or not. By default: true
     pub include_bridge_vlan: bool,
     /// Include ethool information or not. By default: true
     pub include_ethtool: bool,
+    /// Include ethtool statistics or not, only effective when
+    /// `include_ethtool` is true. By default: false
+    pub include_ethtool_stats: bool,
     /// Include mptcp information or not. By default: true
     pub include_mptcp: bool,
+    /// Include devlink information or not. By default: true
+    pub include_devlink: bool,
 }
 
 impl Default for NetStateIfaceFilter {
@@ -28,7 +34,9 @@ impl Default for NetStateIfaceFilter {
             include_sriov_vf_info: true,
             include_bridge_vlan: true,
             include_ethtool: true,
+            include_ethtool_stats: false,
             include_mptcp: true,
+            include_devlink: true,
         }
     }
 }
@@ -41,7 +49,9 @@ impl NetStateIfaceFilter {
             include_sriov_vf_info: false,
             include_bridge_vlan: false,
             include_ethtool: false,
+            include_ethtool_stats: false,
             include_mptcp: false,
+            include_devlink: false,
         }
     }
 }
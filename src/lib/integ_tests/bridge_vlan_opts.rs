@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::panic;
+
+use crate::{NetConf, NetState};
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "br0";
+const PORT_NAME: &str = "vxlan0";
+
+const EXPECTED_PORT_VLAN_OPTS: &str = r#"---
+- vid: 1
+  stp_state: forwarding
+- vid_range:
+    - 100
+    - 102
+  stp_state: forwarding
+  tunnel_id: 10100"#;
+
+const EXPECTED_BR_VLAN_GLOBAL_OPTS: &str = r#"---
+- vid: 1
+  multicast_snooping: true
+- vid_range:
+    - 100
+    - 102
+  multicast_snooping: false"#;
+
+#[test]
+fn test_get_br_vlan_opts_yaml() {
+    with_br_vlan_tunnel_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        let port = &state.ifaces[PORT_NAME];
+        assert_value_match(
+            EXPECTED_BR_VLAN_GLOBAL_OPTS,
+            &iface.bridge.as_ref().unwrap().vlan_global_opts,
+        );
+        assert_value_match(
+            EXPECTED_PORT_VLAN_OPTS,
+            &port.bridge_port.as_ref().unwrap().vlan_opts,
+        );
+    });
+}
+
+const BR_VLAN_OPTS_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: br0
+    bridge:
+      vlan_global_opts:
+        - vid_range:
+            - 100
+            - 102
+          multicast_snooping: true
+  - name: vxlan0
+    bridge_port:
+      vlan_opts:
+        - vid_range:
+            - 100
+            - 101
+          tunnel_id: 20100
+        - vid: 102
+          remove_tunnel_id: true"#;
+
+const EXPECTED_CHANGED_PORT_VLAN_OPTS: &str = r#"---
+- vid: 1
+- vid_range:
+    - 100
+    - 101
+  tunnel_id: 20100
+- vid: 102"#;
+
+const EXPECTED_CHANGED_BR_VLAN_GLOBAL_OPTS: &str = r#"---
+- vid: 1
+  multicast_snooping: true
+- vid_range:
+    - 100
+    - 102
+  multicast_snooping: true"#;
+
+#[test]
+fn test_change_br_vlan_opts() {
+    with_br_vlan_tunnel_iface(|| {
+        let net_conf: NetConf =
+            serde_yaml::from_str(BR_VLAN_OPTS_CHANGE_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        let port = &state.ifaces[PORT_NAME];
+        assert_value_match(
+            EXPECTED_CHANGED_BR_VLAN_GLOBAL_OPTS,
+            &iface.bridge.as_ref().unwrap().vlan_global_opts,
+        );
+        let port_vlan_opts = port
+            .bridge_port
+            .as_ref()
+            .unwrap()
+            .vlan_opts
+            .as_ref()
+            .unwrap();
+        assert_value_match(EXPECTED_CHANGED_PORT_VLAN_OPTS, port_vlan_opts);
+        assert_eq!(port_vlan_opts[2].tunnel_id, None);
+    });
+}
+
+fn with_br_vlan_tunnel_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("brvt");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    DevlinkDevice, DevlinkParamCmode, DevlinkParamData, NetConf, NetState,
+    SriovEswitchMode,
+};
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const EXPECTED_DEVLINK_DEVICE: &str = r#"---
+bus_name: netdevsim
+dev_name: netdevsim1
+driver: netdevsim
+eswitch_mode: legacy
+ports:
+  - index: 0
+    port_type: eth
+    flavour: physical
+    iface: sim0
+    port_number: 1
+  - index: 1
+    port_type: eth
+    flavour: physical
+    iface: sim1
+    port_number: 2"#;
+
+#[test]
+fn test_get_devlink_netdevsim() {
+    with_netdevsim_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let dev = get_netdevsim_devlink(&state);
+        assert_value_match(EXPECTED_DEVLINK_DEVICE, dev);
+
+        let param = dev
+            .params
+            .as_ref()
+            .and_then(|p| p.iter().find(|p| p.name == "max_macs"))
+            .unwrap();
+        assert!(param.generic);
+        assert_eq!(param.values[0].cmode, DevlinkParamCmode::Driverinit);
+        assert!(matches!(param.values[0].data, DevlinkParamData::Number(_)));
+
+        assert_eq!(
+            state.ifaces["sim0"].devlink_port.as_deref(),
+            Some("netdevsim/netdevsim1/0")
+        );
+        assert_eq!(
+            state.ifaces["sim1"].devlink_port.as_deref(),
+            Some("netdevsim/netdevsim1/1")
+        );
+    });
+}
+
+const DEVLINK_CONF_YML: &str = r#"---
+devlink:
+  devices:
+    - bus_name: netdevsim
+      dev_name: netdevsim1
+      eswitch_mode: switchdev
+      params:
+        - name: max_macs
+          cmode: driverinit
+          value: 16
+        - name: test1
+          cmode: driverinit
+          value: false"#;
+
+const EXPECTED_DEVLINK_CHANGED: &str = r#"---
+eswitch_mode: switchdev"#;
+
+const DEVLINK_INVALID_PARAM_YML: &str = r#"---
+devlink:
+  devices:
+    - bus_name: netdevsim
+      dev_name: netdevsim1
+      params:
+        - name: not_exist
+          cmode: runtime
+          value: 1"#;
+
+const DEVLINK_REVERT_YML: &str = r#"---
+devlink:
+  devices:
+    - bus_name: netdevsim
+      dev_name: netdevsim1
+      eswitch_mode: legacy"#;
+
+#[test]
+fn test_change_devlink_netdevsim() {
+    with_netdevsim_iface(|| {
+        let net_conf: NetConf = serde_yaml::from_str(DEVLINK_CONF_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        let dev = get_netdevsim_devlink(&state);
+        assert_value_match(EXPECTED_DEVLINK_CHANGED, dev);
+        assert_eq!(
+            get_param_value(dev, "max_macs"),
+            Some(&DevlinkParamData::Number(16))
+        );
+        assert_eq!(
+            get_param_value(dev, "test1"),
+            Some(&DevlinkParamData::Bool(false))
+        );
+
+        // Applying the same config again should be a no-op
+        net_conf.apply().unwrap();
+
+        let net_conf: NetConf =
+            serde_yaml::from_str(DEVLINK_INVALID_PARAM_YML).unwrap();
+        assert!(matches!(
+            net_conf.apply(),
+            Err(crate::NisporError {
+                kind: crate::ErrorKind::InvalidArgument,
+                ..
+            })
+        ));
+
+        let net_conf: NetConf =
+            serde_yaml::from_str(DEVLINK_REVERT_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        assert_eq!(
+            get_netdevsim_devlink(&state).eswitch_mode,
+            Some(SriovEswitchMode::Legacy)
+        );
+    });
+}
+
+fn get_netdevsim_devlink(state: &NetState) -> &DevlinkDevice {
+    state
+        .devlink
+        .as_ref()
+        .and_then(|d| d.devices.iter().find(|d| d.bus_name == "netdevsim"))
+        .unwrap()
+}
+
+fn get_param_value<'a>(
+    dev: &'a DevlinkDevice,
+    name: &str,
+) -> Option<&'a DevlinkParamData> {
+    dev.params
+        .as_ref()?
+        .iter()
+        .find(|p| p.name == name)?
+        .values
+        .iter()
+        .find(|v| v.cmode == DevlinkParamCmode::Driverinit)
+        .map(|v| &v.data)
+}
+
+fn with_netdevsim_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("sim");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 use pretty_assertions::assert_eq;
 use std::panic;
 
@@ -29,3 +29,47 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+fn create_delete_simple_iface(iface_name: &str, iface_type: crate::IfaceType) {
+    let net_conf: NetConf = serde_yaml::from_str(&format!(
+        "---
+ifaces:
+  - name: {iface_name}
+    type: {iface_type}"
+    ))
+    .unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(state.ifaces[iface_name].iface_type, iface_type);
+
+    let net_conf: NetConf = serde_yaml::from_str(&format!(
+        "---
+ifaces:
+  - name: {iface_name}
+    state: absent"
+    ))
+    .unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get(iface_name));
+}
+
+#[test]
+fn test_create_delete_dummy() {
+    create_delete_simple_iface("dummy2", crate::IfaceType::Dummy);
+}
+
+#[test]
+fn test_create_delete_ifb() {
+    create_delete_simple_iface("ifb1", crate::IfaceType::Ifb);
+}
+
+#[test]
+fn test_create_delete_nlmon() {
+    create_delete_simple_iface("nlmon1", crate::IfaceType::Nlmon);
+}
+
+#[test]
+fn test_create_delete_vcan() {
+    create_delete_simple_iface("vcan1", crate::IfaceType::Vcan);
+}
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{NetConf, NetState};
+use netlink_packet_utils::Emitable;
 use pretty_assertions::assert_eq;
 
 use std::panic;
@@ -139,6 +140,55 @@ fn test_get_ethtool_feature_yaml_of_loopback() {
 
 // TODO: There is no way to test the ethtool ring.
 
+const EXPECTED_CHANNEL_INFO: &str = r#"---
+combined: 1
+combined_max: 1"#;
+
+const EXPECTED_FEC_INFO: &str = r#"---
+modes:
+  - none
+auto: false
+active: none"#;
+
+#[test]
+fn test_get_ethtool_channel_and_fec_yaml() {
+    with_netdevsim_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME0];
+        let ethtool_info = iface.ethtool.as_ref().unwrap();
+        assert_value_match(EXPECTED_CHANNEL_INFO, &ethtool_info.channel);
+        assert_value_match(EXPECTED_FEC_INFO, &ethtool_info.fec);
+    });
+}
+
+const ETHTOOL_COALESCE_SIM_YML: &str = r#"---
+ifaces:
+  - name: sim0
+    ethtool:
+      coalesce:
+        rx_usecs: 100
+        tx_max_frames: 32
+        use_adaptive_rx: true"#;
+
+const EXPECTED_SIM_COALESCE: &str = r#"---
+rx_usecs: 100
+tx_max_frames: 32
+use_adaptive_rx: true"#;
+
+#[test]
+fn test_change_ethtool_coalesce_of_netdevsim() {
+    with_netdevsim_iface(|| {
+        let net_conf: NetConf =
+            serde_yaml::from_str(ETHTOOL_COALESCE_SIM_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        assert_value_match(
+            EXPECTED_SIM_COALESCE,
+            &state.ifaces[IFACE_NAME0].ethtool.as_ref().unwrap().coalesce,
+        );
+    });
+}
+
 fn with_netdevsim_iface<T>(test: T)
 where
     T: FnOnce() + panic::UnwindSafe,
@@ -209,3 +259,361 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const ETHTOOL_FEATURE_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth9
+    type: veth
+    veth:
+      peer: veth9.ep
+    ethtool:
+      features:
+        rx-gro: true
+        tx-checksum-ip-generic: false"#;
+
+const ETHTOOL_FEATURE_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: veth9
+    ethtool:
+      features:
+        rx-gro: false
+        tx-checksum-ip-generic: true"#;
+
+const ETHTOOL_FIXED_FEATURE_YML: &str = r#"---
+ifaces:
+  - name: veth9
+    ethtool:
+      features:
+        rx-lro: true"#;
+
+const ETHTOOL_FEATURE_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth9
+    state: absent"#;
+
+#[test]
+fn test_change_ethtool_features() {
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_FEATURE_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let features = state.ifaces["veth9"]
+        .ethtool
+        .as_ref()
+        .unwrap()
+        .features
+        .as_ref()
+        .unwrap();
+    assert_eq!(features.changeable.get("rx-gro"), Some(&true));
+    assert_eq!(
+        features.changeable.get("tx-checksum-ip-generic"),
+        Some(&false)
+    );
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_FEATURE_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let features = state.ifaces["veth9"]
+        .ethtool
+        .as_ref()
+        .unwrap()
+        .features
+        .as_ref()
+        .unwrap();
+    assert_eq!(features.changeable.get("rx-gro"), Some(&false));
+    assert_eq!(
+        features.changeable.get("tx-checksum-ip-generic"),
+        Some(&true)
+    );
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_FIXED_FEATURE_YML).unwrap();
+    let result = net_conf.apply();
+    assert!(matches!(
+        result,
+        Err(crate::NisporError {
+            kind: crate::ErrorKind::InvalidArgument,
+            ..
+        })
+    ));
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_FEATURE_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
+
+const ETHTOOL_STATS_VETH_YML: &str = r#"---
+ifaces:
+  - name: veth10
+    type: veth
+    veth:
+      peer: veth10.ep"#;
+
+const ETHTOOL_STATS_VETH_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth10
+    state: absent"#;
+
+#[test]
+fn test_get_ethtool_driver_stats() {
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_STATS_VETH_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    let iface_filter = crate::NetStateIfaceFilter {
+        include_ethtool_stats: true,
+        ..Default::default()
+    };
+    let mut filter = crate::NetStateFilter::minimum();
+    filter.iface = Some(iface_filter);
+    let state = NetState::retrieve_with_filter(&filter).unwrap();
+    let peer_index = state.ifaces["veth10.ep"].index;
+    let stats = state.ifaces["veth10"]
+        .ethtool
+        .as_ref()
+        .unwrap()
+        .stats
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        stats.driver.as_ref().unwrap().get("peer_ifindex"),
+        Some(&u64::from(peer_index))
+    );
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_STATS_VETH_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
+
+const ETHTOOL_RSS_VETH_YML: &str = r#"---
+ifaces:
+  - name: veth11
+    type: veth
+    veth:
+      peer: veth11.ep"#;
+
+const ETHTOOL_RSS_EQUAL_YML: &str = r#"---
+ifaces:
+  - name: veth11
+    ethtool:
+      rss:
+        equal: 1"#;
+
+const ETHTOOL_RSS_VETH_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth11
+    state: absent"#;
+
+#[test]
+fn test_change_ethtool_rss_not_supported() {
+    let net_conf: NetConf = serde_yaml::from_str(ETHTOOL_RSS_VETH_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    // veth has no RSS indirection table
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(state.ifaces["veth11"].ethtool.as_ref().unwrap().rss, None);
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_RSS_EQUAL_YML).unwrap();
+    let result = net_conf.apply();
+    assert!(matches!(
+        result,
+        Err(crate::NisporError {
+            kind: crate::ErrorKind::InvalidArgument,
+            ..
+        })
+    ));
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_RSS_VETH_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
+
+const ETHTOOL_RSS_SIM_EQUAL_YML: &str = r#"---
+ifaces:
+  - name: sim0
+    ethtool:
+      rss:
+        equal: 1"#;
+
+#[test]
+fn test_change_ethtool_rss_of_netdevsim() {
+    with_netdevsim_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let table_size = match get_default_rss_info(&state, IFACE_NAME0) {
+            Some(rss) => rss.indirection_table.len(),
+            None => {
+                eprintln!("netdevsim has no RSS support, skipping RSS test");
+                return;
+            }
+        };
+
+        let net_conf: NetConf =
+            serde_yaml::from_str(ETHTOOL_RSS_SIM_EQUAL_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        let rss = get_default_rss_info(&state, IFACE_NAME0).unwrap();
+        assert_eq!(rss.indirection_table, vec![0u32; table_size]);
+
+        // netdevsim only has single receive queue
+        let net_conf: NetConf = serde_yaml::from_str(&format!(
+            r#"---
+ifaces:
+  - name: sim0
+    ethtool:
+      rss:
+        indirection_table: {:?}
+        hash_fields:
+          tcp4: [ip_src, ip_dst, l4_bytes_0_1, l4_bytes_2_3]"#,
+            vec![0u32; table_size]
+        ))
+        .unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        let rss = get_default_rss_info(&state, IFACE_NAME0).unwrap();
+        assert_eq!(rss.indirection_table, vec![0u32; table_size]);
+        assert_eq!(
+            rss.hash_fields.as_ref().and_then(|h| h.get("tcp4")),
+            Some(&vec![
+                "ip_src".to_string(),
+                "ip_dst".to_string(),
+                "l4_bytes_0_1".to_string(),
+                "l4_bytes_2_3".to_string(),
+            ])
+        );
+    });
+}
+
+fn get_default_rss_info<'a>(
+    state: &'a NetState,
+    iface_name: &str,
+) -> Option<&'a crate::EthtoolRssInfo> {
+    state.ifaces[iface_name]
+        .ethtool
+        .as_ref()?
+        .rss
+        .as_ref()?
+        .iter()
+        .find(|r| r.context == 0)
+}
+
+#[test]
+fn test_get_ethtool_timestamping_of_loopback() {
+    let state = NetState::retrieve().unwrap();
+    let ts_info = state.ifaces["lo"]
+        .ethtool
+        .as_ref()
+        .unwrap()
+        .timestamping
+        .as_ref()
+        .unwrap();
+    assert!(ts_info
+        .capabilities
+        .contains(&"software-receive".to_string()));
+    assert!(!ts_info.is_hardware_capable());
+    assert_eq!(ts_info.phc_index, None);
+}
+
+const ETHTOOL_LINK_STATE_VETH_YML: &str = r#"---
+ifaces:
+  - name: veth14
+    type: veth
+    veth:
+      peer: veth14.ep
+  - name: veth14.ep
+    type: veth"#;
+
+const ETHTOOL_LINK_STATE_VETH_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth14
+    state: absent"#;
+
+#[test]
+fn test_get_ethtool_eee_wol_link_state_of_veth() {
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_LINK_STATE_VETH_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    let state = NetState::retrieve().unwrap();
+    let ethtool_info = state.ifaces["veth14"].ethtool.as_ref().unwrap();
+    // veth supports neither EEE nor WoL
+    assert_eq!(ethtool_info.eee, None);
+    assert_eq!(ethtool_info.wol, None);
+    // Both ends of veth are up, hence carrier is on
+    assert_eq!(ethtool_info.link_state.as_ref().map(|l| l.link), Some(true));
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_LINK_STATE_VETH_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
+
+const ETHTOOL_DRIVER_VETH_YML: &str = r#"---
+ifaces:
+  - name: veth12
+    type: veth
+    veth:
+      peer: veth12.ep"#;
+
+const ETHTOOL_PRIVATE_FLAGS_YML: &str = r#"---
+ifaces:
+  - name: veth12
+    ethtool:
+      private_flags:
+        legacy-rx: true"#;
+
+const ETHTOOL_DRIVER_VETH_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth12
+    state: absent"#;
+
+const EXPECTED_VETH_DRIVER_INFO: &str = r#"---
+driver: veth
+supports_statistics: true
+supports_private_flags: false"#;
+
+#[test]
+fn test_get_ethtool_driver_info_and_private_flags() {
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_DRIVER_VETH_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    let state = NetState::retrieve().unwrap();
+    let ethtool_info = state.ifaces["veth12"].ethtool.as_ref().unwrap();
+    assert_value_match(EXPECTED_VETH_DRIVER_INFO, &ethtool_info.driver_info);
+    assert_eq!(ethtool_info.private_flags, None);
+
+    // veth has no private flags
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_PRIVATE_FLAGS_YML).unwrap();
+    let result = net_conf.apply();
+    assert!(matches!(
+        result,
+        Err(crate::NisporError {
+            kind: crate::ErrorKind::InvalidArgument,
+            ..
+        })
+    ));
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ETHTOOL_DRIVER_VETH_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
+
+#[test]
+fn test_parse_ethtool_private_flags() {
+    // Mock of ETHTOOL_MSG_PRIVFLAGS_GET reply as no software interface
+    // supports private flags.
+    let nla = crate::netlink::gen_ethtool_bitset_nla(
+        crate::netlink::ETHTOOL_A_PRIVFLAGS_FLAGS,
+        &[("legacy-rx", true), ("disable-fw-lldp", false)],
+        false,
+    );
+    let mut reply = vec![0u8; nla.buffer_len()];
+    nla.emit(&mut reply);
+
+    let flags = crate::query::parse_private_flags(&reply);
+    assert_eq!(flags.len(), 2);
+    assert_eq!(flags.get("legacy-rx"), Some(&true));
+    assert_eq!(flags.get("disable-fw-lldp"), Some(&false));
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState};
+use pretty_assertions::assert_eq;
+
+use super::utils::assert_value_match;
+
+const GENEVE_CREATE_YML: &str = r#"---
+ifaces:
+  - name: geneve1
+    type: geneve
+    geneve:
+      vni: 1001
+      remote: 192.0.2.1
+      ttl: 64
+      dst_port: 6082
+      df: set"#;
+
+const EXPECTED_GENEVE_INFO: &str = r#"---
+vni: 1001
+remote: 192.0.2.1
+ttl: 64
+df: set
+dst_port: 6082
+collect_metadata: false"#;
+
+const GENEVE_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: geneve1
+    geneve:
+      remote: 192.0.2.2
+      ttl: 32
+      tos: 16"#;
+
+const EXPECTED_CHANGED_GENEVE_INFO: &str = r#"---
+vni: 1001
+remote: 192.0.2.2
+ttl: 32
+tos: 16
+df: set
+dst_port: 6082"#;
+
+const GENEVE_DELETE_YML: &str = r#"---
+ifaces:
+  - name: geneve1
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_geneve() {
+    let net_conf: NetConf = serde_yaml::from_str(GENEVE_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["geneve1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Geneve);
+    assert_value_match(EXPECTED_GENEVE_INFO, &iface.geneve);
+
+    let net_conf: NetConf = serde_yaml::from_str(GENEVE_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_CHANGED_GENEVE_INFO,
+        &state.ifaces["geneve1"].geneve,
+    );
+
+    let net_conf: NetConf = serde_yaml::from_str(GENEVE_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("geneve1"));
+}
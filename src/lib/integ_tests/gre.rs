@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState};
+use pretty_assertions::assert_eq;
+
+use super::utils::assert_value_match;
+
+const GRE_CREATE_YML: &str = r#"---
+ifaces:
+  - name: gre1
+    type: gre
+    gre:
+      local: 192.0.2.1
+      remote: 192.0.2.2
+      ikey: 100
+      okey: 101
+      ttl: 64
+  - name: erspan1
+    type: erspan
+    gre:
+      local: 192.0.2.1
+      remote: 192.0.2.3
+      ikey: 200
+      okey: 200
+      erspan_ver: 1
+      erspan_index: 123
+  - name: gretap1
+    type: gretap
+    gre:
+      local: 192.0.2.1
+      remote: 192.0.2.5"#;
+
+const EXPECTED_GRE_INFO: &str = r#"---
+local: 192.0.2.1
+remote: 192.0.2.2
+ikey: 100
+okey: 101
+iflags: 8192
+oflags: 8192
+ttl: 64"#;
+
+const EXPECTED_ERSPAN_INFO: &str = r#"---
+local: 192.0.2.1
+remote: 192.0.2.3
+ikey: 200
+okey: 200
+erspan_ver: 1
+erspan_index: 123"#;
+
+const EXPECTED_GRETAP_INFO: &str = r#"---
+local: 192.0.2.1
+remote: 192.0.2.5"#;
+
+const GRE_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: gre1
+    gre:
+      remote: 192.0.2.4
+      ttl: 32"#;
+
+const EXPECTED_CHANGED_GRE_INFO: &str = r#"---
+local: 192.0.2.1
+remote: 192.0.2.4
+ikey: 100
+okey: 101
+ttl: 32"#;
+
+const GRE_DELETE_YML: &str = r#"---
+ifaces:
+  - name: gre1
+    state: absent
+  - name: erspan1
+    state: absent
+  - name: gretap1
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_gre() {
+    let net_conf: NetConf = serde_yaml::from_str(GRE_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["gre1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Gre);
+    assert_value_match(EXPECTED_GRE_INFO, &iface.gre);
+    let iface = &state.ifaces["erspan1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Erspan);
+    assert_value_match(EXPECTED_ERSPAN_INFO, &iface.gre);
+    let iface = &state.ifaces["gretap1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::GreTap);
+    assert_value_match(EXPECTED_GRETAP_INFO, &iface.gre);
+
+    let net_conf: NetConf = serde_yaml::from_str(GRE_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(EXPECTED_CHANGED_GRE_INFO, &state.ifaces["gre1"].gre);
+
+    let net_conf: NetConf = serde_yaml::from_str(GRE_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("gre1"));
+    assert_eq!(None, state.ifaces.get("erspan1"));
+    assert_eq!(None, state.ifaces.get("gretap1"));
+}
+
+#[test]
+fn test_gre_iface_type_kind_names() {
+    for (iface_type, name) in [
+        (crate::IfaceType::Gre, "gre"),
+        (crate::IfaceType::GreTap, "gretap"),
+        (crate::IfaceType::Ip6Gre, "ip6gre"),
+        (crate::IfaceType::Ip6GreTap, "ip6gretap"),
+        (crate::IfaceType::Erspan, "erspan"),
+        (crate::IfaceType::Ip6Erspan, "ip6erspan"),
+    ] {
+        assert_eq!(serde_yaml::to_string(&iface_type).unwrap().trim(), name);
+        let parsed: crate::IfaceType = serde_yaml::from_str(name).unwrap();
+        assert_eq!(parsed, iface_type);
+    }
+}
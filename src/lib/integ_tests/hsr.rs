@@ -4,7 +4,7 @@ use std::panic;
 
 use pretty_assertions::assert_eq;
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 
 use super::utils::assert_value_match;
 
@@ -18,7 +18,7 @@ hsr:
   port1: eth1
   port2: eth2
   supervision_addr: 01:15:4e:00:01:2d
-  multicast_spec: 0
+  multicast_spec: 45
   version: 0
   protocol: prp"#;
 
@@ -47,3 +47,84 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const VETH_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth7
+    type: veth
+    veth:
+      peer: veth7.ep
+  - name: veth7.ep
+    type: veth
+  - name: veth8
+    type: veth
+    veth:
+      peer: veth8.ep
+  - name: veth8.ep
+    type: veth"#;
+
+const VETH_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth7
+    type: veth
+    state: absent
+  - name: veth8
+    type: veth
+    state: absent"#;
+
+const HSR_CREATE_YML: &str = r#"---
+ifaces:
+  - name: hsr1
+    type: hsr
+    hsr:
+      port1: veth7
+      port2: veth8
+      multicast_spec: 45
+      protocol: prp"#;
+
+const EXPECTED_CREATED_HSR_INFO: &str = r#"---
+port1: veth7
+port2: veth8
+supervision_addr: 01:15:4e:00:01:2d
+multicast_spec: 45
+protocol: prp"#;
+
+const HSR_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: hsr1
+    hsr:
+      protocol: hsr"#;
+
+const HSR_DELETE_YML: &str = r#"---
+ifaces:
+  - name: hsr1
+    state: absent"#;
+
+#[test]
+fn test_create_delete_hsr() {
+    let net_conf: NetConf = serde_yaml::from_str(VETH_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    let net_conf: NetConf = serde_yaml::from_str(HSR_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["hsr1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Hsr);
+    assert_value_match(EXPECTED_CREATED_HSR_INFO, &iface.hsr);
+
+    // Applying the same config again should be a no-op
+    let net_conf: NetConf = serde_yaml::from_str(HSR_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    // Kernel does not support changing HSR interface
+    let net_conf: NetConf = serde_yaml::from_str(HSR_CHANGE_YML).unwrap();
+    assert!(net_conf.apply().is_err());
+
+    let net_conf: NetConf = serde_yaml::from_str(HSR_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("hsr1"));
+
+    let net_conf: NetConf = serde_yaml::from_str(VETH_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
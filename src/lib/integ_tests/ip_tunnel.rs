@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState};
+use pretty_assertions::assert_eq;
+
+use super::utils::assert_value_match;
+
+const IP_TUNNEL_CREATE_YML: &str = r#"---
+ifaces:
+  - name: ipip1
+    type: ipip
+    ip_tunnel:
+      local: 192.0.2.1
+      remote: 192.0.2.2
+      ttl: 64
+  - name: sit1
+    type: sit
+    ip_tunnel:
+      local: 192.0.2.1
+      ttl: 64
+      ip6rd_prefix: 2001:db8::/32"#;
+
+const EXPECTED_IPIP_INFO: &str = r#"---
+local: 192.0.2.1
+remote: 192.0.2.2
+ttl: 64"#;
+
+const EXPECTED_SIT_INFO: &str = r#"---
+local: 192.0.2.1
+ttl: 64
+ip6rd_prefix: 2001:db8::/32"#;
+
+const IP_TUNNEL_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: ipip1
+    ip_tunnel:
+      ttl: 32"#;
+
+const EXPECTED_CHANGED_IPIP_INFO: &str = r#"---
+local: 192.0.2.1
+remote: 192.0.2.2
+ttl: 32"#;
+
+const IP_TUNNEL_DELETE_YML: &str = r#"---
+ifaces:
+  - name: ipip1
+    state: absent
+  - name: sit1
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_ip_tunnel() {
+    let net_conf: NetConf = serde_yaml::from_str(IP_TUNNEL_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["ipip1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::IpIp);
+    assert_value_match(EXPECTED_IPIP_INFO, &iface.ip_tunnel);
+    let iface = &state.ifaces["sit1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Sit);
+    assert_value_match(EXPECTED_SIT_INFO, &iface.ip_tunnel);
+
+    let net_conf: NetConf = serde_yaml::from_str(IP_TUNNEL_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_CHANGED_IPIP_INFO,
+        &state.ifaces["ipip1"].ip_tunnel,
+    );
+
+    let net_conf: NetConf = serde_yaml::from_str(IP_TUNNEL_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("ipip1"));
+    assert_eq!(None, state.ifaces.get("sit1"));
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState};
+use pretty_assertions::assert_eq;
+
+use super::utils::assert_value_match;
+
+const VETH_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth6
+    type: veth
+    veth:
+      peer: veth6.ep
+  - name: veth6.ep
+    type: veth"#;
+
+const VETH_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth6
+    type: veth
+    state: absent"#;
+
+const IP_VLAN_CREATE_YML: &str = r#"---
+ifaces:
+  - name: ipvlan1
+    type: ip_vlan
+    ip_vlan:
+      base_iface: veth6
+      mode: l2
+      flags: private
+  - name: ipvtap1
+    type: ip_vtap
+    ip_vtap:
+      base_iface: veth6
+      mode: l2
+      flags: private"#;
+
+const EXPECTED_IP_VLAN_INFO: &str = r#"---
+base_iface: veth6
+mode: l2
+flags: private"#;
+
+const IP_VLAN_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: ipvlan1
+    ip_vlan:
+      mode: l3s
+      flags: vepa
+  - name: ipvtap1
+    ip_vtap:
+      mode: l3
+      flags: bridge"#;
+
+const EXPECTED_CHANGED_IP_VLAN_INFO: &str = r#"---
+base_iface: veth6
+mode: l3s
+flags: vepa"#;
+
+const EXPECTED_CHANGED_IP_VTAP_INFO: &str = r#"---
+base_iface: veth6
+mode: l3
+flags: bridge"#;
+
+const IP_VLAN_DELETE_YML: &str = r#"---
+ifaces:
+  - name: ipvlan1
+    state: absent
+  - name: ipvtap1
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_ipvlan_ipvtap() {
+    let net_conf: NetConf = serde_yaml::from_str(VETH_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    let net_conf: NetConf = serde_yaml::from_str(IP_VLAN_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["ipvlan1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::IpVlan);
+    assert_value_match(EXPECTED_IP_VLAN_INFO, &iface.ip_vlan);
+    let iface = &state.ifaces["ipvtap1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::IpVtap);
+    assert_value_match(EXPECTED_IP_VLAN_INFO, &iface.ip_vtap);
+
+    let net_conf: NetConf = serde_yaml::from_str(IP_VLAN_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_CHANGED_IP_VLAN_INFO,
+        &state.ifaces["ipvlan1"].ip_vlan,
+    );
+    assert_value_match(
+        EXPECTED_CHANGED_IP_VTAP_INFO,
+        &state.ifaces["ipvtap1"].ip_vtap,
+    );
+
+    let net_conf: NetConf = serde_yaml::from_str(IP_VLAN_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("ipvlan1"));
+    assert_eq!(None, state.ifaces.get("ipvtap1"));
+
+    let net_conf: NetConf = serde_yaml::from_str(VETH_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
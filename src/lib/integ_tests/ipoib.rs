@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use pretty_assertions::assert_eq;
+
+use crate::{IfaceType, IpoibMode, NetConf, NetState};
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "ipoib1";
+const PKEY: u16 = 0x8001;
+
+const EXPECTED_CHANGED_IPOIB_INFO: &str = r#"---
+pkey: 32769
+mode: connected"#;
+
+const IPOIB_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: ipoib1
+    ipoib:
+      mode: connected"#;
+
+const IPOIB_DELETE_YML: &str = r#"---
+ifaces:
+  - name: ipoib1
+    state: absent"#;
+
+// IPoIB child requires an InfiniBand capable parent which cannot be emulated
+// by software, hence skip this test if no such interface found.
+#[test]
+fn test_create_change_delete_ipoib() {
+    let state = NetState::retrieve().unwrap();
+    let base_iface = match state
+        .ifaces
+        .values()
+        .find(|i| i.iface_type == IfaceType::Infiniband)
+    {
+        Some(i) => i.name.clone(),
+        None => {
+            eprintln!("No InfiniBand interface found, skipping IPoIB test");
+            return;
+        }
+    };
+
+    let net_conf: NetConf = serde_yaml::from_str(&format!(
+        r#"---
+ifaces:
+  - name: {IFACE_NAME}
+    type: ipoib
+    ipoib:
+      base_iface: {base_iface}
+      pkey: {PKEY}
+      mode: datagram"#
+    ))
+    .unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces[IFACE_NAME];
+    assert_eq!(iface.iface_type, IfaceType::Ipoib);
+    let ipoib_info = iface.ipoib.as_ref().unwrap();
+    assert_eq!(ipoib_info.pkey, PKEY);
+    assert_eq!(ipoib_info.mode, IpoibMode::Datagram);
+    assert_eq!(ipoib_info.base_iface.as_ref(), Some(&base_iface));
+
+    let net_conf: NetConf = serde_yaml::from_str(IPOIB_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_CHANGED_IPOIB_INFO,
+        &state.ifaces[IFACE_NAME].ipoib,
+    );
+
+    let net_conf: NetConf = serde_yaml::from_str(IPOIB_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get(IFACE_NAME));
+}
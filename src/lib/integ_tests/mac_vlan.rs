@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 use pretty_assertions::assert_eq;
 
 use std::panic;
@@ -40,3 +40,106 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const VETH_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth2
+    type: veth
+    veth:
+      peer: veth2.ep
+  - name: veth2.ep
+    type: veth"#;
+
+const VETH_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth2
+    type: veth
+    state: absent"#;
+
+const MAC_VLAN_CREATE_YML: &str = r#"---
+ifaces:
+  - name: mac1
+    type: mac_vlan
+    mac_vlan:
+      base_iface: veth2
+      mode: source
+      add_allowed_mac_addresses:
+        - "00:23:45:67:89:1a"
+        - "00:23:45:67:89:1b""#;
+
+const EXPECTED_MAC_VLAN_INFO: &str = r#"---
+base_iface: veth2
+mode: source
+flags: 0
+allowed_mac_addresses:
+  - "00:23:45:67:89:1b"
+  - "00:23:45:67:89:1a""#;
+
+const MAC_VLAN_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: mac1
+    mac_vlan:
+      nopromisc: true
+      add_allowed_mac_addresses:
+        - "00:23:45:67:89:1b"
+        - "00:23:45:67:89:1c"
+      del_allowed_mac_addresses:
+        - "00:23:45:67:89:1a""#;
+
+const EXPECTED_CHANGED_MAC_VLAN_INFO: &str = r#"---
+base_iface: veth2
+mode: source
+flags: 1
+allowed_mac_addresses:
+  - "00:23:45:67:89:1c"
+  - "00:23:45:67:89:1b""#;
+
+const MAC_VLAN_FLUSH_YML: &str = r#"---
+ifaces:
+  - name: mac1
+    mac_vlan:
+      mode: bridge
+      flush_allowed_mac_addresses: true"#;
+
+const MAC_VLAN_DELETE_YML: &str = r#"---
+ifaces:
+  - name: mac1
+    type: mac_vlan
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_macvlan() {
+    let net_conf: NetConf = serde_yaml::from_str(VETH_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    let net_conf: NetConf = serde_yaml::from_str(MAC_VLAN_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["mac1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::MacVlan);
+    assert_value_match(EXPECTED_MAC_VLAN_INFO, &iface.mac_vlan);
+
+    let net_conf: NetConf = serde_yaml::from_str(MAC_VLAN_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_CHANGED_MAC_VLAN_INFO,
+        &state.ifaces["mac1"].mac_vlan,
+    );
+
+    // Allowed MAC addresses are only valid for source mode
+    let net_conf: NetConf = serde_yaml::from_str(MAC_VLAN_FLUSH_YML).unwrap();
+    let result = net_conf.apply();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert!(matches!(e.kind, crate::ErrorKind::InvalidArgument));
+    }
+
+    let net_conf: NetConf = serde_yaml::from_str(MAC_VLAN_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("mac1"));
+
+    let net_conf: NetConf = serde_yaml::from_str(VETH_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
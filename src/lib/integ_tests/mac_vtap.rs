@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 use pretty_assertions::assert_eq;
 
 use std::panic;
@@ -40,3 +40,87 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const VETH_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth3
+    type: veth
+    veth:
+      peer: veth3.ep
+  - name: veth3.ep
+    type: veth"#;
+
+const VETH_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth3
+    type: veth
+    state: absent"#;
+
+const MAC_VTAP_CREATE_YML: &str = r#"---
+ifaces:
+  - name: macvtap1
+    type: mac_vtap
+    mac_vtap:
+      base_iface: veth3
+      mode: source
+      nopromisc: true
+      add_allowed_mac_addresses:
+        - "00:23:45:67:89:1a""#;
+
+const EXPECTED_MAC_VTAP_CREATED_INFO: &str = r#"---
+base_iface: veth3
+mode: source
+flags: 1
+allowed_mac_addresses:
+  - "00:23:45:67:89:1a""#;
+
+const MAC_VTAP_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: macvtap1
+    mac_vtap:
+      mode: source
+      flush_allowed_mac_addresses: true
+      add_allowed_mac_addresses:
+        - "00:23:45:67:89:1b""#;
+
+const EXPECTED_MAC_VTAP_CHANGED_INFO: &str = r#"---
+base_iface: veth3
+mode: source
+flags: 1
+allowed_mac_addresses:
+  - "00:23:45:67:89:1b""#;
+
+const MAC_VTAP_DELETE_YML: &str = r#"---
+ifaces:
+  - name: macvtap1
+    type: mac_vtap
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_macvtap() {
+    let net_conf: NetConf = serde_yaml::from_str(VETH_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    let net_conf: NetConf = serde_yaml::from_str(MAC_VTAP_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["macvtap1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::MacVtap);
+    assert_value_match(EXPECTED_MAC_VTAP_CREATED_INFO, &iface.mac_vtap);
+
+    let net_conf: NetConf = serde_yaml::from_str(MAC_VTAP_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_MAC_VTAP_CHANGED_INFO,
+        &state.ifaces["macvtap1"].mac_vtap,
+    );
+
+    let net_conf: NetConf = serde_yaml::from_str(MAC_VTAP_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("macvtap1"));
+
+    let net_conf: NetConf = serde_yaml::from_str(VETH_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
@@ -4,7 +4,7 @@ use std::panic;
 
 use pretty_assertions::assert_eq;
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 
 use super::utils::assert_value_match;
 
@@ -54,3 +54,238 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const VETH_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth5
+    type: veth
+    veth:
+      peer: veth5.ep
+  - name: veth5.ep
+    type: veth"#;
+
+const MACSEC_CREATE_YML: &str = r#"---
+ifaces:
+  - name: macsec1
+    type: mac_sec
+    macsec:
+      base_iface: veth5
+      encrypt: true
+      tx_sa:
+        - an: 0
+          pn: 1
+          active: true
+          key_id: "01"
+          key: "81818181818181818181818181818181"
+        - an: 1
+          pn: 1
+          active: true
+          key_id: "03"
+          key: "83838383838383838383838383838383"
+      rx_sc:
+        - sci: 1234
+          active: true
+          sa:
+            - an: 1
+              pn: 100
+              active: true
+              key_id: "02"
+              key: "82828282828282828282828282828282""#;
+
+const EXPECTED_MACSEC_SA_INFO: &str = r#"---
+encrypt: true
+base_iface: veth5
+tx_sa:
+  - an: 0
+    pn: 1
+    active: true
+    key_id: "01000000000000000000000000000000"
+  - an: 1
+    pn: 1
+    active: true
+    key_id: "03000000000000000000000000000000"
+rx_sc:
+  - sci: 1234
+    active: true
+    sa:
+      - an: 1
+        pn: 100
+        active: true
+        key_id: "02000000000000000000000000000000""#;
+
+// Replace the key of active SA 0 and remove active SA 1
+const MACSEC_REKEY_YML: &str = r#"---
+ifaces:
+  - name: macsec1
+    macsec:
+      tx_sa:
+        - an: 0
+          pn: 1
+          active: true
+          key_id: "04"
+          key: "84848484848484848484848484848484"
+        - an: 1
+          remove: true"#;
+
+const EXPECTED_REKEYED_MACSEC_SA_INFO: &str = r#"---
+tx_sa:
+  - an: 0
+    pn: 1
+    active: true
+    key_id: "04000000000000000000000000000000""#;
+
+const MACSEC_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: macsec1
+    macsec:
+      encrypt: false
+      tx_sa:
+        - an: 0
+          active: false
+      rx_sc:
+        - sci: 1234
+          remove: true"#;
+
+const EXPECTED_CHANGED_MACSEC_SA_INFO: &str = r#"---
+encrypt: false
+tx_sa:
+  - an: 0
+    active: false
+    key_id: "04000000000000000000000000000000"
+rx_sc: []"#;
+
+const MACSEC_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth5
+    type: veth
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_macsec() {
+    let net_conf: NetConf = serde_yaml::from_str(VETH_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let net_conf: NetConf = serde_yaml::from_str(MACSEC_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["macsec1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::MacSec);
+    assert_value_match(EXPECTED_MACSEC_SA_INFO, &iface.macsec);
+    // Key should never be reported
+    let state_str = serde_json::to_string(&state).unwrap();
+    assert!(!state_str.contains("8181818181"));
+    assert!(!state_str.contains("8282828282"));
+
+    let net_conf: NetConf = serde_yaml::from_str(MACSEC_REKEY_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_REKEYED_MACSEC_SA_INFO,
+        &state.ifaces["macsec1"].macsec,
+    );
+
+    let net_conf: NetConf = serde_yaml::from_str(MACSEC_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_CHANGED_MACSEC_SA_INFO,
+        &state.ifaces["macsec1"].macsec,
+    );
+
+    let net_conf: NetConf = serde_yaml::from_str(MACSEC_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("macsec1"));
+}
+
+const VETH_XPN_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth13
+    type: veth
+    veth:
+      peer: veth13.ep
+  - name: veth13.ep
+    type: veth"#;
+
+const MACSEC_XPN_CREATE_YML: &str = r#"---
+ifaces:
+  - name: macsec2
+    type: mac_sec
+    macsec:
+      base_iface: veth13
+      cipher: gcm-aes-xpn128
+      tx_sa:
+        - an: 0
+          pn: 4294967296
+          active: true
+          key_id: "01"
+          key: "81818181818181818181818181818181"
+          ssci: 1
+          salt: "838383838383838383838383"
+      rx_sc:
+        - sci: 1234
+          active: true
+          sa:
+            - an: 1
+              pn: 100
+              active: true
+              key_id: "02"
+              key: "82828282828282828282828282828282"
+              ssci: 2
+              salt: "848484848484848484848484""#;
+
+const EXPECTED_MACSEC_XPN_SA_INFO: &str = r#"---
+cipher: gcm-aes-xpn128
+base_iface: veth13
+tx_sa:
+  - an: 0
+    pn: 4294967296
+    active: true
+    key_id: "01000000000000000000000000000000"
+    ssci: 1
+rx_sc:
+  - sci: 1234
+    active: true
+    sa:
+      - an: 1
+        pn: 100
+        active: true
+        key_id: "02000000000000000000000000000000"
+        ssci: 2"#;
+
+const MACSEC_XPN_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth13
+    type: veth
+    state: absent"#;
+
+#[test]
+fn test_create_delete_macsec_xpn() {
+    let net_conf: NetConf = serde_yaml::from_str(VETH_XPN_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let net_conf: NetConf =
+        serde_yaml::from_str(MACSEC_XPN_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["macsec2"];
+    assert_value_match(EXPECTED_MACSEC_XPN_SA_INFO, &iface.macsec);
+    // Salt should never be reported
+    let state_str = serde_json::to_string(&state).unwrap();
+    assert!(!state_str.contains("8383838383"));
+    assert!(!state_str.contains("8484848484"));
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(MACSEC_XPN_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("macsec2"));
+}
+
+#[test]
+fn test_macsec_conf_debug_hide_key() {
+    let net_conf: NetConf =
+        serde_yaml::from_str(MACSEC_XPN_CREATE_YML).unwrap();
+    let debug_str = format!("{net_conf:?}");
+    assert!(!debug_str.contains("8181818181"));
+    assert!(!debug_str.contains("8383838383"));
+    assert!(debug_str.contains("<hidden>"));
+}
@@ -11,14 +11,28 @@ mod bridge;
 #[cfg(test)]
 mod bridge_vlan_filter;
 #[cfg(test)]
+mod bridge_vlan_opts;
+#[cfg(test)]
+mod devlink;
+#[cfg(test)]
 mod dummy;
 #[cfg(test)]
 mod ethtool;
 #[cfg(test)]
+mod geneve;
+#[cfg(test)]
+mod gre;
+#[cfg(test)]
 mod hsr;
 #[cfg(test)]
 mod ip;
 #[cfg(test)]
+mod ip_tunnel;
+#[cfg(test)]
+mod ip_vlan;
+#[cfg(test)]
+mod ipoib;
+#[cfg(test)]
 mod mac_vlan;
 #[cfg(test)]
 mod mac_vtap;
@@ -29,6 +43,8 @@ mod route;
 #[cfg(test)]
 mod route_rule;
 #[cfg(test)]
+mod sriov;
+#[cfg(test)]
 mod tap;
 #[cfg(test)]
 mod tun;
@@ -41,4 +57,6 @@ mod vrf;
 #[cfg(test)]
 mod vxlan;
 #[cfg(test)]
+mod wireguard;
+#[cfg(test)]
 mod xfrm;
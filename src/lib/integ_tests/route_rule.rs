@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 
 use std::panic;
 
@@ -78,3 +78,64 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const ROUTE_RULE_ADD_YML: &str = r#"---
+rules:
+  - src: 192.0.2.3/32
+    dst: 192.0.2.4/32
+    iif: lo
+    table: 101
+    priority: 996
+  - address_family: ipv6
+    fw_mark: 16
+    table: 101
+    priority: 996"#;
+
+const EXPECTED_ADDED_RULES: &str = r#"---
+- action: table
+  address_family: ipv6
+  table: 101
+  fw_mark: 16
+  priority: 996
+- action: table
+  address_family: ipv4
+  table: 101
+  dst: 192.0.2.4/32
+  src: 192.0.2.3/32
+  iif: lo
+  priority: 996"#;
+
+const ROUTE_RULE_REMOVE_YML: &str = r#"---
+rules:
+  - src: 192.0.2.3/32
+    dst: 192.0.2.4/32
+    iif: lo
+    table: 101
+    priority: 996
+    remove: true
+  - address_family: ipv6
+    fw_mark: 16
+    table: 101
+    priority: 996
+    remove: true"#;
+
+#[test]
+fn test_add_remove_route_rule_yaml() {
+    let net_conf: NetConf = serde_yaml::from_str(ROUTE_RULE_ADD_YML).unwrap();
+    net_conf.apply().unwrap();
+    // Applying twice should not fail
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let rules: Vec<_> = state
+        .rules
+        .into_iter()
+        .filter(|r| r.table == Some(101))
+        .collect();
+    assert_value_match(EXPECTED_ADDED_RULES, &rules);
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(ROUTE_RULE_REMOVE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert!(!state.rules.iter().any(|r| r.table == Some(101)));
+}
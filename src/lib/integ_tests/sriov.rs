@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState, SriovEswitchMode};
+
+use std::panic;
+
+use super::utils::{assert_value_match, cmd_exec};
+
+const IFACE_NAME: &str = "sim0";
+
+const SRIOV_CONF_YML: &str = r#"---
+ifaces:
+  - name: sim0
+    sriov:
+      total_vfs: 2
+      vfs:
+        - id: 0
+          mac: 36:f7:09:ef:95:f0
+          vlan_id: 100
+          qos: 2
+          spoof_check: true
+          trust: true
+          link_state: enable
+        - id: 1
+          mac: 36:f7:09:ef:95:f1
+          min_tx_rate: 100
+          max_tx_rate: 1000
+          query_rss: true"#;
+
+const EXPECTED_SRIOV_INFO: &str = r#"---
+vfs:
+  - id: 0
+    mac: 36:f7:09:ef:95:f0
+    vlan_id: 100
+    qos: 2
+    spoof_check: true
+    trust: true
+    link_state: enable
+  - id: 1
+    mac: 36:f7:09:ef:95:f1
+    min_tx_rate: 100
+    max_tx_rate: 1000
+    query_rss: true"#;
+
+const SRIOV_VF_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: sim0
+    sriov:
+      vfs:
+        - id: 0
+          vlan_id: 0
+          trust: false"#;
+
+const EXPECTED_SRIOV_VF_CHANGED_INFO: &str = r#"---
+vfs:
+  - id: 0
+    mac: 36:f7:09:ef:95:f0
+    vlan_id: 0
+    qos: 2
+    trust: false"#;
+
+const SRIOV_VF_QINQ_YML: &str = r#"---
+ifaces:
+  - name: sim0
+    sriov:
+      vfs:
+        - id: 1
+          vlan_id: 200
+          qos: 1
+          vlan_proto: 802.1ad"#;
+
+// Changing QoS only should not reset VLAN protocol to 802.1Q
+const SRIOV_VF_QINQ_QOS_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: sim0
+    sriov:
+      vfs:
+        - id: 1
+          qos: 3"#;
+
+const EXPECTED_SRIOV_VF_QINQ_INFO: &str = r#"---
+vfs:
+  - id: 0
+  - id: 1
+    vlan_id: 200
+    qos: 3
+    vlan_proto: 802.1ad"#;
+
+const SRIOV_INVALID_VF_YML: &str = r#"---
+ifaces:
+  - name: sim0
+    sriov:
+      vfs:
+        - id: 2
+          trust: true"#;
+
+const SRIOV_REMOVE_VFS_YML: &str = r#"---
+ifaces:
+  - name: sim0
+    sriov:
+      total_vfs: 0"#;
+
+#[test]
+fn test_change_sriov_vfs() {
+    with_netdevsim_iface(|| {
+        let net_conf: NetConf = serde_yaml::from_str(SRIOV_CONF_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        assert_value_match(
+            EXPECTED_SRIOV_INFO,
+            &state.ifaces[IFACE_NAME].sriov,
+        );
+
+        // Applying the same config again should be a no-op
+        net_conf.apply().unwrap();
+
+        let net_conf: NetConf =
+            serde_yaml::from_str(SRIOV_VF_CHANGE_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        assert_value_match(
+            EXPECTED_SRIOV_VF_CHANGED_INFO,
+            &state.ifaces[IFACE_NAME].sriov,
+        );
+
+        let net_conf: NetConf =
+            serde_yaml::from_str(SRIOV_VF_QINQ_YML).unwrap();
+        net_conf.apply().unwrap();
+        let net_conf: NetConf =
+            serde_yaml::from_str(SRIOV_VF_QINQ_QOS_CHANGE_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        assert_value_match(
+            EXPECTED_SRIOV_VF_QINQ_INFO,
+            &state.ifaces[IFACE_NAME].sriov,
+        );
+
+        let net_conf: NetConf =
+            serde_yaml::from_str(SRIOV_INVALID_VF_YML).unwrap();
+        assert!(matches!(
+            net_conf.apply(),
+            Err(crate::NisporError {
+                kind: crate::ErrorKind::InvalidArgument,
+                ..
+            })
+        ));
+
+        let net_conf: NetConf =
+            serde_yaml::from_str(SRIOV_REMOVE_VFS_YML).unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        assert_eq!(
+            state.ifaces[IFACE_NAME].sriov.as_ref().map(|s| s.vfs.len()),
+            Some(0)
+        );
+    });
+}
+
+#[test]
+fn test_get_sriov_eswitch_mode() {
+    with_netdevsim_iface(|| {
+        assert!(set_eswitch_mode("switchdev"));
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        assert_eq!(
+            iface.sriov.as_ref().and_then(|s| s.eswitch_mode.as_ref()),
+            Some(&SriovEswitchMode::Switchdev)
+        );
+        assert_eq!(iface.phys_port_name.as_deref(), Some("p1"));
+        assert!(iface.phys_switch_id.is_some());
+        assert!(iface.sriov_vf_representor.is_none());
+
+        assert!(set_eswitch_mode("legacy"));
+        let state = NetState::retrieve().unwrap();
+        assert_eq!(
+            state.ifaces[IFACE_NAME]
+                .sriov
+                .as_ref()
+                .and_then(|s| s.eswitch_mode.as_ref()),
+            Some(&SriovEswitchMode::Legacy)
+        );
+    });
+}
+
+fn set_eswitch_mode(mode: &str) -> bool {
+    cmd_exec(
+        "sudo",
+        vec![
+            "devlink",
+            "dev",
+            "eswitch",
+            "set",
+            "netdevsim/netdevsim1",
+            "mode",
+            mode,
+        ],
+    )
+}
+
+fn with_netdevsim_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("sim");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 use pretty_assertions::assert_eq;
 
 use std::panic;
@@ -43,3 +43,142 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const TAP_CREATE_YML: &str = r#"---
+ifaces:
+  - name: tap2
+    type: tun
+    tun:
+      mode: tap
+      owner: 1001
+      group: 0
+      multi_queue: true
+      vnet_hdr: true"#;
+
+const EXPECTED_CREATED_TAP_INFO: &str = r#"---
+mode: tap
+owner: 1001
+group: 0
+pi: false
+vnet_hdr: true
+multi_queue: true
+persist: true"#;
+
+const TAP_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: tap2
+    tun:
+      owner: 1002
+      vnet_hdr: false"#;
+
+const EXPECTED_CHANGED_TAP_INFO: &str = r#"---
+mode: tap
+owner: 1002
+group: 0
+pi: false
+vnet_hdr: false
+multi_queue: true
+persist: true"#;
+
+const TAP_DELETE_YML: &str = r#"---
+ifaces:
+  - name: tap2
+    type: tun
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_tap() {
+    let net_conf: NetConf = serde_yaml::from_str(TAP_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["tap2"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Tun);
+    assert_value_match(EXPECTED_CREATED_TAP_INFO, &iface.tun);
+
+    let net_conf: NetConf = serde_yaml::from_str(TAP_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(EXPECTED_CHANGED_TAP_INFO, &state.ifaces["tap2"].tun);
+
+    let net_conf: NetConf = serde_yaml::from_str(TAP_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("tap2"));
+}
+
+const TAP_IN_USE_CREATE_YML: &str = r#"---
+ifaces:
+  - name: tap3
+    type: tun
+    tun:
+      mode: tap"#;
+
+const TAP_IN_USE_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: tap3
+    tun:
+      vnet_hdr: true"#;
+
+const TAP_IN_USE_DELETE_YML: &str = r#"---
+ifaces:
+  - name: tap3
+    type: tun
+    state: absent"#;
+
+// The `struct ifreq` with only `ifr_flags` of the union used.
+#[repr(C)]
+struct TunIfReq {
+    ifr_name: [u8; libc::IFNAMSIZ],
+    ifr_flags: i16,
+    _padding: [u8; 22],
+}
+
+// Attach to the single queue TAP like a VM or VPN daemon would do.
+fn attach_tap(name: &str) -> std::fs::File {
+    use std::os::unix::io::AsRawFd;
+
+    const TUNSETIFF: u64 = 0x400454ca;
+    const IFF_TAP: i16 = 0x0002;
+    const IFF_NO_PI: i16 = 0x1000;
+
+    let fd = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/net/tun")
+        .unwrap();
+    let mut ifr = TunIfReq {
+        ifr_name: [0; libc::IFNAMSIZ],
+        ifr_flags: IFF_TAP | IFF_NO_PI,
+        _padding: [0; 22],
+    };
+    ifr.ifr_name[..name.len()].copy_from_slice(name.as_bytes());
+    let rc = unsafe {
+        libc::ioctl(fd.as_raw_fd(), TUNSETIFF as _, &mut ifr as *mut TunIfReq)
+    };
+    assert_eq!(rc, 0);
+    fd
+}
+
+#[test]
+fn test_change_tap_in_use() {
+    let net_conf: NetConf =
+        serde_yaml::from_str(TAP_IN_USE_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+
+    let fd = attach_tap("tap3");
+    let net_conf: NetConf =
+        serde_yaml::from_str(TAP_IN_USE_CHANGE_YML).unwrap();
+    let result = net_conf.apply();
+    drop(fd);
+    assert!(matches!(
+        result,
+        Err(crate::NisporError {
+            kind: crate::ErrorKind::InvalidArgument,
+            ..
+        })
+    ));
+
+    let net_conf: NetConf =
+        serde_yaml::from_str(TAP_IN_USE_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+}
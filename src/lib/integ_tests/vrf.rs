@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 use pretty_assertions::assert_eq;
 
 use std::panic;
@@ -38,3 +38,69 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const VRF_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth4
+    type: veth
+    controller: vrf1
+    veth:
+      peer: veth4.ep
+  - name: veth4.ep
+    type: veth
+  - name: vrf1
+    type: vrf
+    vrf:
+      table_id: 1001
+      l3mdev_rule: true
+routes:
+  - dst: 198.51.100.0/24
+    oif: veth4
+    vrf: vrf1
+rules:
+  - src: 192.0.2.1/32
+    priority: 995
+    vrf: vrf1"#;
+
+const EXPECTED_CREATED_VRF_INFO: &str = r#"---
+table_id: 1001
+subordinates:
+  - veth4"#;
+
+const VRF_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth4
+    type: veth
+    state: absent
+  - name: vrf1
+    type: vrf
+    state: absent
+rules:
+  - src: 192.0.2.1/32
+    priority: 995
+    table: 1001
+    remove: true"#;
+
+#[test]
+fn test_create_delete_vrf() {
+    let net_conf: NetConf = serde_yaml::from_str(VRF_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["vrf1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Vrf);
+    assert_value_match(EXPECTED_CREATED_VRF_INFO, &iface.vrf);
+    assert!(state.routes.iter().any(
+        |r| r.table == 1001 && r.dst.as_deref() == Some("198.51.100.0/24")
+    ));
+    assert!(state
+        .rules
+        .iter()
+        .any(|r| r.table == Some(1001) && r.priority == Some(995)));
+    assert!(state.rules.iter().any(|r| r.l3mdev == Some(true)));
+
+    let net_conf: NetConf = serde_yaml::from_str(VRF_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("vrf1"));
+    assert!(!state.rules.iter().any(|r| r.priority == Some(995)));
+}
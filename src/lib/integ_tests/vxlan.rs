@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 use pretty_assertions::assert_eq;
 
 use std::panic;
@@ -62,3 +62,83 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const VXLAN_CREATE_YML: &str = r#"---
+ifaces:
+  - name: vxlan1
+    type: vxlan
+    vxlan:
+      vxlan_id: 102
+      remote: 8.8.4.4
+      local: 1.1.1.2
+      dst_port: 4790
+      learning: false
+      src_port_min: 1000
+      src_port_max: 2000"#;
+
+const VXLAN_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: vxlan1
+    type: vxlan
+    vxlan:
+      remote: 8.8.8.8
+      ttl: 64
+      learning: true"#;
+
+const VXLAN_CHANGE_VNI_YML: &str = r#"---
+ifaces:
+  - name: vxlan1
+    type: vxlan
+    vxlan:
+      vxlan_id: 103"#;
+
+const VXLAN_DELETE_YML: &str = r#"---
+ifaces:
+  - name: vxlan1
+    type: vxlan
+    state: absent"#;
+
+const EXPECTED_CREATED_VXLAN_INFO: &str = r#"---
+remote: 8.8.4.4
+vxlan_id: 102
+local: 1.1.1.2
+learning: false
+src_port_min: 1000
+src_port_max: 2000
+dst_port: 4790"#;
+
+const EXPECTED_CHANGED_VXLAN_INFO: &str = r#"---
+remote: 8.8.8.8
+vxlan_id: 102
+local: 1.1.1.2
+ttl: 64
+learning: true
+dst_port: 4790"#;
+
+#[test]
+fn test_create_change_delete_vxlan() {
+    let net_conf: NetConf = serde_yaml::from_str(VXLAN_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["vxlan1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Vxlan);
+    assert_value_match(EXPECTED_CREATED_VXLAN_INFO, &iface.vxlan);
+
+    let net_conf: NetConf = serde_yaml::from_str(VXLAN_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["vxlan1"];
+    assert_value_match(EXPECTED_CHANGED_VXLAN_INFO, &iface.vxlan);
+
+    let net_conf: NetConf = serde_yaml::from_str(VXLAN_CHANGE_VNI_YML).unwrap();
+    let result = net_conf.apply();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert!(matches!(e.kind, crate::ErrorKind::InvalidArgument));
+    }
+
+    let net_conf: NetConf = serde_yaml::from_str(VXLAN_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("vxlan1"));
+}
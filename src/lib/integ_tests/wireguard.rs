@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState};
+use pretty_assertions::assert_eq;
+
+use super::utils::assert_value_match;
+
+const WG_CREATE_YML: &str = r#"---
+ifaces:
+  - name: wg1
+    type: wireguard
+    wireguard:
+      private_key: ERERERERERERERERERERERERERERERERERERERERERE=
+      listen_port: 51820
+      fwmark: 100
+      peers:
+        - public_key: AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyA=
+          preshared_key: ISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+P0A=
+          endpoint: 192.0.2.1:51820
+          allowed_ips:
+            - 198.51.100.1/24
+            - 2001:db8::/64
+          persistent_keepalive: 25"#;
+
+const EXPECTED_WG_INFO: &str = r#"---
+listen_port: 51820
+fwmark: 100
+peers:
+  - public_key: AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyA=
+    endpoint: 192.0.2.1:51820
+    allowed_ips:
+      - 198.51.100.0/24
+      - 2001:db8::/64
+    latest_handshake: 0
+    persistent_keepalive: 25"#;
+
+const WG_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: wg1
+    wireguard:
+      listen_port: 51821
+      peers:
+        - public_key: ISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+P0A=
+          allowed_ips:
+            - 203.0.113.0/24
+      replace_peers: true"#;
+
+const EXPECTED_CHANGED_WG_INFO: &str = r#"---
+listen_port: 51821
+fwmark: 100
+peers:
+  - public_key: ISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+P0A=
+    allowed_ips:
+      - 203.0.113.0/24"#;
+
+const WG_DELETE_YML: &str = r#"---
+ifaces:
+  - name: wg1
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_wireguard() {
+    let net_conf: NetConf = serde_yaml::from_str(WG_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["wg1"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Wireguard);
+    assert_value_match(EXPECTED_WG_INFO, &iface.wireguard);
+    assert!(iface
+        .wireguard
+        .as_ref()
+        .and_then(|w| w.public_key.as_ref())
+        .is_some());
+    // Private key and preshared key should never be reported
+    let state_str = serde_yaml::to_string(&state).unwrap();
+    assert!(!state_str.contains("ERERERERERERERERERERERERERERERERERERERERERE="));
+    assert!(!state_str.contains("preshared_key"));
+
+    let net_conf: NetConf = serde_yaml::from_str(WG_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match(
+        EXPECTED_CHANGED_WG_INFO,
+        &state.ifaces["wg1"].wireguard,
+    );
+    assert_eq!(
+        state.ifaces["wg1"]
+            .wireguard
+            .as_ref()
+            .map(|w| w.peers.len()),
+        Some(1)
+    );
+
+    let net_conf: NetConf = serde_yaml::from_str(WG_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("wg1"));
+}
@@ -4,7 +4,7 @@ use std::panic;
 
 use pretty_assertions::assert_eq;
 
-use crate::NetState;
+use crate::{NetConf, NetState};
 
 use super::utils::assert_value_match;
 
@@ -42,3 +42,41 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const XFRM_CREATE_YML: &str = r#"---
+ifaces:
+  - name: xfrm2
+    type: xfrm
+    xfrm:
+      iface_id: 100"#;
+
+const XFRM_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: xfrm2
+    xfrm:
+      iface_id: 101"#;
+
+const XFRM_DELETE_YML: &str = r#"---
+ifaces:
+  - name: xfrm2
+    state: absent"#;
+
+#[test]
+fn test_create_change_delete_xfrm() {
+    let net_conf: NetConf = serde_yaml::from_str(XFRM_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["xfrm2"];
+    assert_eq!(iface.iface_type, crate::IfaceType::Xfrm);
+    assert_value_match("iface_id: 100", &iface.xfrm);
+
+    let net_conf: NetConf = serde_yaml::from_str(XFRM_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_value_match("iface_id: 101", &state.ifaces["xfrm2"].xfrm);
+
+    let net_conf: NetConf = serde_yaml::from_str(XFRM_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("xfrm2"));
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Some ethtool features (driver statistics, RSS hash fields) have no netlink
+// equivalent, use the SIOCETHTOOL ioctl like the `ethtool` command does.
+
+use std::os::unix::{io::AsRawFd, net::UnixDatagram};
+
+use crate::NisporError;
+
+const SIOCETHTOOL: u64 = 0x8946;
+
+// The `struct ifreq` with only `ifr_data` of the union used.
+#[repr(C)]
+struct EthtoolIfReq {
+    ifr_name: [u8; libc::IFNAMSIZ],
+    ifr_data: *mut libc::c_void,
+    _padding: [u8; 16],
+}
+
+// The `data` should hold the ethtool command struct starting with the `u32`
+// command number. The kernel will write the reply back to `data`.
+pub(crate) fn ethtool_ioctl(
+    iface_name: &str,
+    data: &mut [u8],
+) -> Result<(), std::io::Error> {
+    if iface_name.len() >= libc::IFNAMSIZ {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Interface name {iface_name} is too long"),
+        ));
+    }
+    let socket = UnixDatagram::unbound()?;
+    let mut ifr = EthtoolIfReq {
+        ifr_name: [0; libc::IFNAMSIZ],
+        ifr_data: data.as_mut_ptr() as *mut libc::c_void,
+        _padding: [0; 16],
+    };
+    for (i, c) in iface_name.as_bytes().iter().enumerate() {
+        ifr.ifr_name[i] = *c;
+    }
+    let rc = unsafe {
+        libc::ioctl(
+            socket.as_raw_fd(),
+            SIOCETHTOOL as _,
+            &mut ifr as *mut EthtoolIfReq,
+        )
+    };
+    if rc < 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn ethtool_ioctl_error(
+    iface_name: &str,
+    e: std::io::Error,
+) -> NisporError {
+    let msg = format!("Failed to SIOCETHTOOL on interface {iface_name}: {e}");
+    match e.raw_os_error() {
+        Some(libc::EPERM) => NisporError::permission_deny(msg),
+        Some(libc::EINVAL) | Some(libc::EOPNOTSUPP) => {
+            NisporError::invalid_argument(msg)
+        }
+        _ => NisporError::bug(msg),
+    }
+}
+
+pub(crate) fn is_ioctl_not_supported(e: &std::io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(libc::EOPNOTSUPP))
+}
@@ -1,8 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod base64;
 mod error;
 #[cfg(test)]
 mod integ_tests;
+mod ioctl;
 mod mac;
 // Since rust 1.62, the `#[default]` can be used for setting default value of
 // `#[derive(Default)]` for enum. The cargo clippy will complain if we impl the
@@ -17,8 +19,15 @@ mod netlink;
 mod query;
 
 pub use crate::conf::{
-    BondConf, BridgeConf, IfaceConf, IpAddrConf, IpConf, RouteConf, VethConf,
-    VlanConf,
+    BondConf, BridgeConf, BridgePortConf, BridgeVlanGlobalOptsConf,
+    BridgeVlanOptsConf, DevlinkConf, DevlinkDeviceConf, DevlinkParamConf,
+    DevlinkSfConf, EthtoolCoalesceConf, EthtoolConf, EthtoolLinkModeConf,
+    EthtoolPauseConf, EthtoolRingConf, EthtoolRssConf, GeneveConf, GreConf,
+    HsrConf, IfaceConf, IpAddrConf, IpConf, IpTunnelConf, IpVlanConf,
+    IpoibConf, MacSecConf, MacSecRxScConf, MacSecSaConf, MacVlanConf,
+    MacVtapConf, RouteConf, RouteRuleConf, SriovConf, TunConf, VethConf,
+    VfConf, VlanConf, VrfConf, VxlanConf, WireguardConf, WireguardPeerConf,
+    XfrmConf,
 };
 pub use crate::error::{ErrorKind, NisporError};
 pub use crate::filter::{
@@ -33,15 +42,26 @@ pub use crate::query::{
     BondMode, BondModeArpAllTargets, BondPrimaryReselect, BondSubordinateInfo,
     BondSubordinateState, BondXmitHashPolicy, BridgeInfo, BridgePortInfo,
     BridgePortMulticastRouterType, BridgePortStpState, BridgeStpState,
-    BridgeVlanEntry, BridgeVlanProtocol, ControllerType, EthtoolCoalesceInfo,
-    EthtoolFeatureInfo, EthtoolInfo, EthtoolLinkModeDuplex,
-    EthtoolLinkModeInfo, EthtoolPauseInfo, EthtoolRingInfo, HsrInfo,
-    HsrProtocol, Iface, IfaceFlag, IfaceState, IfaceType, IpFamily, IpoibInfo,
-    IpoibMode, Ipv4AddrInfo, Ipv4Info, Ipv6AddrFlag, Ipv6AddrInfo, Ipv6Info,
-    MacSecCipherId, MacSecInfo, MacSecOffload, MacSecValidate, MacVlanInfo,
-    MacVlanMode, MacVtapInfo, MacVtapMode, Mptcp, MptcpAddress,
-    MptcpAddressFlag, MultipathRoute, MultipathRouteFlags, Route,
-    RouteProtocol, RouteRule, RouteScope, RouteType, RuleAction, SriovInfo,
-    TunInfo, TunMode, VethInfo, VfInfo, VfLinkState, VfState, VlanInfo,
-    VlanProtocol, VrfInfo, VrfSubordinateInfo, VxlanInfo, XfrmInfo,
+    BridgeVlanEntry, BridgeVlanGlobalOpts, BridgeVlanOpts, BridgeVlanProtocol,
+    ControllerType, Devlink, DevlinkDevice, DevlinkEswitchEncapMode,
+    DevlinkEswitchInlineMode, DevlinkParam, DevlinkParamCmode,
+    DevlinkParamData, DevlinkParamType, DevlinkParamValue, DevlinkPort,
+    DevlinkPortFlavour, DevlinkPortFunctionOpstate, DevlinkPortFunctionState,
+    DevlinkPortType, EthtoolChannelInfo, EthtoolCoalesceInfo,
+    EthtoolDriverInfo, EthtoolEeeInfo, EthtoolFeatureInfo, EthtoolFecInfo,
+    EthtoolInfo, EthtoolLinkExtState, EthtoolLinkModeDuplex,
+    EthtoolLinkModeInfo, EthtoolLinkStateInfo, EthtoolPauseInfo,
+    EthtoolRingInfo, EthtoolRssHashFunction, EthtoolRssInfo, EthtoolStatsInfo,
+    EthtoolTimestampingInfo, EthtoolWolInfo, GeneveDf, GeneveInfo,
+    GreErspanDir, GreInfo, HsrInfo, HsrProtocol, Iface, IfaceFlag, IfaceState,
+    IfaceType, IpFamily, IpTunnelInfo, IpVlanFlag, IpVlanInfo, IpVlanMode,
+    IpoibInfo, IpoibMode, Ipv4AddrInfo, Ipv4Info, Ipv6AddrFlag, Ipv6AddrInfo,
+    Ipv6Info, MacSecCipherId, MacSecInfo, MacSecOffload, MacSecRxScInfo,
+    MacSecSaInfo, MacSecValidate, MacVlanInfo, MacVlanMode, MacVtapInfo,
+    MacVtapMode, Mptcp, MptcpAddress, MptcpAddressFlag, MultipathRoute,
+    MultipathRouteFlags, Route, RouteProtocol, RouteRule, RouteScope,
+    RouteType, RuleAction, SriovEswitchMode, SriovInfo, TunInfo, TunMode,
+    TunnelEncapType, VethInfo, VfInfo, VfLinkState, VfRepresentorInfo, VfState,
+    VlanInfo, VlanProtocol, VrfInfo, VrfSubordinateInfo, VxlanInfo,
+    WireguardInfo, WireguardPeerInfo, XfrmInfo,
 };
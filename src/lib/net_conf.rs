@@ -4,16 +4,25 @@ use serde::{Deserialize, Serialize};
 use tokio::runtime;
 
 use super::{
-    conf::{apply_routes_conf, change_ifaces, create_ifaces, delete_ifaces},
+    conf::{
+        apply_devlink_conf, apply_route_rules_conf, apply_routes_conf,
+        change_ifaces, create_ifaces, delete_ifaces,
+    },
     query::{get_iface_name2index, get_ifaces},
 };
-use crate::{IfaceConf, IfaceState, NisporError, RouteConf};
+use crate::{
+    DevlinkConf, IfaceConf, IfaceState, NisporError, RouteConf, RouteRuleConf,
+};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct NetConf {
     pub ifaces: Option<Vec<IfaceConf>>,
     pub routes: Option<Vec<RouteConf>>,
+    pub rules: Option<Vec<RouteRuleConf>>,
+    /// Applied before interfaces as changing e-switch mode or creating
+    /// subfunctions will create new interfaces.
+    pub devlink: Option<DevlinkConf>,
 }
 
 impl NetConf {
@@ -23,6 +32,10 @@ impl NetConf {
     }
 
     pub async fn apply_async(&self) -> Result<(), NisporError> {
+        if let Some(devlink) = self.devlink.as_ref() {
+            apply_devlink_conf(devlink)?;
+        }
+
         if let Some(ref ifaces) = &self.ifaces {
             let cur_iface_name_2_index = get_iface_name2index().await?;
             let mut new_ifaces = Vec::new();
@@ -59,6 +72,12 @@ impl NetConf {
                 apply_routes_conf(routes, &cur_iface_name_2_index).await?;
             }
         }
+
+        if let Some(rules) = self.rules.as_ref() {
+            if !rules.is_empty() {
+                apply_route_rules_conf(rules).await?;
+            }
+        }
         Ok(())
     }
 }
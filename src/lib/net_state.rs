@@ -6,11 +6,12 @@ use serde::{Deserialize, Serialize};
 use tokio::runtime;
 
 use super::query::{
-    get_ifaces, get_mptcp, get_route_rules, get_routes, merge_mptcp_info,
+    get_devlink, get_ifaces, get_mptcp, get_route_rules, get_routes,
+    merge_devlink_info, merge_mptcp_info,
 };
 use crate::{
-    Iface, Mptcp, NetStateFilter, NetStateIfaceFilter, NisporError, Route,
-    RouteRule,
+    Devlink, Iface, Mptcp, NetStateFilter, NetStateIfaceFilter, NisporError,
+    Route, RouteRule,
 };
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -21,6 +22,8 @@ pub struct NetState {
     pub rules: Vec<RouteRule>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mptcp: Option<Mptcp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devlink: Option<Devlink>,
 }
 
 impl NetState {
@@ -71,6 +74,22 @@ impl NetState {
             } else {
                 None
             };
+        let devlink =
+            if filter.iface.as_ref().map(|f| f.include_devlink) == Some(true) {
+                // Kernel might be compiled without devlink support
+                match get_devlink() {
+                    Ok(devlink) => {
+                        merge_devlink_info(&mut ifaces, &devlink);
+                        Some(devlink)
+                    }
+                    Err(e) => {
+                        log::info!("Failed to query devlink: {}", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
         if filter.iface.is_none() {
             ifaces = HashMap::new();
         }
@@ -79,6 +98,7 @@ impl NetState {
             routes,
             rules,
             mptcp,
+            devlink,
         })
     }
 }
@@ -0,0 +1,510 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The rtnetlink crate does not support RTM_GETVLAN and RTM_NEWVLAN yet.
+
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator};
+use netlink_packet_utils::Emitable;
+use netlink_sys::constants::NETLINK_ROUTE;
+
+use crate::{
+    netlink::{
+        gen_nested_nla, nl_request, parse_as_u16, parse_as_u32, parse_as_u64,
+        parse_as_u8, NLM_F_ACK, NLM_F_DUMP,
+    },
+    BridgeVlanGlobalOpts, BridgeVlanGlobalOptsConf, BridgeVlanOpts,
+    BridgeVlanOptsConf, NisporError,
+};
+
+const RTM_NEWVLAN: u16 = 112;
+const RTM_GETVLAN: u16 = 114;
+const RTM_DELLINK: u32 = 17;
+const RTM_SETLINK: u32 = 19;
+
+const AF_BRIDGE: u8 = libc::AF_BRIDGE as u8;
+
+const BRIDGE_VLANDB_DUMP_FLAGS: u16 = 1;
+const BRIDGE_VLANDB_DUMPF_GLOBAL: u32 = 1 << 1;
+
+const BRIDGE_VLANDB_ENTRY: u16 = 1;
+const BRIDGE_VLANDB_GLOBAL_OPTIONS: u16 = 2;
+
+const BRIDGE_VLANDB_ENTRY_INFO: u16 = 1;
+const BRIDGE_VLANDB_ENTRY_RANGE: u16 = 2;
+const BRIDGE_VLANDB_ENTRY_STATE: u16 = 3;
+const BRIDGE_VLANDB_ENTRY_TUNNEL_INFO: u16 = 4;
+const BRIDGE_VLANDB_ENTRY_STATS: u16 = 5;
+const BRIDGE_VLANDB_ENTRY_MCAST_ROUTER: u16 = 6;
+const BRIDGE_VLANDB_ENTRY_MCAST_N_GROUPS: u16 = 7;
+const BRIDGE_VLANDB_ENTRY_MCAST_MAX_GROUPS: u16 = 8;
+const BRIDGE_VLANDB_ENTRY_NEIGH_SUPPRESS: u16 = 9;
+
+const BRIDGE_VLANDB_TINFO_ID: u16 = 1;
+const BRIDGE_VLANDB_TINFO_CMD: u16 = 2;
+
+const BRIDGE_VLANDB_GOPTS_ID: u16 = 1;
+const BRIDGE_VLANDB_GOPTS_RANGE: u16 = 2;
+const BRIDGE_VLANDB_GOPTS_MCAST_SNOOPING: u16 = 3;
+const BRIDGE_VLANDB_GOPTS_MCAST_IGMP_VERSION: u16 = 4;
+const BRIDGE_VLANDB_GOPTS_MCAST_MLD_VERSION: u16 = 5;
+const BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_CNT: u16 = 6;
+const BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_CNT: u16 = 7;
+const BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_INTVL: u16 = 8;
+const BRIDGE_VLANDB_GOPTS_MCAST_MEMBERSHIP_INTVL: u16 = 10;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERIER_INTVL: u16 = 11;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERY_INTVL: u16 = 12;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERY_RESPONSE_INTVL: u16 = 13;
+const BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_INTVL: u16 = 14;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERIER: u16 = 15;
+const BRIDGE_VLANDB_GOPTS_MCAST_ROUTER_PORTS: u16 = 16;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERIER_STATE: u16 = 17;
+const BRIDGE_VLANDB_GOPTS_MSTI: u16 = 18;
+
+// Only change the VLAN options without touching VLAN membership
+const BRIDGE_VLAN_INFO_ONLY_OPTS: u16 = 1 << 6;
+
+// struct br_vlan_msg
+const BR_VLAN_MSG_LEN: usize = 8;
+
+pub(crate) fn get_bridge_vlan_opts(
+    iface_index: Option<u32>,
+) -> Result<Vec<(u32, Vec<BridgeVlanOpts>)>, NisporError> {
+    let mut ret: Vec<(u32, Vec<BridgeVlanOpts>)> = Vec::new();
+    for (index, data) in dump_vlan_db(iface_index, BRIDGE_VLANDB_ENTRY)? {
+        let opt = parse_vlan_db_entry(&data)?;
+        // The kernel might split VLANs of the same interface into multiple
+        // messages.
+        match ret.iter_mut().find(|(i, _)| *i == index) {
+            Some((_, opts)) => opts.push(opt),
+            None => ret.push((index, vec![opt])),
+        }
+    }
+    Ok(ret)
+}
+
+pub(crate) fn get_bridge_vlan_global_opts(
+    iface_index: Option<u32>,
+) -> Result<Vec<(u32, Vec<BridgeVlanGlobalOpts>)>, NisporError> {
+    let mut ret: Vec<(u32, Vec<BridgeVlanGlobalOpts>)> = Vec::new();
+    for (index, data) in
+        dump_vlan_db(iface_index, BRIDGE_VLANDB_GLOBAL_OPTIONS)?
+    {
+        let opt = parse_vlan_db_global_opts(&data)?;
+        match ret.iter_mut().find(|(i, _)| *i == index) {
+            Some((_, opts)) => opts.push(opt),
+            None => ret.push((index, vec![opt])),
+        }
+    }
+    Ok(ret)
+}
+
+// Return the interface index and payload of specified NLA kind
+fn dump_vlan_db(
+    iface_index: Option<u32>,
+    nla_kind: u16,
+) -> Result<Vec<(u32, Vec<u8>)>, NisporError> {
+    let mut nlas = Vec::new();
+    if nla_kind == BRIDGE_VLANDB_GLOBAL_OPTIONS {
+        nlas.push(DefaultNla::new(
+            BRIDGE_VLANDB_DUMP_FLAGS,
+            BRIDGE_VLANDB_DUMPF_GLOBAL.to_ne_bytes().to_vec(),
+        ));
+    }
+    let payload = gen_br_vlan_msg(iface_index.unwrap_or_default(), &nlas);
+
+    let mut ret = Vec::new();
+    for reply in nl_request(NETLINK_ROUTE, RTM_GETVLAN, NLM_F_DUMP, &payload)? {
+        if reply.len() < BR_VLAN_MSG_LEN {
+            continue;
+        }
+        let index = parse_as_u32(&reply[4..])?;
+        for nla in NlasIterator::new(&reply[BR_VLAN_MSG_LEN..]) {
+            let nla = nla?;
+            if nla.kind() == nla_kind {
+                ret.push((index, nla.value().to_vec()));
+            }
+        }
+    }
+    Ok(ret)
+}
+
+fn parse_vid_range(
+    vid: u16,
+    range_end: Option<u16>,
+) -> (Option<u16>, Option<(u16, u16)>) {
+    match range_end {
+        Some(end) if end != vid => (None, Some((vid, end))),
+        _ => (Some(vid), None),
+    }
+}
+
+fn parse_vlan_db_entry(data: &[u8]) -> Result<BridgeVlanOpts, NisporError> {
+    let mut ret = BridgeVlanOpts::default();
+    let mut vid = 0;
+    let mut range_end = None;
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        let payload = nla.value();
+        match nla.kind() {
+            BRIDGE_VLANDB_ENTRY_INFO => {
+                // struct bridge_vlan_info {
+                //      __u16 flags;
+                //      __u16 vid;
+                // }
+                vid = parse_as_u16(payload.get(2..).unwrap_or_default())?;
+            }
+            BRIDGE_VLANDB_ENTRY_RANGE => {
+                range_end = Some(parse_as_u16(payload)?);
+            }
+            BRIDGE_VLANDB_ENTRY_STATE => {
+                ret.stp_state = Some(parse_as_u8(payload)?.into());
+            }
+            BRIDGE_VLANDB_ENTRY_TUNNEL_INFO => {
+                for tun_nla in NlasIterator::new(payload) {
+                    let tun_nla = tun_nla?;
+                    if tun_nla.kind() == BRIDGE_VLANDB_TINFO_ID {
+                        ret.tunnel_id = Some(parse_as_u32(tun_nla.value())?);
+                    }
+                }
+            }
+            BRIDGE_VLANDB_ENTRY_MCAST_ROUTER => {
+                ret.multicast_router = Some(parse_as_u8(payload)?.into());
+            }
+            BRIDGE_VLANDB_ENTRY_MCAST_N_GROUPS => {
+                ret.multicast_n_groups = Some(parse_as_u32(payload)?);
+            }
+            BRIDGE_VLANDB_ENTRY_MCAST_MAX_GROUPS => {
+                ret.multicast_max_groups = Some(parse_as_u32(payload)?);
+            }
+            BRIDGE_VLANDB_ENTRY_NEIGH_SUPPRESS => {
+                ret.neigh_suppress = Some(parse_as_u8(payload)? > 0);
+            }
+            BRIDGE_VLANDB_ENTRY_STATS => (),
+            _ => {
+                log::debug!(
+                    "Unhandled bridge VLAN entry NLA {} {:?}",
+                    nla.kind(),
+                    payload
+                );
+            }
+        }
+    }
+    let (vid, vid_range) = parse_vid_range(vid, range_end);
+    ret.vid = vid;
+    ret.vid_range = vid_range;
+    Ok(ret)
+}
+
+fn parse_vlan_db_global_opts(
+    data: &[u8],
+) -> Result<BridgeVlanGlobalOpts, NisporError> {
+    let mut ret = BridgeVlanGlobalOpts::default();
+    let mut vid = 0;
+    let mut range_end = None;
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        let payload = nla.value();
+        match nla.kind() {
+            BRIDGE_VLANDB_GOPTS_ID => vid = parse_as_u16(payload)?,
+            BRIDGE_VLANDB_GOPTS_RANGE => {
+                range_end = Some(parse_as_u16(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_SNOOPING => {
+                ret.multicast_snooping = Some(parse_as_u8(payload)? > 0);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_QUERIER => {
+                ret.multicast_querier = Some(parse_as_u8(payload)? > 0);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_IGMP_VERSION => {
+                ret.multicast_igmp_version = Some(parse_as_u8(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_MLD_VERSION => {
+                ret.multicast_mld_version = Some(parse_as_u8(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_CNT => {
+                ret.multicast_last_member_count = Some(parse_as_u32(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_CNT => {
+                ret.multicast_startup_query_count =
+                    Some(parse_as_u32(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_INTVL => {
+                ret.multicast_last_member_interval =
+                    Some(parse_as_u64(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_MEMBERSHIP_INTVL => {
+                ret.multicast_membership_interval =
+                    Some(parse_as_u64(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_QUERIER_INTVL => {
+                ret.multicast_querier_interval = Some(parse_as_u64(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_QUERY_INTVL => {
+                ret.multicast_query_interval = Some(parse_as_u64(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_QUERY_RESPONSE_INTVL => {
+                ret.multicast_query_response_interval =
+                    Some(parse_as_u64(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_INTVL => {
+                ret.multicast_startup_query_interval =
+                    Some(parse_as_u64(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MSTI => {
+                ret.msti = Some(parse_as_u16(payload)?);
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_ROUTER_PORTS
+            | BRIDGE_VLANDB_GOPTS_MCAST_QUERIER_STATE => (),
+            _ => {
+                log::debug!(
+                    "Unhandled bridge VLAN global option NLA {} {:?}",
+                    nla.kind(),
+                    payload
+                );
+            }
+        }
+    }
+    let (vid, vid_range) = parse_vid_range(vid, range_end);
+    ret.vid = vid;
+    ret.vid_range = vid_range;
+    Ok(ret)
+}
+
+pub(crate) fn set_bridge_vlan_opts(
+    iface_index: u32,
+    opts: &[BridgeVlanOptsConf],
+    cur_opts: Option<&[BridgeVlanOpts]>,
+) -> Result<(), NisporError> {
+    let mut entries = Vec::new();
+    for opt in opts {
+        let (vid, range_end) = get_vid_and_range_end(opt.vid, opt.vid_range)?;
+        let mut vlan_info = BRIDGE_VLAN_INFO_ONLY_OPTS.to_ne_bytes().to_vec();
+        vlan_info.extend_from_slice(&vid.to_ne_bytes());
+        let mut nlas =
+            vec![DefaultNla::new(BRIDGE_VLANDB_ENTRY_INFO, vlan_info)];
+        if let Some(range_end) = range_end {
+            nlas.push(DefaultNla::new(
+                BRIDGE_VLANDB_ENTRY_RANGE,
+                range_end.to_ne_bytes().to_vec(),
+            ));
+        }
+
+        let tunnel_changed = if let Some(tunnel_id) = opt.tunnel_id {
+            !is_tunnel_id_matches(
+                cur_opts.unwrap_or_default(),
+                vid,
+                range_end.unwrap_or(vid),
+                tunnel_id,
+            )
+        } else {
+            false
+        };
+        // The kernel refuses to override existing VLAN tunnel mapping, hence
+        // remove it first.
+        if opt.remove_tunnel_id || tunnel_changed {
+            let mut del_nlas = nlas.clone();
+            del_nlas.push(gen_tunnel_info_nla(RTM_DELLINK, None));
+            entries.push(gen_nested_nla(BRIDGE_VLANDB_ENTRY, &del_nlas));
+        }
+        if let (Some(tunnel_id), true) = (opt.tunnel_id, tunnel_changed) {
+            nlas.push(gen_tunnel_info_nla(RTM_SETLINK, Some(tunnel_id)));
+        }
+        if let Some(stp_state) = opt.stp_state.as_ref() {
+            nlas.push(DefaultNla::new(
+                BRIDGE_VLANDB_ENTRY_STATE,
+                vec![u8::from(stp_state.clone())],
+            ));
+        }
+        if let Some(router) = opt.multicast_router.as_ref() {
+            nlas.push(DefaultNla::new(
+                BRIDGE_VLANDB_ENTRY_MCAST_ROUTER,
+                vec![u8::from(router.clone())],
+            ));
+        }
+        if let Some(max_groups) = opt.multicast_max_groups {
+            nlas.push(DefaultNla::new(
+                BRIDGE_VLANDB_ENTRY_MCAST_MAX_GROUPS,
+                max_groups.to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(neigh_suppress) = opt.neigh_suppress {
+            nlas.push(DefaultNla::new(
+                BRIDGE_VLANDB_ENTRY_NEIGH_SUPPRESS,
+                vec![neigh_suppress.into()],
+            ));
+        }
+        entries.push(gen_nested_nla(BRIDGE_VLANDB_ENTRY, &nlas));
+    }
+    if entries.is_empty() {
+        return Ok(());
+    }
+    nl_request(
+        NETLINK_ROUTE,
+        RTM_NEWVLAN,
+        NLM_F_ACK,
+        &gen_br_vlan_msg(iface_index, &entries),
+    )?;
+    Ok(())
+}
+
+pub(crate) fn set_bridge_vlan_global_opts(
+    iface_index: u32,
+    opts: &[BridgeVlanGlobalOptsConf],
+) -> Result<(), NisporError> {
+    let mut entries = Vec::new();
+    for opt in opts {
+        let (vid, range_end) = get_vid_and_range_end(opt.vid, opt.vid_range)?;
+        let mut nlas = vec![DefaultNla::new(
+            BRIDGE_VLANDB_GOPTS_ID,
+            vid.to_ne_bytes().to_vec(),
+        )];
+        if let Some(range_end) = range_end {
+            nlas.push(DefaultNla::new(
+                BRIDGE_VLANDB_GOPTS_RANGE,
+                range_end.to_ne_bytes().to_vec(),
+            ));
+        }
+        for (kind, value) in [
+            (BRIDGE_VLANDB_GOPTS_MCAST_SNOOPING, opt.multicast_snooping),
+            (BRIDGE_VLANDB_GOPTS_MCAST_QUERIER, opt.multicast_querier),
+        ] {
+            if let Some(v) = value {
+                nlas.push(DefaultNla::new(kind, vec![v.into()]));
+            }
+        }
+        for (kind, value) in [
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_IGMP_VERSION,
+                opt.multicast_igmp_version,
+            ),
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_MLD_VERSION,
+                opt.multicast_mld_version,
+            ),
+        ] {
+            if let Some(v) = value {
+                nlas.push(DefaultNla::new(kind, vec![v]));
+            }
+        }
+        for (kind, value) in [
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_CNT,
+                opt.multicast_last_member_count,
+            ),
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_CNT,
+                opt.multicast_startup_query_count,
+            ),
+        ] {
+            if let Some(v) = value {
+                nlas.push(DefaultNla::new(kind, v.to_ne_bytes().to_vec()));
+            }
+        }
+        for (kind, value) in [
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_INTVL,
+                opt.multicast_last_member_interval,
+            ),
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_MEMBERSHIP_INTVL,
+                opt.multicast_membership_interval,
+            ),
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_QUERIER_INTVL,
+                opt.multicast_querier_interval,
+            ),
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_QUERY_INTVL,
+                opt.multicast_query_interval,
+            ),
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_QUERY_RESPONSE_INTVL,
+                opt.multicast_query_response_interval,
+            ),
+            (
+                BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_INTVL,
+                opt.multicast_startup_query_interval,
+            ),
+        ] {
+            if let Some(v) = value {
+                nlas.push(DefaultNla::new(kind, v.to_ne_bytes().to_vec()));
+            }
+        }
+        if let Some(msti) = opt.msti {
+            nlas.push(DefaultNla::new(
+                BRIDGE_VLANDB_GOPTS_MSTI,
+                msti.to_ne_bytes().to_vec(),
+            ));
+        }
+        entries.push(gen_nested_nla(BRIDGE_VLANDB_GLOBAL_OPTIONS, &nlas));
+    }
+    if entries.is_empty() {
+        return Ok(());
+    }
+    nl_request(
+        NETLINK_ROUTE,
+        RTM_NEWVLAN,
+        NLM_F_ACK,
+        &gen_br_vlan_msg(iface_index, &entries),
+    )?;
+    Ok(())
+}
+
+fn get_vid_and_range_end(
+    vid: Option<u16>,
+    vid_range: Option<(u16, u16)>,
+) -> Result<(u16, Option<u16>), NisporError> {
+    match (vid, vid_range) {
+        (Some(vid), None) => Ok((vid, None)),
+        (None, Some((start, end))) if start < end => Ok((start, Some(end))),
+        _ => Err(NisporError::invalid_argument(format!(
+            "Bridge VLAN options should define either vid or valid \
+            vid_range, got vid {vid:?} vid_range {vid_range:?}"
+        ))),
+    }
+}
+
+// Whether all VLANs in specified range are mapped to desired tunnel IDs
+fn is_tunnel_id_matches(
+    cur_opts: &[BridgeVlanOpts],
+    start: u16,
+    end: u16,
+    tunnel_id: u32,
+) -> bool {
+    (start..=end).all(|vid| {
+        cur_opts.iter().any(|cur_opt| {
+            let (cur_start, cur_end) = match (cur_opt.vid, cur_opt.vid_range) {
+                (Some(v), _) => (v, v),
+                (None, Some(r)) => r,
+                _ => return false,
+            };
+            vid >= cur_start
+                && vid <= cur_end
+                && cur_opt
+                    .tunnel_id
+                    .map(|t| t.wrapping_add(u32::from(vid - cur_start)))
+                    == Some(tunnel_id.wrapping_add(u32::from(vid - start)))
+        })
+    })
+}
+
+fn gen_tunnel_info_nla(cmd: u32, tunnel_id: Option<u32>) -> DefaultNla {
+    let mut nlas = vec![DefaultNla::new(
+        BRIDGE_VLANDB_TINFO_CMD,
+        cmd.to_ne_bytes().to_vec(),
+    )];
+    if let Some(tunnel_id) = tunnel_id {
+        nlas.push(DefaultNla::new(
+            BRIDGE_VLANDB_TINFO_ID,
+            tunnel_id.to_ne_bytes().to_vec(),
+        ));
+    }
+    gen_nested_nla(BRIDGE_VLANDB_ENTRY_TUNNEL_INFO, &nlas)
+}
+
+fn gen_br_vlan_msg(iface_index: u32, nlas: &[DefaultNla]) -> Vec<u8> {
+    let mut ret = vec![0; BR_VLAN_MSG_LEN + nlas.buffer_len()];
+    ret[0] = AF_BRIDGE;
+    ret[4..BR_VLAN_MSG_LEN].copy_from_slice(&iface_index.to_ne_bytes());
+    nlas.emit(&mut ret[BR_VLAN_MSG_LEN..]);
+    ret
+}
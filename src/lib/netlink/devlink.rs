@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The devlink instances are managed by the `devlink` generic netlink family.
+
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator};
+
+use crate::{
+    netlink::{
+        genl_family_id, genl_request, parse_as_string, parse_as_u16, NLM_F_ACK,
+        NLM_F_DUMP,
+    },
+    NisporError,
+};
+
+const DEVLINK_GENL_NAME: &str = "devlink";
+const DEVLINK_GENL_VERSION: u8 = 1;
+
+pub(crate) const DEVLINK_CMD_GET: u8 = 1;
+pub(crate) const DEVLINK_CMD_PORT_GET: u8 = 5;
+pub(crate) const DEVLINK_CMD_PORT_SET: u8 = 6;
+pub(crate) const DEVLINK_CMD_PORT_NEW: u8 = 7;
+pub(crate) const DEVLINK_CMD_PORT_DEL: u8 = 8;
+pub(crate) const DEVLINK_CMD_ESWITCH_GET: u8 = 29;
+pub(crate) const DEVLINK_CMD_ESWITCH_SET: u8 = 30;
+pub(crate) const DEVLINK_CMD_PARAM_GET: u8 = 38;
+pub(crate) const DEVLINK_CMD_PARAM_SET: u8 = 39;
+pub(crate) const DEVLINK_CMD_INFO_GET: u8 = 51;
+
+pub(crate) const DEVLINK_ATTR_BUS_NAME: u16 = 1;
+pub(crate) const DEVLINK_ATTR_DEV_NAME: u16 = 2;
+pub(crate) const DEVLINK_ATTR_PORT_INDEX: u16 = 3;
+pub(crate) const DEVLINK_ATTR_PORT_TYPE: u16 = 4;
+pub(crate) const DEVLINK_ATTR_PORT_NETDEV_NAME: u16 = 7;
+pub(crate) const DEVLINK_ATTR_PORT_IBDEV_NAME: u16 = 8;
+pub(crate) const DEVLINK_ATTR_PORT_SPLIT_GROUP: u16 = 10;
+pub(crate) const DEVLINK_ATTR_ESWITCH_MODE: u16 = 25;
+pub(crate) const DEVLINK_ATTR_ESWITCH_INLINE_MODE: u16 = 26;
+pub(crate) const DEVLINK_ATTR_ESWITCH_ENCAP_MODE: u16 = 62;
+pub(crate) const DEVLINK_ATTR_PORT_FLAVOUR: u16 = 77;
+pub(crate) const DEVLINK_ATTR_PORT_NUMBER: u16 = 78;
+pub(crate) const DEVLINK_ATTR_PORT_SPLIT_SUBPORT_NUMBER: u16 = 79;
+pub(crate) const DEVLINK_ATTR_PARAM: u16 = 80;
+pub(crate) const DEVLINK_ATTR_PARAM_NAME: u16 = 81;
+pub(crate) const DEVLINK_ATTR_PARAM_GENERIC: u16 = 82;
+pub(crate) const DEVLINK_ATTR_PARAM_TYPE: u16 = 83;
+pub(crate) const DEVLINK_ATTR_PARAM_VALUES_LIST: u16 = 84;
+pub(crate) const DEVLINK_ATTR_PARAM_VALUE: u16 = 85;
+pub(crate) const DEVLINK_ATTR_PARAM_VALUE_DATA: u16 = 86;
+pub(crate) const DEVLINK_ATTR_PARAM_VALUE_CMODE: u16 = 87;
+pub(crate) const DEVLINK_ATTR_INFO_DRIVER_NAME: u16 = 98;
+pub(crate) const DEVLINK_ATTR_INFO_SERIAL_NUMBER: u16 = 99;
+pub(crate) const DEVLINK_ATTR_INFO_VERSION_FIXED: u16 = 100;
+pub(crate) const DEVLINK_ATTR_INFO_VERSION_RUNNING: u16 = 101;
+pub(crate) const DEVLINK_ATTR_INFO_VERSION_STORED: u16 = 102;
+pub(crate) const DEVLINK_ATTR_INFO_VERSION_NAME: u16 = 103;
+pub(crate) const DEVLINK_ATTR_INFO_VERSION_VALUE: u16 = 104;
+pub(crate) const DEVLINK_ATTR_PORT_PCI_PF_NUMBER: u16 = 127;
+pub(crate) const DEVLINK_ATTR_PORT_PCI_VF_NUMBER: u16 = 128;
+pub(crate) const DEVLINK_ATTR_PORT_FUNCTION: u16 = 145;
+pub(crate) const DEVLINK_ATTR_INFO_BOARD_SERIAL_NUMBER: u16 = 146;
+pub(crate) const DEVLINK_ATTR_PORT_SPLITTABLE: u16 = 148;
+pub(crate) const DEVLINK_ATTR_PORT_EXTERNAL: u16 = 149;
+pub(crate) const DEVLINK_ATTR_PORT_CONTROLLER_NUMBER: u16 = 150;
+pub(crate) const DEVLINK_ATTR_PORT_PCI_SF_NUMBER: u16 = 164;
+
+pub(crate) const DEVLINK_PORT_FUNCTION_ATTR_HW_ADDR: u16 = 1;
+pub(crate) const DEVLINK_PORT_FN_ATTR_STATE: u16 = 2;
+pub(crate) const DEVLINK_PORT_FN_ATTR_OPSTATE: u16 = 3;
+
+pub(crate) fn gen_devlink_handle_nlas(
+    bus_name: &str,
+    dev_name: &str,
+) -> Vec<DefaultNla> {
+    let mut bus = bus_name.as_bytes().to_vec();
+    bus.push(0);
+    let mut dev = dev_name.as_bytes().to_vec();
+    dev.push(0);
+    vec![
+        DefaultNla::new(DEVLINK_ATTR_BUS_NAME, bus),
+        DefaultNla::new(DEVLINK_ATTR_DEV_NAME, dev),
+    ]
+}
+
+// Return the devlink handle bus name and device name of each reply along with
+// the reply attributes.
+pub(crate) fn devlink_dump(
+    cmd: u8,
+) -> Result<Vec<(String, String, Vec<u8>)>, NisporError> {
+    let family_id = genl_family_id(DEVLINK_GENL_NAME)?;
+    let mut ret = Vec::new();
+    for reply in
+        genl_request(family_id, cmd, DEVLINK_GENL_VERSION, NLM_F_DUMP, &[])?
+    {
+        if let Some((bus_name, dev_name)) = parse_devlink_handle(&reply)? {
+            ret.push((bus_name, dev_name, reply));
+        }
+    }
+    Ok(ret)
+}
+
+pub(crate) fn devlink_request(
+    cmd: u8,
+    flags: u16,
+    nlas: &[DefaultNla],
+) -> Result<Vec<Vec<u8>>, NisporError> {
+    let family_id = genl_family_id(DEVLINK_GENL_NAME)?;
+    genl_request(family_id, cmd, DEVLINK_GENL_VERSION, flags, nlas)
+}
+
+fn parse_devlink_handle(
+    reply: &[u8],
+) -> Result<Option<(String, String)>, NisporError> {
+    let mut bus_name = None;
+    let mut dev_name = None;
+    for nla in NlasIterator::new(reply).flatten() {
+        match nla.kind() {
+            DEVLINK_ATTR_BUS_NAME => {
+                bus_name = Some(parse_as_string(nla.value())?)
+            }
+            DEVLINK_ATTR_DEV_NAME => {
+                dev_name = Some(parse_as_string(nla.value())?)
+            }
+            _ => (),
+        }
+    }
+    Ok(bus_name.zip(dev_name))
+}
+
+// Send a request to the devlink device, return the reply attributes.
+pub(crate) fn devlink_set(
+    bus_name: &str,
+    dev_name: &str,
+    cmd: u8,
+    nlas: &[DefaultNla],
+) -> Result<Vec<Vec<u8>>, NisporError> {
+    let mut all_nlas = gen_devlink_handle_nlas(bus_name, dev_name);
+    all_nlas.extend_from_slice(nlas);
+    devlink_request(cmd, NLM_F_ACK, &all_nlas)
+}
+
+// Return None if e-switch mode is not reported by kernel.
+pub(crate) fn devlink_get_eswitch_mode(
+    bus_name: &str,
+    dev_name: &str,
+) -> Result<Option<u16>, NisporError> {
+    for reply in devlink_request(
+        DEVLINK_CMD_ESWITCH_GET,
+        NLM_F_ACK,
+        &gen_devlink_handle_nlas(bus_name, dev_name),
+    )? {
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            if nla.kind() == DEVLINK_ATTR_ESWITCH_MODE {
+                return Ok(Some(parse_as_u16(nla.value())?));
+            }
+        }
+    }
+    Ok(None)
+}
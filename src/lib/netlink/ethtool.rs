@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The ethtool crate only provides SET support for channels and does not
+// support every GET message, the missing ones are generated here.
+
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator};
+
+use crate::{
+    netlink::{
+        gen_nested_nla, genl_family_id, genl_request, parse_as_string,
+        NLM_F_ACK, NLM_F_DUMP,
+    },
+    NisporError,
+};
+
+const ETHTOOL_GENL_NAME: &str = "ethtool";
+const ETHTOOL_GENL_VERSION: u8 = 1;
+
+pub(crate) const ETHTOOL_MSG_LINKMODES_SET: u8 = 5;
+pub(crate) const ETHTOOL_MSG_LINKSTATE_GET: u8 = 6;
+pub(crate) const ETHTOOL_MSG_WOL_GET: u8 = 9;
+pub(crate) const ETHTOOL_MSG_FEATURES_SET: u8 = 12;
+pub(crate) const ETHTOOL_MSG_PRIVFLAGS_GET: u8 = 13;
+pub(crate) const ETHTOOL_MSG_PRIVFLAGS_SET: u8 = 14;
+pub(crate) const ETHTOOL_MSG_RINGS_SET: u8 = 16;
+pub(crate) const ETHTOOL_MSG_COALESCE_SET: u8 = 20;
+pub(crate) const ETHTOOL_MSG_PAUSE_SET: u8 = 22;
+pub(crate) const ETHTOOL_MSG_EEE_GET: u8 = 23;
+pub(crate) const ETHTOOL_MSG_TSINFO_GET: u8 = 25;
+pub(crate) const ETHTOOL_MSG_PAUSE_GET: u8 = 21;
+pub(crate) const ETHTOOL_MSG_STATS_GET: u8 = 32;
+pub(crate) const ETHTOOL_MSG_RSS_GET: u8 = 38;
+
+// All ETHTOOL_A_XXX_HEADER are 1 except ETHTOOL_A_STATS_HEADER
+pub(crate) const ETHTOOL_A_HEADER: u16 = 1;
+const ETHTOOL_A_HEADER_DEV_NAME: u16 = 2;
+const ETHTOOL_A_HEADER_FLAGS: u16 = 3;
+const ETHTOOL_FLAG_OMIT_REPLY: u32 = 1 << 1;
+pub(crate) const ETHTOOL_FLAG_STATS: u32 = 1 << 2;
+
+const ETHTOOL_A_BITSET_NOMASK: u16 = 1;
+const ETHTOOL_A_BITSET_BITS: u16 = 3;
+const ETHTOOL_A_BITSET_BITS_BIT: u16 = 1;
+const ETHTOOL_A_BITSET_BIT_NAME: u16 = 2;
+const ETHTOOL_A_BITSET_BIT_VALUE: u16 = 3;
+
+pub(crate) const ETHTOOL_A_LINKMODES_AUTONEG: u16 = 2;
+pub(crate) const ETHTOOL_A_LINKMODES_OURS: u16 = 3;
+pub(crate) const ETHTOOL_A_LINKMODES_SPEED: u16 = 5;
+pub(crate) const ETHTOOL_A_LINKMODES_DUPLEX: u16 = 6;
+
+pub(crate) const ETHTOOL_A_FEATURES_WANTED: u16 = 3;
+
+pub(crate) const ETHTOOL_A_PRIVFLAGS_FLAGS: u16 = 2;
+
+pub(crate) const ETHTOOL_A_RINGS_RX: u16 = 6;
+pub(crate) const ETHTOOL_A_RINGS_RX_MINI: u16 = 7;
+pub(crate) const ETHTOOL_A_RINGS_RX_JUMBO: u16 = 8;
+pub(crate) const ETHTOOL_A_RINGS_TX: u16 = 9;
+
+pub(crate) const ETHTOOL_A_COALESCE_RX_USECS: u16 = 2;
+pub(crate) const ETHTOOL_A_COALESCE_RX_MAX_FRAMES: u16 = 3;
+pub(crate) const ETHTOOL_A_COALESCE_RX_USECS_IRQ: u16 = 4;
+pub(crate) const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_IRQ: u16 = 5;
+pub(crate) const ETHTOOL_A_COALESCE_TX_USECS: u16 = 6;
+pub(crate) const ETHTOOL_A_COALESCE_TX_MAX_FRAMES: u16 = 7;
+pub(crate) const ETHTOOL_A_COALESCE_TX_USECS_IRQ: u16 = 8;
+pub(crate) const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_IRQ: u16 = 9;
+pub(crate) const ETHTOOL_A_COALESCE_STATS_BLOCK_USECS: u16 = 10;
+pub(crate) const ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX: u16 = 11;
+pub(crate) const ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX: u16 = 12;
+pub(crate) const ETHTOOL_A_COALESCE_PKT_RATE_LOW: u16 = 13;
+pub(crate) const ETHTOOL_A_COALESCE_RX_USECS_LOW: u16 = 14;
+pub(crate) const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_LOW: u16 = 15;
+pub(crate) const ETHTOOL_A_COALESCE_TX_USECS_LOW: u16 = 16;
+pub(crate) const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_LOW: u16 = 17;
+pub(crate) const ETHTOOL_A_COALESCE_PKT_RATE_HIGH: u16 = 18;
+pub(crate) const ETHTOOL_A_COALESCE_RX_USECS_HIGH: u16 = 19;
+pub(crate) const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_HIGH: u16 = 20;
+pub(crate) const ETHTOOL_A_COALESCE_TX_USECS_HIGH: u16 = 21;
+pub(crate) const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_HIGH: u16 = 22;
+pub(crate) const ETHTOOL_A_COALESCE_RATE_SAMPLE_INTERVAL: u16 = 23;
+
+pub(crate) const ETHTOOL_A_PAUSE_AUTONEG: u16 = 2;
+pub(crate) const ETHTOOL_A_PAUSE_RX: u16 = 3;
+pub(crate) const ETHTOOL_A_PAUSE_TX: u16 = 4;
+pub(crate) const ETHTOOL_A_PAUSE_STATS: u16 = 5;
+
+pub(crate) const ETHTOOL_A_PAUSE_STAT_TX_FRAMES: u16 = 2;
+pub(crate) const ETHTOOL_A_PAUSE_STAT_RX_FRAMES: u16 = 3;
+
+pub(crate) const ETHTOOL_A_STATS_HEADER: u16 = 2;
+pub(crate) const ETHTOOL_A_STATS_GROUPS: u16 = 3;
+pub(crate) const ETHTOOL_A_STATS_GRP: u16 = 4;
+
+pub(crate) const ETHTOOL_A_STATS_GRP_ID: u16 = 2;
+pub(crate) const ETHTOOL_A_STATS_GRP_STAT: u16 = 4;
+pub(crate) const ETHTOOL_A_STATS_GRP_HIST_RX: u16 = 5;
+pub(crate) const ETHTOOL_A_STATS_GRP_HIST_TX: u16 = 6;
+pub(crate) const ETHTOOL_A_STATS_GRP_HIST_BKT_LOW: u16 = 7;
+pub(crate) const ETHTOOL_A_STATS_GRP_HIST_BKT_HI: u16 = 8;
+pub(crate) const ETHTOOL_A_STATS_GRP_HIST_VAL: u16 = 9;
+
+pub(crate) const ETHTOOL_A_LINKSTATE_LINK: u16 = 2;
+pub(crate) const ETHTOOL_A_LINKSTATE_SQI: u16 = 3;
+pub(crate) const ETHTOOL_A_LINKSTATE_SQI_MAX: u16 = 4;
+pub(crate) const ETHTOOL_A_LINKSTATE_EXT_STATE: u16 = 5;
+pub(crate) const ETHTOOL_A_LINKSTATE_EXT_SUBSTATE: u16 = 6;
+pub(crate) const ETHTOOL_A_LINKSTATE_EXT_DOWN_CNT: u16 = 7;
+
+pub(crate) const ETHTOOL_A_WOL_MODES: u16 = 2;
+
+pub(crate) const ETHTOOL_A_TSINFO_TIMESTAMPING: u16 = 2;
+pub(crate) const ETHTOOL_A_TSINFO_TX_TYPES: u16 = 3;
+pub(crate) const ETHTOOL_A_TSINFO_RX_FILTERS: u16 = 4;
+pub(crate) const ETHTOOL_A_TSINFO_PHC_INDEX: u16 = 5;
+
+pub(crate) const ETHTOOL_A_RSS_CONTEXT: u16 = 2;
+pub(crate) const ETHTOOL_A_RSS_HFUNC: u16 = 3;
+pub(crate) const ETHTOOL_A_RSS_INDIR: u16 = 4;
+pub(crate) const ETHTOOL_A_RSS_HKEY: u16 = 5;
+
+pub(crate) const ETHTOOL_A_EEE_MODES_OURS: u16 = 2;
+pub(crate) const ETHTOOL_A_EEE_MODES_PEER: u16 = 3;
+pub(crate) const ETHTOOL_A_EEE_ACTIVE: u16 = 4;
+pub(crate) const ETHTOOL_A_EEE_ENABLED: u16 = 5;
+pub(crate) const ETHTOOL_A_EEE_TX_LPI_ENABLED: u16 = 6;
+pub(crate) const ETHTOOL_A_EEE_TX_LPI_TIMER: u16 = 7;
+
+// Return interface name and attributes of each reply.
+pub(crate) fn ethtool_dump(
+    cmd: u8,
+) -> Result<Vec<(String, Vec<u8>)>, NisporError> {
+    ethtool_dump_full(cmd, ETHTOOL_A_HEADER, 0, &[])
+}
+
+pub(crate) fn ethtool_dump_full(
+    cmd: u8,
+    header_kind: u16,
+    header_flags: u32,
+    nlas: &[DefaultNla],
+) -> Result<Vec<(String, Vec<u8>)>, NisporError> {
+    let family_id = genl_family_id(ETHTOOL_GENL_NAME)?;
+    let mut all_nlas = Vec::new();
+    if header_flags != 0 {
+        all_nlas.push(gen_nested_nla(
+            header_kind,
+            &[DefaultNla::new(
+                ETHTOOL_A_HEADER_FLAGS,
+                header_flags.to_ne_bytes().to_vec(),
+            )],
+        ));
+    }
+    all_nlas.extend_from_slice(nlas);
+    let mut ret = Vec::new();
+    for reply in genl_request(
+        family_id,
+        cmd,
+        ETHTOOL_GENL_VERSION,
+        NLM_F_DUMP,
+        &all_nlas,
+    )? {
+        let mut iface_name = None;
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            if nla.kind() == header_kind {
+                for hdr_nla in NlasIterator::new(nla.value()).flatten() {
+                    if hdr_nla.kind() == ETHTOOL_A_HEADER_DEV_NAME {
+                        iface_name = Some(parse_as_string(hdr_nla.value())?);
+                    }
+                }
+            }
+        }
+        if let Some(iface_name) = iface_name {
+            ret.push((iface_name, reply));
+        }
+    }
+    Ok(ret)
+}
+
+// Parse bitset in verbose(non-compact) form, return the name and value of
+// each bit. When bitset has no mask, all listed bits are set.
+pub(crate) fn parse_ethtool_bitset(data: &[u8]) -> Vec<(String, bool)> {
+    let mut ret = Vec::new();
+    let mut no_mask = false;
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            ETHTOOL_A_BITSET_NOMASK => no_mask = true,
+            ETHTOOL_A_BITSET_BITS => {
+                for bit_nla in NlasIterator::new(nla.value()).flatten() {
+                    if bit_nla.kind() != ETHTOOL_A_BITSET_BITS_BIT {
+                        continue;
+                    }
+                    let mut name = None;
+                    let mut value = false;
+                    for attr in NlasIterator::new(bit_nla.value()).flatten() {
+                        match attr.kind() {
+                            ETHTOOL_A_BITSET_BIT_NAME => {
+                                name = parse_as_string(attr.value()).ok()
+                            }
+                            ETHTOOL_A_BITSET_BIT_VALUE => value = true,
+                            _ => (),
+                        }
+                    }
+                    if let Some(name) = name {
+                        ret.push((name, value));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    if no_mask {
+        for (_, value) in ret.iter_mut() {
+            *value = true;
+        }
+    }
+    ret
+}
+
+pub(crate) fn ethtool_set(
+    iface_name: &str,
+    cmd: u8,
+    nlas: &[DefaultNla],
+) -> Result<(), NisporError> {
+    let family_id = genl_family_id(ETHTOOL_GENL_NAME)?;
+    let mut dev_name = iface_name.as_bytes().to_vec();
+    dev_name.push(0);
+    let mut all_nlas = vec![gen_nested_nla(
+        ETHTOOL_A_HEADER,
+        &[
+            DefaultNla::new(ETHTOOL_A_HEADER_DEV_NAME, dev_name),
+            DefaultNla::new(
+                ETHTOOL_A_HEADER_FLAGS,
+                ETHTOOL_FLAG_OMIT_REPLY.to_ne_bytes().to_vec(),
+            ),
+        ],
+    )];
+    all_nlas.extend_from_slice(nlas);
+    genl_request(family_id, cmd, ETHTOOL_GENL_VERSION, NLM_F_ACK, &all_nlas)?;
+    Ok(())
+}
+
+// Generate bitset in verbose(non-compact) form using bit names.
+// When `no_mask` is true, the listed bits are set and all others are
+// cleared, otherwise only listed bits are changed to specified value.
+pub(crate) fn gen_ethtool_bitset_nla(
+    kind: u16,
+    bits: &[(&str, bool)],
+    no_mask: bool,
+) -> DefaultNla {
+    let bit_nlas: Vec<DefaultNla> = bits
+        .iter()
+        .map(|(name, value)| {
+            let mut name = name.as_bytes().to_vec();
+            name.push(0);
+            let mut nlas =
+                vec![DefaultNla::new(ETHTOOL_A_BITSET_BIT_NAME, name)];
+            if *value {
+                nlas.push(DefaultNla::new(
+                    ETHTOOL_A_BITSET_BIT_VALUE,
+                    Vec::new(),
+                ));
+            }
+            gen_nested_nla(ETHTOOL_A_BITSET_BITS_BIT, &nlas)
+        })
+        .collect();
+    let mut nlas = Vec::new();
+    if no_mask {
+        nlas.push(DefaultNla::new(ETHTOOL_A_BITSET_NOMASK, Vec::new()));
+    }
+    nlas.push(gen_nested_nla(ETHTOOL_A_BITSET_BITS, &bit_nlas));
+    gen_nested_nla(kind, &nlas)
+}
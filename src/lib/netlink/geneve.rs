@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The IFLA_INFO_DATA attributes of GENEVE which are not supported by
+// netlink-packet-route yet.
+
+pub(crate) const IFLA_GENEVE_ID: u16 = 1;
+pub(crate) const IFLA_GENEVE_REMOTE: u16 = 2;
+pub(crate) const IFLA_GENEVE_TTL: u16 = 3;
+pub(crate) const IFLA_GENEVE_TOS: u16 = 4;
+pub(crate) const IFLA_GENEVE_PORT: u16 = 5;
+pub(crate) const IFLA_GENEVE_COLLECT_METADATA: u16 = 6;
+pub(crate) const IFLA_GENEVE_REMOTE6: u16 = 7;
+pub(crate) const IFLA_GENEVE_UDP_CSUM: u16 = 8;
+pub(crate) const IFLA_GENEVE_UDP_ZERO_CSUM6_TX: u16 = 9;
+pub(crate) const IFLA_GENEVE_UDP_ZERO_CSUM6_RX: u16 = 10;
+pub(crate) const IFLA_GENEVE_LABEL: u16 = 11;
+pub(crate) const IFLA_GENEVE_TTL_INHERIT: u16 = 12;
+pub(crate) const IFLA_GENEVE_DF: u16 = 13;
+pub(crate) const IFLA_GENEVE_INNER_PROTO_INHERIT: u16 = 14;
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Minimum generic netlink support for families not covered by rust-netlink
+// crates.
+
+use netlink_packet_utils::{
+    nla::{DefaultNla, NlasIterator},
+    Emitable,
+};
+use netlink_sys::constants::NETLINK_GENERIC;
+
+use crate::{
+    netlink::{nl_request, parse_as_u16, NLM_F_ACK},
+    NisporError,
+};
+
+const GENL_HDR_LEN: usize = 4;
+const GENL_ID_CTRL: u16 = 0x10;
+const CTRL_CMD_GETFAMILY: u8 = 3;
+const CTRL_ATTR_FAMILY_ID: u16 = 1;
+const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+const CTRL_VERSION: u8 = 1;
+
+pub(crate) fn genl_family_id(name: &str) -> Result<u16, NisporError> {
+    let mut family_name = name.as_bytes().to_vec();
+    family_name.push(0);
+    let replies = genl_request(
+        GENL_ID_CTRL,
+        CTRL_CMD_GETFAMILY,
+        CTRL_VERSION,
+        NLM_F_ACK,
+        &[DefaultNla::new(CTRL_ATTR_FAMILY_NAME, family_name)],
+    )?;
+    for reply in replies {
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            if nla.kind() == CTRL_ATTR_FAMILY_ID {
+                return parse_as_u16(nla.value());
+            }
+        }
+    }
+    Err(NisporError::bug(format!(
+        "Generic netlink family {name} not found"
+    )))
+}
+
+// Return the attributes(without generic netlink header) of each reply.
+pub(crate) fn genl_request(
+    family_id: u16,
+    cmd: u8,
+    version: u8,
+    flags: u16,
+    nlas: &[DefaultNla],
+) -> Result<Vec<Vec<u8>>, NisporError> {
+    let mut payload = vec![0; GENL_HDR_LEN + nlas.buffer_len()];
+    payload[0] = cmd;
+    payload[1] = version;
+    nlas.emit(&mut payload[GENL_HDR_LEN..]);
+    let mut ret = Vec::new();
+    for reply in nl_request(NETLINK_GENERIC, family_id, flags, &payload)? {
+        if reply.len() >= GENL_HDR_LEN {
+            ret.push(reply[GENL_HDR_LEN..].to_vec());
+        }
+    }
+    Ok(ret)
+}
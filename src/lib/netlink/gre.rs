@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The IFLA_INFO_DATA attributes of GRE tunnels which are not supported by
+// netlink-packet-route yet.
+
+pub(crate) const IFLA_GRE_LINK: u16 = 1;
+pub(crate) const IFLA_GRE_IFLAGS: u16 = 2;
+pub(crate) const IFLA_GRE_OFLAGS: u16 = 3;
+pub(crate) const IFLA_GRE_IKEY: u16 = 4;
+pub(crate) const IFLA_GRE_OKEY: u16 = 5;
+pub(crate) const IFLA_GRE_LOCAL: u16 = 6;
+pub(crate) const IFLA_GRE_REMOTE: u16 = 7;
+pub(crate) const IFLA_GRE_TTL: u16 = 8;
+pub(crate) const IFLA_GRE_TOS: u16 = 9;
+pub(crate) const IFLA_GRE_PMTUDISC: u16 = 10;
+pub(crate) const IFLA_GRE_ENCAP_LIMIT: u16 = 11;
+pub(crate) const IFLA_GRE_FLOWINFO: u16 = 12;
+pub(crate) const IFLA_GRE_ENCAP_TYPE: u16 = 14;
+pub(crate) const IFLA_GRE_ENCAP_FLAGS: u16 = 15;
+pub(crate) const IFLA_GRE_ENCAP_SPORT: u16 = 16;
+pub(crate) const IFLA_GRE_ENCAP_DPORT: u16 = 17;
+pub(crate) const IFLA_GRE_COLLECT_METADATA: u16 = 18;
+pub(crate) const IFLA_GRE_IGNORE_DF: u16 = 19;
+pub(crate) const IFLA_GRE_FWMARK: u16 = 20;
+pub(crate) const IFLA_GRE_ERSPAN_INDEX: u16 = 21;
+pub(crate) const IFLA_GRE_ERSPAN_VER: u16 = 22;
+pub(crate) const IFLA_GRE_ERSPAN_DIR: u16 = 23;
+pub(crate) const IFLA_GRE_ERSPAN_HWID: u16 = 24;
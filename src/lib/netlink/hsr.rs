@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The IFLA_INFO_DATA attributes of HSR which are not supported by
+// netlink-packet-route yet.
+
+// Linux kernel 6.11+
+pub(crate) const IFLA_HSR_INTERLINK: u16 = 8;
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The IFLA_INFO_DATA attributes of ipip, sit and ip6tnl tunnels which are not
+// supported by netlink-packet-route yet.
+
+pub(crate) const IFLA_IPTUN_LINK: u16 = 1;
+pub(crate) const IFLA_IPTUN_LOCAL: u16 = 2;
+pub(crate) const IFLA_IPTUN_REMOTE: u16 = 3;
+pub(crate) const IFLA_IPTUN_TTL: u16 = 4;
+pub(crate) const IFLA_IPTUN_TOS: u16 = 5;
+pub(crate) const IFLA_IPTUN_ENCAP_LIMIT: u16 = 6;
+pub(crate) const IFLA_IPTUN_FLOWINFO: u16 = 7;
+pub(crate) const IFLA_IPTUN_FLAGS: u16 = 8;
+pub(crate) const IFLA_IPTUN_PROTO: u16 = 9;
+pub(crate) const IFLA_IPTUN_PMTUDISC: u16 = 10;
+pub(crate) const IFLA_IPTUN_6RD_PREFIX: u16 = 11;
+pub(crate) const IFLA_IPTUN_6RD_RELAY_PREFIX: u16 = 12;
+pub(crate) const IFLA_IPTUN_6RD_PREFIXLEN: u16 = 13;
+pub(crate) const IFLA_IPTUN_6RD_RELAY_PREFIXLEN: u16 = 14;
+pub(crate) const IFLA_IPTUN_ENCAP_TYPE: u16 = 15;
+pub(crate) const IFLA_IPTUN_ENCAP_FLAGS: u16 = 16;
+pub(crate) const IFLA_IPTUN_ENCAP_SPORT: u16 = 17;
+pub(crate) const IFLA_IPTUN_ENCAP_DPORT: u16 = 18;
+pub(crate) const IFLA_IPTUN_COLLECT_METADATA: u16 = 19;
+pub(crate) const IFLA_IPTUN_FWMARK: u16 = 20;
+
+pub(crate) const IP6_FLOWINFO_TCLASS_SHIFT: u32 = 20;
+pub(crate) const IP6_FLOWINFO_TCLASS_MASK: u32 = 0x0ff0_0000;
+pub(crate) const IP6_FLOWINFO_FLOWLABEL_MASK: u32 = 0x000f_ffff;
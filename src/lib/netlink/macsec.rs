@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The MACsec SC and SA are managed by the `macsec` generic netlink family.
+
+use std::collections::HashMap;
+
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator};
+
+use crate::{
+    netlink::{
+        gen_nested_nla, genl_family_id, genl_request, parse_as_u32,
+        parse_as_u64, parse_as_u8, NLM_F_ACK, NLM_F_DUMP,
+    },
+    MacSecRxScInfo, MacSecSaInfo, NisporError,
+};
+
+const MACSEC_GENL_NAME: &str = "macsec";
+const MACSEC_GENL_VERSION: u8 = 1;
+
+const MACSEC_CMD_GET_TXSC: u8 = 0;
+pub(crate) const MACSEC_CMD_ADD_RXSC: u8 = 1;
+pub(crate) const MACSEC_CMD_DEL_RXSC: u8 = 2;
+pub(crate) const MACSEC_CMD_UPD_RXSC: u8 = 3;
+pub(crate) const MACSEC_CMD_ADD_TXSA: u8 = 4;
+pub(crate) const MACSEC_CMD_DEL_TXSA: u8 = 5;
+pub(crate) const MACSEC_CMD_UPD_TXSA: u8 = 6;
+pub(crate) const MACSEC_CMD_ADD_RXSA: u8 = 7;
+pub(crate) const MACSEC_CMD_DEL_RXSA: u8 = 8;
+pub(crate) const MACSEC_CMD_UPD_RXSA: u8 = 9;
+
+const MACSEC_ATTR_IFINDEX: u16 = 1;
+const MACSEC_ATTR_RXSC_CONFIG: u16 = 2;
+const MACSEC_ATTR_SA_CONFIG: u16 = 3;
+const MACSEC_ATTR_TXSA_LIST: u16 = 5;
+const MACSEC_ATTR_RXSC_LIST: u16 = 6;
+
+const MACSEC_RXSC_ATTR_SCI: u16 = 1;
+const MACSEC_RXSC_ATTR_ACTIVE: u16 = 2;
+const MACSEC_RXSC_ATTR_SA_LIST: u16 = 3;
+
+pub(crate) const MACSEC_SA_ATTR_AN: u16 = 1;
+pub(crate) const MACSEC_SA_ATTR_ACTIVE: u16 = 2;
+pub(crate) const MACSEC_SA_ATTR_PN: u16 = 3;
+pub(crate) const MACSEC_SA_ATTR_KEY: u16 = 4;
+pub(crate) const MACSEC_SA_ATTR_KEYID: u16 = 5;
+pub(crate) const MACSEC_SA_ATTR_SSCI: u16 = 8;
+pub(crate) const MACSEC_SA_ATTR_SALT: u16 = 9;
+
+pub(crate) const MACSEC_KEYID_LEN: usize = 16;
+
+#[derive(Debug, Default)]
+pub(crate) struct MacSecSecy {
+    pub(crate) tx_sa: Vec<MacSecSaInfo>,
+    pub(crate) rx_sc: Vec<MacSecRxScInfo>,
+}
+
+// Return SC and SA information indexed by interface index
+pub(crate) fn get_macsec_secys() -> Result<HashMap<u32, MacSecSecy>, NisporError>
+{
+    let family_id = genl_family_id(MACSEC_GENL_NAME)?;
+    let mut ret = HashMap::new();
+    for reply in genl_request(
+        family_id,
+        MACSEC_CMD_GET_TXSC,
+        MACSEC_GENL_VERSION,
+        NLM_F_DUMP,
+        &[],
+    )? {
+        let mut iface_index = None;
+        let mut secy = MacSecSecy::default();
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            match nla.kind() {
+                MACSEC_ATTR_IFINDEX => {
+                    iface_index = Some(parse_as_u32(nla.value())?);
+                }
+                MACSEC_ATTR_TXSA_LIST => {
+                    for sa_nla in NlasIterator::new(nla.value()).flatten() {
+                        secy.tx_sa.push(parse_sa(sa_nla.value())?);
+                    }
+                }
+                MACSEC_ATTR_RXSC_LIST => {
+                    for sc_nla in NlasIterator::new(nla.value()).flatten() {
+                        secy.rx_sc.push(parse_rx_sc(sc_nla.value())?);
+                    }
+                }
+                _ => (),
+            }
+        }
+        if let Some(iface_index) = iface_index {
+            ret.insert(iface_index, secy);
+        }
+    }
+    Ok(ret)
+}
+
+fn parse_rx_sc(data: &[u8]) -> Result<MacSecRxScInfo, NisporError> {
+    let mut ret = MacSecRxScInfo::default();
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            MACSEC_RXSC_ATTR_SCI => ret.sci = parse_as_u64(nla.value())?,
+            MACSEC_RXSC_ATTR_ACTIVE => {
+                ret.active = parse_as_u8(nla.value())? > 0
+            }
+            MACSEC_RXSC_ATTR_SA_LIST => {
+                for sa_nla in NlasIterator::new(nla.value()).flatten() {
+                    ret.sa.push(parse_sa(sa_nla.value())?);
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(ret)
+}
+
+// Kernel never include the key in reply.
+fn parse_sa(data: &[u8]) -> Result<MacSecSaInfo, NisporError> {
+    let mut ret = MacSecSaInfo::default();
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            MACSEC_SA_ATTR_AN => ret.an = parse_as_u8(nla.value())?,
+            MACSEC_SA_ATTR_ACTIVE => ret.active = parse_as_u8(nla.value())? > 0,
+            // XPN cipher is using 64 bits PN
+            MACSEC_SA_ATTR_PN => {
+                ret.pn = if nla.value().len() >= 8 {
+                    parse_as_u64(nla.value())?
+                } else {
+                    parse_as_u32(nla.value())?.into()
+                }
+            }
+            MACSEC_SA_ATTR_KEYID => {
+                ret.key_id = nla
+                    .value()
+                    .iter()
+                    .map(|b| format!("{b:02x}"))
+                    .collect::<Vec<String>>()
+                    .join("");
+            }
+            MACSEC_SA_ATTR_SSCI => {
+                ret.ssci = Some(parse_as_u32(nla.value())?);
+            }
+            _ => (),
+        }
+    }
+    Ok(ret)
+}
+
+pub(crate) fn macsec_rx_sc_request(
+    cmd: u8,
+    iface_index: u32,
+    sci: u64,
+    active: Option<bool>,
+) -> Result<(), NisporError> {
+    let mut sc_nlas = vec![DefaultNla::new(
+        MACSEC_RXSC_ATTR_SCI,
+        sci.to_ne_bytes().to_vec(),
+    )];
+    if let Some(active) = active {
+        sc_nlas.push(DefaultNla::new(
+            MACSEC_RXSC_ATTR_ACTIVE,
+            vec![active.into()],
+        ));
+    }
+    macsec_request(
+        cmd,
+        &[
+            DefaultNla::new(
+                MACSEC_ATTR_IFINDEX,
+                iface_index.to_ne_bytes().to_vec(),
+            ),
+            gen_nested_nla(MACSEC_ATTR_RXSC_CONFIG, &sc_nlas),
+        ],
+    )
+}
+
+// The `rx_sci` is required for RX SA.
+pub(crate) fn macsec_sa_request(
+    cmd: u8,
+    iface_index: u32,
+    rx_sci: Option<u64>,
+    sa_nlas: &[DefaultNla],
+) -> Result<(), NisporError> {
+    let mut nlas = vec![DefaultNla::new(
+        MACSEC_ATTR_IFINDEX,
+        iface_index.to_ne_bytes().to_vec(),
+    )];
+    if let Some(sci) = rx_sci {
+        nlas.push(gen_nested_nla(
+            MACSEC_ATTR_RXSC_CONFIG,
+            &[DefaultNla::new(
+                MACSEC_RXSC_ATTR_SCI,
+                sci.to_ne_bytes().to_vec(),
+            )],
+        ));
+    }
+    nlas.push(gen_nested_nla(MACSEC_ATTR_SA_CONFIG, sa_nlas));
+    macsec_request(cmd, &nlas)
+}
+
+fn macsec_request(cmd: u8, nlas: &[DefaultNla]) -> Result<(), NisporError> {
+    let family_id = genl_family_id(MACSEC_GENL_NAME)?;
+    genl_request(family_id, cmd, MACSEC_GENL_VERSION, NLM_F_ACK, nlas)?;
+    Ok(())
+}
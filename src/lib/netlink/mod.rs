@@ -2,11 +2,33 @@
 
 mod bridge;
 mod bridge_vlan;
+mod bridge_vlan_opts;
+mod devlink;
+mod ethtool;
+mod geneve;
+mod genl;
+mod gre;
+mod hsr;
 mod ip;
+mod ip_tunnel;
+mod macsec;
 #[allow(dead_code)] // some nla::parse_xx functions might be unused
 mod nla;
+mod socket;
+mod wireguard;
 
 pub(crate) use crate::netlink::bridge::*;
 pub(crate) use crate::netlink::bridge_vlan::*;
+pub(crate) use crate::netlink::bridge_vlan_opts::*;
+pub(crate) use crate::netlink::devlink::*;
+pub(crate) use crate::netlink::ethtool::*;
+pub(crate) use crate::netlink::geneve::*;
+pub(crate) use crate::netlink::genl::*;
+pub(crate) use crate::netlink::gre::*;
+pub(crate) use crate::netlink::hsr::*;
 pub(crate) use crate::netlink::ip::*;
+pub(crate) use crate::netlink::ip_tunnel::*;
+pub(crate) use crate::netlink::macsec::*;
 pub(crate) use crate::netlink::nla::*;
+pub(crate) use crate::netlink::socket::*;
+pub(crate) use crate::netlink::wireguard::*;
@@ -1,6 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::NisporError;
+use netlink_packet_utils::{
+    nla::{DefaultNla, NLA_F_NESTED},
+    Emitable,
+};
 use std::net::Ipv4Addr;
 use std::net::Ipv6Addr;
 
@@ -40,6 +44,14 @@ pub(crate) fn parse_as_u32(data: &[u8]) -> Result<u32, NisporError> {
     ]))
 }
 
+pub(crate) fn parse_as_be16(data: &[u8]) -> Result<u16, NisporError> {
+    Ok(u16::from_be(parse_as_u16(data)?))
+}
+
+pub(crate) fn parse_as_be32(data: &[u8]) -> Result<u32, NisporError> {
+    Ok(u32::from_be(parse_as_u32(data)?))
+}
+
 pub(crate) fn parse_as_u64(data: &[u8]) -> Result<u64, NisporError> {
     let err_msg = "wrong index when parsing as u64";
     Ok(u64::from_ne_bytes([
@@ -70,6 +82,14 @@ pub(crate) fn parse_as_u64(data: &[u8]) -> Result<u64, NisporError> {
     ]))
 }
 
+// Parse NULL terminated string
+pub(crate) fn parse_as_string(data: &[u8]) -> Result<String, NisporError> {
+    let data = data.split(|c| *c == 0).next().unwrap_or_default();
+    String::from_utf8(data.to_vec()).map_err(|e| {
+        NisporError::bug(format!("Got invalid UTF-8 string {data:?}: {e}"))
+    })
+}
+
 pub(crate) fn parse_as_ipv4(data: &[u8]) -> Result<Ipv4Addr, NisporError> {
     let addr_bytes: [u8; 4] = data.try_into().map_err(|_| {
         NisporError::invalid_argument(
@@ -87,3 +107,9 @@ pub(crate) fn parse_as_ipv6(data: &[u8]) -> Result<Ipv6Addr, NisporError> {
     })?;
     Ok(Ipv6Addr::from(addr_bytes))
 }
+
+pub(crate) fn gen_nested_nla(kind: u16, nlas: &[DefaultNla]) -> DefaultNla {
+    let mut data = vec![0; nlas.buffer_len()];
+    nlas.emit(&mut data);
+    DefaultNla::new(kind | NLA_F_NESTED, data)
+}
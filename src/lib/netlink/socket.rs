@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The rust-netlink crates do not cover every netlink message nispor needs,
+// this is a minimum blocking netlink request/reply implementation for those
+// messages.
+
+use libc::EPERM;
+use netlink_packet_utils::nla::NlasIterator;
+use netlink_sys::{Socket, SocketAddr};
+
+use crate::{
+    netlink::{parse_as_u16, parse_as_u32},
+    NisporError,
+};
+
+const NLMSG_HDR_LEN: usize = 16;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const NLM_F_ACK_TLVS: u16 = 0x200;
+const NLMSGERR_ATTR_MSG: u16 = 1;
+
+pub(crate) const NLM_F_REQUEST: u16 = 0x01;
+pub(crate) const NLM_F_ACK: u16 = 0x04;
+pub(crate) const NLM_F_DUMP: u16 = 0x300;
+
+const NL_REQUEST_SEQ: u32 = 1;
+
+// Send single netlink request and return the payload(without netlink
+// header) of each reply.
+pub(crate) fn nl_request(
+    protocol: isize,
+    msg_type: u16,
+    flags: u16,
+    payload: &[u8],
+) -> Result<Vec<Vec<u8>>, NisporError> {
+    let mut socket = Socket::new(protocol)?;
+    socket.bind_auto()?;
+    socket.connect(&SocketAddr::new(0, 0))?;
+    // Failure on requesting extended ACK is not fatal
+    socket.set_ext_ack(true).ok();
+    socket.set_cap_ack(true).ok();
+
+    let mut buffer = Vec::with_capacity(NLMSG_HDR_LEN + payload.len());
+    buffer.extend_from_slice(
+        &((NLMSG_HDR_LEN + payload.len()) as u32).to_ne_bytes(),
+    );
+    buffer.extend_from_slice(&msg_type.to_ne_bytes());
+    buffer.extend_from_slice(&(flags | NLM_F_REQUEST).to_ne_bytes());
+    buffer.extend_from_slice(&NL_REQUEST_SEQ.to_ne_bytes());
+    buffer.extend_from_slice(&0u32.to_ne_bytes());
+    buffer.extend_from_slice(payload);
+    socket.send(&buffer, 0)?;
+
+    let mut ret = Vec::new();
+    loop {
+        let (reply, _) = socket.recv_from_full()?;
+        let mut offset = 0;
+        while offset + NLMSG_HDR_LEN <= reply.len() {
+            let msg_len = parse_as_u32(&reply[offset..])? as usize;
+            let reply_type = parse_as_u16(&reply[offset + 4..])?;
+            let reply_flags = parse_as_u16(&reply[offset + 6..])?;
+            if msg_len < NLMSG_HDR_LEN || offset + msg_len > reply.len() {
+                return Err(NisporError::bug(format!(
+                    "Got invalid netlink message length {msg_len} for \
+                    request {msg_type}"
+                )));
+            }
+            let reply_payload =
+                &reply[offset + NLMSG_HDR_LEN..offset + msg_len];
+            match reply_type {
+                NLMSG_DONE => return Ok(ret),
+                NLMSG_ERROR => {
+                    let errno = -(parse_as_u32(reply_payload)? as i32);
+                    if errno == 0 {
+                        return Ok(ret);
+                    }
+                    return Err(nl_errno_to_nispor_error(
+                        msg_type,
+                        errno,
+                        reply_flags,
+                        reply_payload,
+                    ));
+                }
+                _ => ret.push(reply_payload.to_vec()),
+            }
+            offset += (msg_len + 3) & !3;
+        }
+        if flags & NLM_F_DUMP != NLM_F_DUMP && flags & NLM_F_ACK == 0 {
+            return Ok(ret);
+        }
+    }
+}
+
+fn nl_errno_to_nispor_error(
+    msg_type: u16,
+    errno: i32,
+    flags: u16,
+    payload: &[u8],
+) -> NisporError {
+    let mut msg = std::io::Error::from_raw_os_error(errno).to_string();
+    // The error code is followed by the original netlink header as we
+    // requested NETLINK_CAP_ACK
+    if flags & NLM_F_ACK_TLVS > 0 && payload.len() > 4 + NLMSG_HDR_LEN {
+        for nla in NlasIterator::new(&payload[4 + NLMSG_HDR_LEN..]).flatten() {
+            if nla.kind() == NLMSGERR_ATTR_MSG {
+                let ext_msg = nla.value().split(|c| *c == 0).next();
+                if let Some(ext_msg) = ext_msg {
+                    msg =
+                        format!("{msg}: {}", String::from_utf8_lossy(ext_msg));
+                }
+            }
+        }
+    }
+    if errno == EPERM {
+        NisporError::permission_deny(msg)
+    } else {
+        NisporError::bug(format!(
+            "Got netlink error for request {msg_type}: code {errno}, \
+            msg: {msg}"
+        ))
+    }
+}
@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The WireGuard device and peers are managed by the `wireguard` generic
+// netlink family.
+
+use std::net::{
+    IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6,
+};
+
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator};
+
+use crate::{
+    base64::base64_encode,
+    netlink::{
+        genl_family_id, genl_request, parse_as_be16, parse_as_u16,
+        parse_as_u32, parse_as_u64, parse_as_u8, NLM_F_ACK, NLM_F_DUMP,
+    },
+    NisporError, WireguardInfo, WireguardPeerInfo,
+};
+
+const WG_GENL_NAME: &str = "wireguard";
+const WG_GENL_VERSION: u8 = 1;
+
+const WG_CMD_GET_DEVICE: u8 = 0;
+const WG_CMD_SET_DEVICE: u8 = 1;
+
+const WGDEVICE_A_IFINDEX: u16 = 1;
+pub(crate) const WGDEVICE_A_PRIVATE_KEY: u16 = 3;
+const WGDEVICE_A_PUBLIC_KEY: u16 = 4;
+pub(crate) const WGDEVICE_A_LISTEN_PORT: u16 = 6;
+pub(crate) const WGDEVICE_A_FWMARK: u16 = 7;
+pub(crate) const WGDEVICE_A_PEERS: u16 = 8;
+
+pub(crate) const WGPEER_A_PUBLIC_KEY: u16 = 1;
+pub(crate) const WGPEER_A_PRESHARED_KEY: u16 = 2;
+pub(crate) const WGPEER_A_FLAGS: u16 = 3;
+pub(crate) const WGPEER_A_ENDPOINT: u16 = 4;
+pub(crate) const WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL: u16 = 5;
+const WGPEER_A_LAST_HANDSHAKE_TIME: u16 = 6;
+const WGPEER_A_RX_BYTES: u16 = 7;
+const WGPEER_A_TX_BYTES: u16 = 8;
+pub(crate) const WGPEER_A_ALLOWEDIPS: u16 = 9;
+
+pub(crate) const WGPEER_F_REMOVE_ME: u32 = 1 << 0;
+pub(crate) const WGPEER_F_REPLACE_ALLOWEDIPS: u32 = 1 << 1;
+
+pub(crate) const WGALLOWEDIP_A_FAMILY: u16 = 1;
+pub(crate) const WGALLOWEDIP_A_IPADDR: u16 = 2;
+pub(crate) const WGALLOWEDIP_A_CIDR_MASK: u16 = 3;
+
+pub(crate) const WG_KEY_LEN: usize = 32;
+
+const AF_INET: u16 = libc::AF_INET as u16;
+const AF_INET6: u16 = libc::AF_INET6 as u16;
+
+// The private key and preshared keys are included in kernel reply for
+// privileged user, we never store them.
+pub(crate) fn get_wireguard_info(
+    iface_index: u32,
+) -> Result<WireguardInfo, NisporError> {
+    let family_id = genl_family_id(WG_GENL_NAME)?;
+    let mut info = WireguardInfo::default();
+    // Kernel might split the peers into multiple replies when they cannot
+    // fit into single message.
+    for reply in genl_request(
+        family_id,
+        WG_CMD_GET_DEVICE,
+        WG_GENL_VERSION,
+        NLM_F_DUMP,
+        &[DefaultNla::new(
+            WGDEVICE_A_IFINDEX,
+            iface_index.to_ne_bytes().to_vec(),
+        )],
+    )? {
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            match nla.kind() {
+                WGDEVICE_A_PUBLIC_KEY => {
+                    info.public_key = parse_key(nla.value());
+                }
+                WGDEVICE_A_LISTEN_PORT => {
+                    info.listen_port = parse_as_u16(nla.value())?;
+                }
+                WGDEVICE_A_FWMARK => info.fwmark = parse_as_u32(nla.value())?,
+                WGDEVICE_A_PEERS => {
+                    for peer_nla in NlasIterator::new(nla.value()).flatten() {
+                        merge_peer(
+                            &mut info.peers,
+                            parse_peer(peer_nla.value())?,
+                        );
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    Ok(info)
+}
+
+// The allowed IPs of single peer might be split into multiple replies also.
+fn merge_peer(peers: &mut Vec<WireguardPeerInfo>, peer: WireguardPeerInfo) {
+    if let Some(cur_peer) =
+        peers.iter_mut().find(|p| p.public_key == peer.public_key)
+    {
+        cur_peer.allowed_ips.extend(peer.allowed_ips);
+    } else {
+        peers.push(peer);
+    }
+}
+
+fn parse_peer(data: &[u8]) -> Result<WireguardPeerInfo, NisporError> {
+    let mut ret = WireguardPeerInfo::default();
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            WGPEER_A_PUBLIC_KEY => {
+                ret.public_key = parse_key(nla.value()).unwrap_or_default();
+            }
+            WGPEER_A_ENDPOINT => ret.endpoint = parse_endpoint(nla.value())?,
+            WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL => {
+                ret.persistent_keepalive = parse_as_u16(nla.value())?;
+            }
+            // The `struct __kernel_timespec`, we only care about seconds.
+            WGPEER_A_LAST_HANDSHAKE_TIME => {
+                ret.latest_handshake = parse_as_u64(nla.value())?;
+            }
+            WGPEER_A_RX_BYTES => ret.rx_bytes = parse_as_u64(nla.value())?,
+            WGPEER_A_TX_BYTES => ret.tx_bytes = parse_as_u64(nla.value())?,
+            WGPEER_A_ALLOWEDIPS => {
+                for ip_nla in NlasIterator::new(nla.value()).flatten() {
+                    if let Some(ip) = parse_allowed_ip(ip_nla.value())? {
+                        ret.allowed_ips.push(ip);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(ret)
+}
+
+fn parse_key(data: &[u8]) -> Option<String> {
+    // Kernel reply all zero key when not configured
+    if data.len() != WG_KEY_LEN || data.iter().all(|b| *b == 0) {
+        None
+    } else {
+        Some(base64_encode(data))
+    }
+}
+
+// The endpoint is `struct sockaddr_in` or `struct sockaddr_in6`.
+fn parse_endpoint(data: &[u8]) -> Result<Option<String>, NisporError> {
+    let family = parse_as_u16(data)?;
+    let port = parse_as_be16(data.get(2..).unwrap_or_default())?;
+    let addr = match family {
+        AF_INET if data.len() >= 8 => {
+            let mut ip = [0u8; 4];
+            ip.copy_from_slice(&data[4..8]);
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::from(ip), port))
+        }
+        AF_INET6 if data.len() >= 28 => {
+            let mut ip = [0u8; 16];
+            ip.copy_from_slice(&data[8..24]);
+            SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::from(ip),
+                port,
+                u32::from_be(parse_as_u32(&data[4..8])?),
+                parse_as_u32(&data[24..28])?,
+            ))
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(addr.to_string()))
+}
+
+fn parse_allowed_ip(data: &[u8]) -> Result<Option<String>, NisporError> {
+    let mut family = None;
+    let mut ip = None;
+    let mut prefix_len = None;
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            WGALLOWEDIP_A_FAMILY => family = Some(parse_as_u16(nla.value())?),
+            WGALLOWEDIP_A_IPADDR => ip = Some(nla.value().to_vec()),
+            WGALLOWEDIP_A_CIDR_MASK => {
+                prefix_len = Some(parse_as_u8(nla.value())?)
+            }
+            _ => (),
+        }
+    }
+    Ok(match (family, ip, prefix_len) {
+        (Some(AF_INET), Some(ip), Some(prefix_len)) if ip.len() == 4 => {
+            let mut addr = [0u8; 4];
+            addr.copy_from_slice(&ip);
+            Some(format!("{}/{}", Ipv4Addr::from(addr), prefix_len))
+        }
+        (Some(AF_INET6), Some(ip), Some(prefix_len)) if ip.len() == 16 => {
+            let mut addr = [0u8; 16];
+            addr.copy_from_slice(&ip);
+            Some(format!("{}/{}", Ipv6Addr::from(addr), prefix_len))
+        }
+        _ => None,
+    })
+}
+
+pub(crate) fn endpoint_to_sockaddr(endpoint: &SocketAddr) -> Vec<u8> {
+    let mut ret = Vec::new();
+    match endpoint {
+        SocketAddr::V4(addr) => {
+            ret.extend_from_slice(&AF_INET.to_ne_bytes());
+            ret.extend_from_slice(&addr.port().to_be_bytes());
+            ret.extend_from_slice(&addr.ip().octets());
+            // sin_zero
+            ret.extend_from_slice(&[0u8; 8]);
+        }
+        SocketAddr::V6(addr) => {
+            ret.extend_from_slice(&AF_INET6.to_ne_bytes());
+            ret.extend_from_slice(&addr.port().to_be_bytes());
+            ret.extend_from_slice(&addr.flowinfo().to_be_bytes());
+            ret.extend_from_slice(&addr.ip().octets());
+            ret.extend_from_slice(&addr.scope_id().to_ne_bytes());
+        }
+    }
+    ret
+}
+
+pub(crate) fn allowed_ip_to_nlas(
+    ip: &IpAddr,
+    prefix_len: u8,
+) -> Vec<DefaultNla> {
+    let (family, octets) = match ip {
+        IpAddr::V4(i) => (AF_INET, i.octets().to_vec()),
+        IpAddr::V6(i) => (AF_INET6, i.octets().to_vec()),
+    };
+    vec![
+        DefaultNla::new(WGALLOWEDIP_A_FAMILY, family.to_ne_bytes().to_vec()),
+        DefaultNla::new(WGALLOWEDIP_A_IPADDR, octets),
+        DefaultNla::new(WGALLOWEDIP_A_CIDR_MASK, vec![prefix_len]),
+    ]
+}
+
+pub(crate) fn wireguard_set_device(
+    iface_index: u32,
+    nlas: &[DefaultNla],
+) -> Result<(), NisporError> {
+    let family_id = genl_family_id(WG_GENL_NAME)?;
+    let mut all_nlas = vec![DefaultNla::new(
+        WGDEVICE_A_IFINDEX,
+        iface_index.to_ne_bytes().to_vec(),
+    )];
+    all_nlas.extend_from_slice(nlas);
+    genl_request(
+        family_id,
+        WG_CMD_SET_DEVICE,
+        WG_GENL_VERSION,
+        NLM_F_ACK,
+        &all_nlas,
+    )?;
+    Ok(())
+}
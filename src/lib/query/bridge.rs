@@ -7,7 +7,10 @@ use netlink_packet_route::link::{AfSpecBridge, InfoBridgePort, InfoData};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    netlink::{parse_af_spec_bridge_info, parse_bridge_id, parse_bridge_info},
+    netlink::{
+        get_bridge_vlan_global_opts, get_bridge_vlan_opts,
+        parse_af_spec_bridge_info, parse_bridge_id, parse_bridge_info,
+    },
     ControllerType, Iface, IfaceType, NisporError,
 };
 
@@ -154,6 +157,10 @@ pub struct BridgeInfo {
     pub multicast_igmp_version: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub multicast_mld_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan_opts: Option<Vec<BridgeVlanOpts>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan_global_opts: Option<Vec<BridgeVlanGlobalOpts>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -194,6 +201,20 @@ impl From<u8> for BridgePortStpState {
     }
 }
 
+impl From<BridgePortStpState> for u8 {
+    fn from(value: BridgePortStpState) -> u8 {
+        match value {
+            BridgePortStpState::Disabled => BR_STATE_DISABLED,
+            BridgePortStpState::Listening => BR_STATE_LISTENING,
+            BridgePortStpState::Learning => BR_STATE_LEARNING,
+            BridgePortStpState::Forwarding => BR_STATE_FORWARDING,
+            BridgePortStpState::Blocking => BR_STATE_BLOCKING,
+            BridgePortStpState::Other(d) => d,
+            BridgePortStpState::Unknown => u8::MAX,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
@@ -299,6 +320,8 @@ pub struct BridgePortInfo {
     pub neigh_vlan_supress: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub backup_nexthop_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan_opts: Option<Vec<BridgeVlanOpts>>,
 }
 
 pub(crate) fn get_bridge_info(
@@ -455,6 +478,68 @@ pub struct BridgeVlanEntry {
     pub is_egress_untagged: bool,
 }
 
+/// Per-VLAN options of bridge or bridge port.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgeVlanOpts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vid: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vid_range: Option<(u16, u16)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stp_state: Option<BridgePortStpState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_router: Option<BridgePortMulticastRouterType>,
+    /// The tunnel ID(e.g. VxLAN VNI) this VLAN mapped to when `vlan_tunnel`
+    /// is enabled on bridge port. For VLAN range, this is the tunnel ID of
+    /// the first VLAN, the following VLANs are mapped to consecutive tunnel
+    /// IDs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tunnel_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_n_groups: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_max_groups: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub neigh_suppress: Option<bool>,
+}
+
+/// Per-VLAN global options of bridge.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgeVlanGlobalOpts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vid: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vid_range: Option<(u16, u16)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_snooping: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_querier: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_igmp_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_mld_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_last_member_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_startup_query_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_last_member_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_membership_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_querier_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_query_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_query_response_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_startup_query_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msti: Option<u16>,
+}
+
 pub(crate) fn parse_bridge_vlan_info(
     iface_state: &mut Iface,
     nlas: &[AfSpecBridge],
@@ -475,3 +560,37 @@ pub(crate) fn parse_bridge_vlan_info(
     }
     Ok(())
 }
+
+pub(crate) fn fill_bridge_vlan_opts(
+    iface_states: &mut HashMap<String, Iface>,
+    iface_index: Option<u32>,
+) -> Result<(), NisporError> {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(iface.index, iface.name.clone());
+    }
+
+    for (index, opts) in get_bridge_vlan_opts(iface_index)? {
+        if let Some(iface) = index_to_name
+            .get(&index)
+            .and_then(|name| iface_states.get_mut(name))
+        {
+            if let Some(ref mut bridge_info) = iface.bridge {
+                bridge_info.vlan_opts = Some(opts);
+            } else if let Some(ref mut port_info) = iface.bridge_port {
+                port_info.vlan_opts = Some(opts);
+            }
+        }
+    }
+
+    for (index, opts) in get_bridge_vlan_global_opts(iface_index)? {
+        if let Some(bridge_info) = index_to_name
+            .get(&index)
+            .and_then(|name| iface_states.get_mut(name))
+            .and_then(|iface| iface.bridge.as_mut())
+        {
+            bridge_info.vlan_global_opts = Some(opts);
+        }
+    }
+    Ok(())
+}
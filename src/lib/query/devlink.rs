@@ -0,0 +1,872 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    mac::parse_as_mac,
+    netlink::{
+        devlink_dump, devlink_request, gen_devlink_handle_nlas,
+        parse_as_string, parse_as_u16, parse_as_u32, parse_as_u64, parse_as_u8,
+        DEVLINK_ATTR_ESWITCH_ENCAP_MODE, DEVLINK_ATTR_ESWITCH_INLINE_MODE,
+        DEVLINK_ATTR_ESWITCH_MODE, DEVLINK_ATTR_INFO_BOARD_SERIAL_NUMBER,
+        DEVLINK_ATTR_INFO_DRIVER_NAME, DEVLINK_ATTR_INFO_SERIAL_NUMBER,
+        DEVLINK_ATTR_INFO_VERSION_FIXED, DEVLINK_ATTR_INFO_VERSION_NAME,
+        DEVLINK_ATTR_INFO_VERSION_RUNNING, DEVLINK_ATTR_INFO_VERSION_STORED,
+        DEVLINK_ATTR_INFO_VERSION_VALUE, DEVLINK_ATTR_PARAM,
+        DEVLINK_ATTR_PARAM_GENERIC, DEVLINK_ATTR_PARAM_NAME,
+        DEVLINK_ATTR_PARAM_TYPE, DEVLINK_ATTR_PARAM_VALUE,
+        DEVLINK_ATTR_PARAM_VALUES_LIST, DEVLINK_ATTR_PARAM_VALUE_CMODE,
+        DEVLINK_ATTR_PARAM_VALUE_DATA, DEVLINK_ATTR_PORT_CONTROLLER_NUMBER,
+        DEVLINK_ATTR_PORT_EXTERNAL, DEVLINK_ATTR_PORT_FLAVOUR,
+        DEVLINK_ATTR_PORT_FUNCTION, DEVLINK_ATTR_PORT_IBDEV_NAME,
+        DEVLINK_ATTR_PORT_INDEX, DEVLINK_ATTR_PORT_NETDEV_NAME,
+        DEVLINK_ATTR_PORT_NUMBER, DEVLINK_ATTR_PORT_PCI_PF_NUMBER,
+        DEVLINK_ATTR_PORT_PCI_SF_NUMBER, DEVLINK_ATTR_PORT_PCI_VF_NUMBER,
+        DEVLINK_ATTR_PORT_SPLITTABLE, DEVLINK_ATTR_PORT_SPLIT_GROUP,
+        DEVLINK_ATTR_PORT_SPLIT_SUBPORT_NUMBER, DEVLINK_ATTR_PORT_TYPE,
+        DEVLINK_CMD_ESWITCH_GET, DEVLINK_CMD_GET, DEVLINK_CMD_INFO_GET,
+        DEVLINK_CMD_PARAM_GET, DEVLINK_CMD_PORT_GET,
+        DEVLINK_PORT_FN_ATTR_OPSTATE, DEVLINK_PORT_FN_ATTR_STATE,
+        DEVLINK_PORT_FUNCTION_ATTR_HW_ADDR, NLM_F_ACK,
+    },
+    Iface, NisporError, SriovEswitchMode,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Devlink {
+    pub devices: Vec<DevlinkDevice>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct DevlinkDevice {
+    pub bus_name: String,
+    pub dev_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board_serial_number: Option<String>,
+    /// Versions of hardware components which do not change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions_fixed: Option<BTreeMap<String, String>>,
+    /// Versions of currently running firmware
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions_running: Option<BTreeMap<String, String>>,
+    /// Versions of firmware stored in flash, will be used after reboot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions_stored: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eswitch_mode: Option<SriovEswitchMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eswitch_inline_mode: Option<DevlinkEswitchInlineMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eswitch_encap_mode: Option<DevlinkEswitchEncapMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Vec<DevlinkParam>>,
+    pub ports: Vec<DevlinkPort>,
+}
+
+impl DevlinkDevice {
+    /// The `<bus_name>/<dev_name>` used by `devlink` command.
+    pub fn handle(&self) -> String {
+        format!("{}/{}", self.bus_name, self.dev_name)
+    }
+}
+
+const DEVLINK_ESWITCH_INLINE_MODE_NONE: u8 = 0;
+const DEVLINK_ESWITCH_INLINE_MODE_LINK: u8 = 1;
+const DEVLINK_ESWITCH_INLINE_MODE_NETWORK: u8 = 2;
+const DEVLINK_ESWITCH_INLINE_MODE_TRANSPORT: u8 = 3;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DevlinkEswitchInlineMode {
+    None,
+    Link,
+    Network,
+    Transport,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for DevlinkEswitchInlineMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u8> for DevlinkEswitchInlineMode {
+    fn from(d: u8) -> Self {
+        match d {
+            DEVLINK_ESWITCH_INLINE_MODE_NONE => Self::None,
+            DEVLINK_ESWITCH_INLINE_MODE_LINK => Self::Link,
+            DEVLINK_ESWITCH_INLINE_MODE_NETWORK => Self::Network,
+            DEVLINK_ESWITCH_INLINE_MODE_TRANSPORT => Self::Transport,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<DevlinkEswitchInlineMode> for u8 {
+    fn from(v: DevlinkEswitchInlineMode) -> u8 {
+        match v {
+            DevlinkEswitchInlineMode::None => DEVLINK_ESWITCH_INLINE_MODE_NONE,
+            DevlinkEswitchInlineMode::Link => DEVLINK_ESWITCH_INLINE_MODE_LINK,
+            DevlinkEswitchInlineMode::Network => {
+                DEVLINK_ESWITCH_INLINE_MODE_NETWORK
+            }
+            DevlinkEswitchInlineMode::Transport => {
+                DEVLINK_ESWITCH_INLINE_MODE_TRANSPORT
+            }
+            DevlinkEswitchInlineMode::Other(d) => d,
+            DevlinkEswitchInlineMode::Unknown => {
+                log::warn!(
+                    "Treating DevlinkEswitchInlineMode::Unknown as DevlinkEswitchInlineMode::None"
+                );
+                DEVLINK_ESWITCH_INLINE_MODE_NONE
+            }
+        }
+    }
+}
+
+const DEVLINK_ESWITCH_ENCAP_MODE_NONE: u8 = 0;
+const DEVLINK_ESWITCH_ENCAP_MODE_BASIC: u8 = 1;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DevlinkEswitchEncapMode {
+    None,
+    Basic,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for DevlinkEswitchEncapMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u8> for DevlinkEswitchEncapMode {
+    fn from(d: u8) -> Self {
+        match d {
+            DEVLINK_ESWITCH_ENCAP_MODE_NONE => Self::None,
+            DEVLINK_ESWITCH_ENCAP_MODE_BASIC => Self::Basic,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<DevlinkEswitchEncapMode> for u8 {
+    fn from(v: DevlinkEswitchEncapMode) -> u8 {
+        match v {
+            DevlinkEswitchEncapMode::None => DEVLINK_ESWITCH_ENCAP_MODE_NONE,
+            DevlinkEswitchEncapMode::Basic => DEVLINK_ESWITCH_ENCAP_MODE_BASIC,
+            DevlinkEswitchEncapMode::Other(d) => d,
+            DevlinkEswitchEncapMode::Unknown => {
+                log::warn!(
+                    "Treating DevlinkEswitchEncapMode::Unknown as DevlinkEswitchEncapMode::None"
+                );
+                DEVLINK_ESWITCH_ENCAP_MODE_NONE
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct DevlinkParam {
+    pub name: String,
+    /// Whether this parameter is defined by kernel for all drivers
+    pub generic: bool,
+    pub param_type: DevlinkParamType,
+    pub values: Vec<DevlinkParamValue>,
+}
+
+const DEVLINK_PARAM_TYPE_U8: u8 = 1;
+const DEVLINK_PARAM_TYPE_U16: u8 = 2;
+const DEVLINK_PARAM_TYPE_U32: u8 = 3;
+const DEVLINK_PARAM_TYPE_U64: u8 = 4;
+const DEVLINK_PARAM_TYPE_STRING: u8 = 5;
+const DEVLINK_PARAM_TYPE_BOOL: u8 = 6;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DevlinkParamType {
+    U8,
+    U16,
+    U32,
+    U64,
+    String,
+    Bool,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for DevlinkParamType {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u8> for DevlinkParamType {
+    fn from(d: u8) -> Self {
+        match d {
+            DEVLINK_PARAM_TYPE_U8 => Self::U8,
+            DEVLINK_PARAM_TYPE_U16 => Self::U16,
+            DEVLINK_PARAM_TYPE_U32 => Self::U32,
+            DEVLINK_PARAM_TYPE_U64 => Self::U64,
+            DEVLINK_PARAM_TYPE_STRING => Self::String,
+            DEVLINK_PARAM_TYPE_BOOL => Self::Bool,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<DevlinkParamType> for u8 {
+    fn from(v: DevlinkParamType) -> u8 {
+        match v {
+            DevlinkParamType::U8 => DEVLINK_PARAM_TYPE_U8,
+            DevlinkParamType::U16 => DEVLINK_PARAM_TYPE_U16,
+            DevlinkParamType::U32 => DEVLINK_PARAM_TYPE_U32,
+            DevlinkParamType::U64 => DEVLINK_PARAM_TYPE_U64,
+            DevlinkParamType::String => DEVLINK_PARAM_TYPE_STRING,
+            DevlinkParamType::Bool => DEVLINK_PARAM_TYPE_BOOL,
+            DevlinkParamType::Other(d) => d,
+            DevlinkParamType::Unknown => {
+                log::warn!(
+                    "Treating DevlinkParamType::Unknown as DevlinkParamType::U32"
+                );
+                DEVLINK_PARAM_TYPE_U32
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct DevlinkParamValue {
+    pub cmode: DevlinkParamCmode,
+    pub data: DevlinkParamData,
+}
+
+const DEVLINK_PARAM_CMODE_RUNTIME: u8 = 0;
+const DEVLINK_PARAM_CMODE_DRIVERINIT: u8 = 1;
+const DEVLINK_PARAM_CMODE_PERMANENT: u8 = 2;
+
+/// The configuration mode of devlink parameter
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DevlinkParamCmode {
+    /// Applied immediately
+    Runtime,
+    /// Applied after driver reload
+    Driverinit,
+    /// Stored in device non-volatile memory, applied after device reset
+    Permanent,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for DevlinkParamCmode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u8> for DevlinkParamCmode {
+    fn from(d: u8) -> Self {
+        match d {
+            DEVLINK_PARAM_CMODE_RUNTIME => Self::Runtime,
+            DEVLINK_PARAM_CMODE_DRIVERINIT => Self::Driverinit,
+            DEVLINK_PARAM_CMODE_PERMANENT => Self::Permanent,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<DevlinkParamCmode> for u8 {
+    fn from(v: DevlinkParamCmode) -> u8 {
+        match v {
+            DevlinkParamCmode::Runtime => DEVLINK_PARAM_CMODE_RUNTIME,
+            DevlinkParamCmode::Driverinit => DEVLINK_PARAM_CMODE_DRIVERINIT,
+            DevlinkParamCmode::Permanent => DEVLINK_PARAM_CMODE_PERMANENT,
+            DevlinkParamCmode::Other(d) => d,
+            DevlinkParamCmode::Unknown => {
+                log::warn!(
+                    "Treating DevlinkParamCmode::Unknown as DevlinkParamCmode::Runtime"
+                );
+                DEVLINK_PARAM_CMODE_RUNTIME
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum DevlinkParamData {
+    Bool(bool),
+    Number(u64),
+    String(String),
+}
+
+impl Default for DevlinkParamData {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct DevlinkPort {
+    pub index: u32,
+    pub port_type: DevlinkPortType,
+    pub flavour: DevlinkPortFlavour,
+    /// Kernel network interface of this port
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iface: Option<String>,
+    /// InfiniBand device name of this port
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ib_device: Option<String>,
+    /// Physical port number, only for `physical` flavour
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port_number: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_group: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_subport_number: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub splittable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub controller_number: Option<u32>,
+    /// Whether the PCI function belongs to external host
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pf_number: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vf_number: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sf_number: Option<u32>,
+    /// MAC address of the PCI function behind this port
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_hw_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_state: Option<DevlinkPortFunctionState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_opstate: Option<DevlinkPortFunctionOpstate>,
+}
+
+const DEVLINK_PORT_TYPE_NOTSET: u16 = 0;
+const DEVLINK_PORT_TYPE_AUTO: u16 = 1;
+const DEVLINK_PORT_TYPE_ETH: u16 = 2;
+const DEVLINK_PORT_TYPE_IB: u16 = 3;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DevlinkPortType {
+    Notset,
+    Auto,
+    Eth,
+    Ib,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for DevlinkPortType {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u16> for DevlinkPortType {
+    fn from(d: u16) -> Self {
+        match d {
+            DEVLINK_PORT_TYPE_NOTSET => Self::Notset,
+            DEVLINK_PORT_TYPE_AUTO => Self::Auto,
+            DEVLINK_PORT_TYPE_ETH => Self::Eth,
+            DEVLINK_PORT_TYPE_IB => Self::Ib,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+const DEVLINK_PORT_FLAVOUR_PHYSICAL: u16 = 0;
+const DEVLINK_PORT_FLAVOUR_CPU: u16 = 1;
+const DEVLINK_PORT_FLAVOUR_DSA: u16 = 2;
+const DEVLINK_PORT_FLAVOUR_PCI_PF: u16 = 3;
+const DEVLINK_PORT_FLAVOUR_PCI_VF: u16 = 4;
+const DEVLINK_PORT_FLAVOUR_VIRTUAL: u16 = 5;
+const DEVLINK_PORT_FLAVOUR_UNUSED: u16 = 6;
+const DEVLINK_PORT_FLAVOUR_PCI_SF: u16 = 7;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DevlinkPortFlavour {
+    Physical,
+    Cpu,
+    Dsa,
+    Pcipf,
+    Pcivf,
+    Virtual,
+    Unused,
+    Pcisf,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for DevlinkPortFlavour {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u16> for DevlinkPortFlavour {
+    fn from(d: u16) -> Self {
+        match d {
+            DEVLINK_PORT_FLAVOUR_PHYSICAL => Self::Physical,
+            DEVLINK_PORT_FLAVOUR_CPU => Self::Cpu,
+            DEVLINK_PORT_FLAVOUR_DSA => Self::Dsa,
+            DEVLINK_PORT_FLAVOUR_PCI_PF => Self::Pcipf,
+            DEVLINK_PORT_FLAVOUR_PCI_VF => Self::Pcivf,
+            DEVLINK_PORT_FLAVOUR_VIRTUAL => Self::Virtual,
+            DEVLINK_PORT_FLAVOUR_UNUSED => Self::Unused,
+            DEVLINK_PORT_FLAVOUR_PCI_SF => Self::Pcisf,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<DevlinkPortFlavour> for u16 {
+    fn from(v: DevlinkPortFlavour) -> u16 {
+        match v {
+            DevlinkPortFlavour::Physical => DEVLINK_PORT_FLAVOUR_PHYSICAL,
+            DevlinkPortFlavour::Cpu => DEVLINK_PORT_FLAVOUR_CPU,
+            DevlinkPortFlavour::Dsa => DEVLINK_PORT_FLAVOUR_DSA,
+            DevlinkPortFlavour::Pcipf => DEVLINK_PORT_FLAVOUR_PCI_PF,
+            DevlinkPortFlavour::Pcivf => DEVLINK_PORT_FLAVOUR_PCI_VF,
+            DevlinkPortFlavour::Virtual => DEVLINK_PORT_FLAVOUR_VIRTUAL,
+            DevlinkPortFlavour::Unused => DEVLINK_PORT_FLAVOUR_UNUSED,
+            DevlinkPortFlavour::Pcisf => DEVLINK_PORT_FLAVOUR_PCI_SF,
+            DevlinkPortFlavour::Other(d) => d,
+            DevlinkPortFlavour::Unknown => {
+                log::warn!(
+                    "Treating DevlinkPortFlavour::Unknown as DevlinkPortFlavour::Physical"
+                );
+                DEVLINK_PORT_FLAVOUR_PHYSICAL
+            }
+        }
+    }
+}
+
+const DEVLINK_PORT_FN_STATE_INACTIVE: u8 = 0;
+const DEVLINK_PORT_FN_STATE_ACTIVE: u8 = 1;
+
+/// Administrative state of the PCI function behind the port
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DevlinkPortFunctionState {
+    Inactive,
+    Active,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for DevlinkPortFunctionState {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u8> for DevlinkPortFunctionState {
+    fn from(d: u8) -> Self {
+        match d {
+            DEVLINK_PORT_FN_STATE_INACTIVE => Self::Inactive,
+            DEVLINK_PORT_FN_STATE_ACTIVE => Self::Active,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<DevlinkPortFunctionState> for u8 {
+    fn from(v: DevlinkPortFunctionState) -> u8 {
+        match v {
+            DevlinkPortFunctionState::Inactive => {
+                DEVLINK_PORT_FN_STATE_INACTIVE
+            }
+            DevlinkPortFunctionState::Active => DEVLINK_PORT_FN_STATE_ACTIVE,
+            DevlinkPortFunctionState::Other(d) => d,
+            DevlinkPortFunctionState::Unknown => {
+                log::warn!(
+                    "Treating DevlinkPortFunctionState::Unknown as DevlinkPortFunctionState::Inactive"
+                );
+                DEVLINK_PORT_FN_STATE_INACTIVE
+            }
+        }
+    }
+}
+
+const DEVLINK_PORT_FN_OPSTATE_DETACHED: u8 = 0;
+const DEVLINK_PORT_FN_OPSTATE_ATTACHED: u8 = 1;
+
+/// Operational state of the PCI function behind the port
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DevlinkPortFunctionOpstate {
+    Detached,
+    Attached,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for DevlinkPortFunctionOpstate {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u8> for DevlinkPortFunctionOpstate {
+    fn from(d: u8) -> Self {
+        match d {
+            DEVLINK_PORT_FN_OPSTATE_DETACHED => Self::Detached,
+            DEVLINK_PORT_FN_OPSTATE_ATTACHED => Self::Attached,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+pub(crate) fn get_devlink() -> Result<Devlink, NisporError> {
+    let mut devices: Vec<DevlinkDevice> = Vec::new();
+    for (bus_name, dev_name, _) in devlink_dump(DEVLINK_CMD_GET)? {
+        devices.push(DevlinkDevice {
+            bus_name,
+            dev_name,
+            ..Default::default()
+        });
+    }
+
+    // Not every driver support device information, parameters and e-switch
+    for (bus_name, dev_name, reply) in devlink_dump(DEVLINK_CMD_INFO_GET)
+        .unwrap_or_else(|e| {
+            log::debug!("Failed to dump devlink device info: {}", e);
+            Vec::new()
+        })
+    {
+        if let Some(dev) = find_dev(&mut devices, &bus_name, &dev_name) {
+            parse_dev_info(dev, &reply)?;
+        }
+    }
+
+    for (bus_name, dev_name, reply) in devlink_dump(DEVLINK_CMD_PARAM_GET)
+        .unwrap_or_else(|e| {
+            log::debug!("Failed to dump devlink parameters: {}", e);
+            Vec::new()
+        })
+    {
+        if let Some(dev) = find_dev(&mut devices, &bus_name, &dev_name) {
+            for nla in NlasIterator::new(reply.as_slice()).flatten() {
+                if nla.kind() == DEVLINK_ATTR_PARAM {
+                    dev.params
+                        .get_or_insert_with(Vec::new)
+                        .push(parse_param(nla.value())?);
+                }
+            }
+        }
+    }
+
+    for (bus_name, dev_name, reply) in devlink_dump(DEVLINK_CMD_PORT_GET)? {
+        if let Some(dev) = find_dev(&mut devices, &bus_name, &dev_name) {
+            dev.ports.push(parse_port(&reply)?);
+        }
+    }
+
+    for dev in devices.iter_mut() {
+        if let Err(e) = fill_eswitch(dev) {
+            log::debug!(
+                "Failed to query e-switch of devlink device {}: {}",
+                dev.handle(),
+                e
+            );
+        }
+        dev.ports.sort_unstable_by_key(|p| p.index);
+    }
+
+    Ok(Devlink { devices })
+}
+
+fn find_dev<'a>(
+    devices: &'a mut [DevlinkDevice],
+    bus_name: &str,
+    dev_name: &str,
+) -> Option<&'a mut DevlinkDevice> {
+    devices
+        .iter_mut()
+        .find(|d| d.bus_name == bus_name && d.dev_name == dev_name)
+}
+
+fn parse_dev_info(
+    dev: &mut DevlinkDevice,
+    reply: &[u8],
+) -> Result<(), NisporError> {
+    for nla in NlasIterator::new(reply).flatten() {
+        match nla.kind() {
+            DEVLINK_ATTR_INFO_DRIVER_NAME => {
+                dev.driver = Some(parse_as_string(nla.value())?);
+            }
+            DEVLINK_ATTR_INFO_SERIAL_NUMBER => {
+                dev.serial_number = Some(parse_as_string(nla.value())?);
+            }
+            DEVLINK_ATTR_INFO_BOARD_SERIAL_NUMBER => {
+                dev.board_serial_number = Some(parse_as_string(nla.value())?);
+            }
+            DEVLINK_ATTR_INFO_VERSION_FIXED => {
+                insert_version(&mut dev.versions_fixed, nla.value())?;
+            }
+            DEVLINK_ATTR_INFO_VERSION_RUNNING => {
+                insert_version(&mut dev.versions_running, nla.value())?;
+            }
+            DEVLINK_ATTR_INFO_VERSION_STORED => {
+                insert_version(&mut dev.versions_stored, nla.value())?;
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn insert_version(
+    versions: &mut Option<BTreeMap<String, String>>,
+    data: &[u8],
+) -> Result<(), NisporError> {
+    let mut name = None;
+    let mut value = None;
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            DEVLINK_ATTR_INFO_VERSION_NAME => {
+                name = Some(parse_as_string(nla.value())?);
+            }
+            DEVLINK_ATTR_INFO_VERSION_VALUE => {
+                value = Some(parse_as_string(nla.value())?);
+            }
+            _ => (),
+        }
+    }
+    if let (Some(name), Some(value)) = (name, value) {
+        versions
+            .get_or_insert_with(BTreeMap::new)
+            .insert(name, value);
+    }
+    Ok(())
+}
+
+fn parse_param(data: &[u8]) -> Result<DevlinkParam, NisporError> {
+    let mut param = DevlinkParam::default();
+    let mut values_list = None;
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            DEVLINK_ATTR_PARAM_NAME => {
+                param.name = parse_as_string(nla.value())?;
+            }
+            DEVLINK_ATTR_PARAM_GENERIC => param.generic = true,
+            DEVLINK_ATTR_PARAM_TYPE => {
+                param.param_type = parse_as_u8(nla.value())?.into();
+            }
+            DEVLINK_ATTR_PARAM_VALUES_LIST => {
+                values_list = Some(nla.value().to_vec())
+            }
+            _ => (),
+        }
+    }
+    // The value data is parsed based on parameter type which might be
+    // placed after the value list.
+    if let Some(values_list) = values_list {
+        for nla in NlasIterator::new(values_list.as_slice()).flatten() {
+            if nla.kind() == DEVLINK_ATTR_PARAM_VALUE {
+                param
+                    .values
+                    .push(parse_param_value(param.param_type, nla.value())?);
+            }
+        }
+    }
+    Ok(param)
+}
+
+fn parse_param_value(
+    param_type: DevlinkParamType,
+    data: &[u8],
+) -> Result<DevlinkParamValue, NisporError> {
+    let mut cmode = DevlinkParamCmode::Unknown;
+    let mut value_data = None;
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            DEVLINK_ATTR_PARAM_VALUE_CMODE => {
+                cmode = parse_as_u8(nla.value())?.into();
+            }
+            DEVLINK_ATTR_PARAM_VALUE_DATA => {
+                value_data = Some(nla.value().to_vec())
+            }
+            _ => (),
+        }
+    }
+    let data = match (param_type, value_data.as_deref()) {
+        // Boolean is a flag attribute, missing means false.
+        (DevlinkParamType::Bool, v) => DevlinkParamData::Bool(v.is_some()),
+        (DevlinkParamType::U8, Some(v)) => {
+            DevlinkParamData::Number(parse_as_u8(v)?.into())
+        }
+        (DevlinkParamType::U16, Some(v)) => {
+            DevlinkParamData::Number(parse_as_u16(v)?.into())
+        }
+        (DevlinkParamType::U32, Some(v)) => {
+            DevlinkParamData::Number(parse_as_u32(v)?.into())
+        }
+        (DevlinkParamType::U64, Some(v)) => {
+            DevlinkParamData::Number(parse_as_u64(v)?)
+        }
+        (DevlinkParamType::String, Some(v)) => {
+            DevlinkParamData::String(parse_as_string(v)?)
+        }
+        (t, _) => {
+            return Err(NisporError::bug(format!(
+                "Got unsupported devlink parameter type {t:?} with data \
+                {value_data:?}"
+            )));
+        }
+    };
+    Ok(DevlinkParamValue { cmode, data })
+}
+
+fn parse_port(reply: &[u8]) -> Result<DevlinkPort, NisporError> {
+    let mut port = DevlinkPort::default();
+    for nla in NlasIterator::new(reply).flatten() {
+        match nla.kind() {
+            DEVLINK_ATTR_PORT_INDEX => port.index = parse_as_u32(nla.value())?,
+            DEVLINK_ATTR_PORT_TYPE => {
+                port.port_type = parse_as_u16(nla.value())?.into()
+            }
+            DEVLINK_ATTR_PORT_FLAVOUR => {
+                port.flavour = parse_as_u16(nla.value())?.into()
+            }
+            DEVLINK_ATTR_PORT_NETDEV_NAME => {
+                port.iface = Some(parse_as_string(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_IBDEV_NAME => {
+                port.ib_device = Some(parse_as_string(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_NUMBER => {
+                port.port_number = Some(parse_as_u32(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_SPLIT_GROUP => {
+                port.split_group = Some(parse_as_u32(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_SPLIT_SUBPORT_NUMBER => {
+                port.split_subport_number = Some(parse_as_u32(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_SPLITTABLE => {
+                port.splittable = Some(parse_as_u8(nla.value())? > 0)
+            }
+            DEVLINK_ATTR_PORT_CONTROLLER_NUMBER => {
+                port.controller_number = Some(parse_as_u32(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_EXTERNAL => {
+                port.external = Some(parse_as_u8(nla.value())? > 0)
+            }
+            DEVLINK_ATTR_PORT_PCI_PF_NUMBER => {
+                port.pf_number = Some(parse_as_u16(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_PCI_VF_NUMBER => {
+                port.vf_number = Some(parse_as_u16(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_PCI_SF_NUMBER => {
+                port.sf_number = Some(parse_as_u32(nla.value())?)
+            }
+            DEVLINK_ATTR_PORT_FUNCTION => {
+                parse_port_function(&mut port, nla.value())?
+            }
+            _ => (),
+        }
+    }
+    Ok(port)
+}
+
+fn parse_port_function(
+    port: &mut DevlinkPort,
+    data: &[u8],
+) -> Result<(), NisporError> {
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            DEVLINK_PORT_FUNCTION_ATTR_HW_ADDR if !nla.value().is_empty() => {
+                port.function_hw_addr =
+                    Some(parse_as_mac(nla.value().len(), nla.value())?);
+            }
+            DEVLINK_PORT_FN_ATTR_STATE => {
+                port.function_state = Some(parse_as_u8(nla.value())?.into());
+            }
+            DEVLINK_PORT_FN_ATTR_OPSTATE => {
+                port.function_opstate = Some(parse_as_u8(nla.value())?.into());
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn fill_eswitch(dev: &mut DevlinkDevice) -> Result<(), NisporError> {
+    for reply in devlink_request(
+        DEVLINK_CMD_ESWITCH_GET,
+        NLM_F_ACK,
+        &gen_devlink_handle_nlas(&dev.bus_name, &dev.dev_name),
+    )? {
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            match nla.kind() {
+                DEVLINK_ATTR_ESWITCH_MODE => {
+                    dev.eswitch_mode = Some(parse_as_u16(nla.value())?.into());
+                }
+                DEVLINK_ATTR_ESWITCH_INLINE_MODE => {
+                    dev.eswitch_inline_mode =
+                        Some(parse_as_u8(nla.value())?.into());
+                }
+                DEVLINK_ATTR_ESWITCH_ENCAP_MODE => {
+                    dev.eswitch_encap_mode =
+                        Some(parse_as_u8(nla.value())?.into());
+                }
+                _ => (),
+            }
+        }
+    }
+    Ok(())
+}
+
+// Set `Iface.devlink_port` to `<bus_name>/<dev_name>/<port_index>`.
+pub(crate) fn merge_devlink_info(
+    iface_states: &mut HashMap<String, Iface>,
+    devlink: &Devlink,
+) {
+    for dev in devlink.devices.iter() {
+        for port in dev.ports.iter() {
+            if let Some(iface) = port
+                .iface
+                .as_ref()
+                .and_then(|iface_name| iface_states.get_mut(iface_name))
+            {
+                iface.devlink_port =
+                    Some(format!("{}/{}", dev.handle(), port.index));
+            }
+        }
+    }
+}
@@ -3,14 +3,33 @@
 use std::collections::{hash_map::Entry, BTreeMap, HashMap};
 
 use ethtool::{
-    EthtoolAttr, EthtoolCoalesceAttr, EthtoolFeatureAttr, EthtoolFeatureBit,
-    EthtoolHandle, EthtoolHeader, EthtoolLinkModeAttr, EthtoolPauseAttr,
-    EthtoolRingAttr,
+    EthtoolAttr, EthtoolChannelAttr, EthtoolCoalesceAttr, EthtoolFeatureAttr,
+    EthtoolFeatureBit, EthtoolFecAttr, EthtoolFecMode, EthtoolHandle,
+    EthtoolHeader, EthtoolLinkModeAttr, EthtoolPauseAttr, EthtoolRingAttr,
 };
 use futures::stream::TryStreamExt;
+use netlink_packet_utils::nla::NlasIterator;
 use serde::{Deserialize, Serialize, Serializer};
 
-use crate::NisporError;
+use crate::{
+    netlink::{
+        ethtool_dump, parse_as_u32, parse_as_u8, parse_ethtool_bitset,
+        ETHTOOL_A_EEE_ACTIVE, ETHTOOL_A_EEE_ENABLED, ETHTOOL_A_EEE_MODES_OURS,
+        ETHTOOL_A_EEE_MODES_PEER, ETHTOOL_A_EEE_TX_LPI_ENABLED,
+        ETHTOOL_A_EEE_TX_LPI_TIMER, ETHTOOL_A_LINKSTATE_EXT_DOWN_CNT,
+        ETHTOOL_A_LINKSTATE_EXT_STATE, ETHTOOL_A_LINKSTATE_EXT_SUBSTATE,
+        ETHTOOL_A_LINKSTATE_LINK, ETHTOOL_A_LINKSTATE_SQI,
+        ETHTOOL_A_LINKSTATE_SQI_MAX, ETHTOOL_A_TSINFO_PHC_INDEX,
+        ETHTOOL_A_TSINFO_RX_FILTERS, ETHTOOL_A_TSINFO_TIMESTAMPING,
+        ETHTOOL_A_TSINFO_TX_TYPES, ETHTOOL_A_WOL_MODES, ETHTOOL_MSG_EEE_GET,
+        ETHTOOL_MSG_LINKSTATE_GET, ETHTOOL_MSG_TSINFO_GET, ETHTOOL_MSG_WOL_GET,
+    },
+    EthtoolDriverInfo, EthtoolRssInfo, EthtoolStatsInfo, NisporError,
+};
+
+use super::ethtool_driver::{dump_private_flags, get_ethtool_driver_infos};
+use super::ethtool_rss::get_ethtool_rss_infos;
+use super::ethtool_stats::get_ethtool_stats;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[non_exhaustive]
@@ -121,6 +140,21 @@ impl From<&ethtool::EthtoolLinkModeDuplex> for EthtoolLinkModeDuplex {
     }
 }
 
+const DUPLEX_HALF: u8 = 0;
+const DUPLEX_FULL: u8 = 1;
+const DUPLEX_UNKNOWN: u8 = 0xff;
+
+impl From<&EthtoolLinkModeDuplex> for u8 {
+    fn from(v: &EthtoolLinkModeDuplex) -> u8 {
+        match v {
+            EthtoolLinkModeDuplex::Half => DUPLEX_HALF,
+            EthtoolLinkModeDuplex::Full => DUPLEX_FULL,
+            EthtoolLinkModeDuplex::Unknown => DUPLEX_UNKNOWN,
+            EthtoolLinkModeDuplex::Other(d) => *d,
+        }
+    }
+}
+
 impl Default for EthtoolLinkModeDuplex {
     fn default() -> Self {
         EthtoolLinkModeDuplex::Unknown
@@ -144,6 +178,151 @@ pub struct EthtoolLinkModeInfo {
     pub lanes: Option<u32>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolChannelInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rx: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rx_max: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_max: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_max: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combined: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combined_max: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolEeeInfo {
+    pub enabled: bool,
+    pub active: bool,
+    pub tx_lpi_enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_lpi_timer: Option<u32>,
+    /// Advertised EEE link modes
+    pub ours: Vec<String>,
+    /// EEE link modes advertised by link partner
+    pub peer: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolFecInfo {
+    /// Configured FEC modes
+    pub modes: Vec<String>,
+    /// Driver choose FEC mode based on SFP module parameters
+    pub auto: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolWolInfo {
+    /// Supported Wake-on-LAN modes, e.g. `magic`, `phy`
+    pub supported: Vec<String>,
+    pub enabled: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolTimestampingInfo {
+    /// Timestamping capabilities, e.g. `hardware-transmit`,
+    /// `software-receive`, `hardware-raw-clock`
+    pub capabilities: Vec<String>,
+    /// Supported hardware transmit timestamping modes, e.g. `off`, `on`
+    pub tx_types: Vec<String>,
+    /// Supported hardware receive filter modes, e.g. `none`, `all`
+    pub rx_filters: Vec<String>,
+    /// Index of PTP hardware clock(PHC)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phc_index: Option<u32>,
+    /// The PTP device of PHC, e.g. `/dev/ptp0`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ptp_device: Option<String>,
+}
+
+impl EthtoolTimestampingInfo {
+    /// Whether both transmit and receive hardware timestamping are supported.
+    pub fn is_hardware_capable(&self) -> bool {
+        self.capabilities.iter().any(|c| c == "hardware-transmit")
+            && self.capabilities.iter().any(|c| c == "hardware-receive")
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EthtoolLinkExtState {
+    Autoneg,
+    LinkTrainingFailure,
+    LinkLogicalMismatch,
+    BadSignalIntegrity,
+    NoCable,
+    CableIssue,
+    EepromIssue,
+    CalibrationFailure,
+    PowerBudgetExceeded,
+    Overheat,
+    ModuleIssue,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for EthtoolLinkExtState {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u8> for EthtoolLinkExtState {
+    fn from(d: u8) -> Self {
+        match d {
+            0 => Self::Autoneg,
+            1 => Self::LinkTrainingFailure,
+            2 => Self::LinkLogicalMismatch,
+            3 => Self::BadSignalIntegrity,
+            4 => Self::NoCable,
+            5 => Self::CableIssue,
+            6 => Self::EepromIssue,
+            7 => Self::CalibrationFailure,
+            8 => Self::PowerBudgetExceeded,
+            9 => Self::Overheat,
+            10 => Self::ModuleIssue,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolLinkStateInfo {
+    pub link: bool,
+    /// Signal Quality Index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sqi: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sqi_max: Option<u32>,
+    /// Extended reason of link down
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext_state: Option<EthtoolLinkExtState>,
+    /// The meaning of sub-state depends on `ext_state`, please refer to
+    /// `enum ethtool_link_ext_substate_*` of kernel `linux/ethtool.h`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext_substate: Option<u8>,
+    /// Number of times the link went down
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext_down_count: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct EthtoolInfo {
@@ -157,6 +336,30 @@ pub struct EthtoolInfo {
     pub ring: Option<EthtoolRingInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub link_mode: Option<EthtoolLinkModeInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<EthtoolChannelInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eee: Option<EthtoolEeeInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fec: Option<EthtoolFecInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wol: Option<EthtoolWolInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_state: Option<EthtoolLinkStateInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamping: Option<EthtoolTimestampingInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_info: Option<EthtoolDriverInfo>,
+    /// Driver specific private flags, equal to `ethtool --show-priv-flags`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_flags: Option<BTreeMap<String, bool>>,
+    /// RSS information of each RSS context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rss: Option<Vec<EthtoolRssInfo>>,
+    /// Only included when `NetStateIfaceFilter.include_ethtool_stats` is
+    /// true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<EthtoolStatsInfo>,
 }
 
 fn ordered_map<S>(
@@ -171,6 +374,7 @@ where
 }
 
 pub(crate) async fn get_ethtool_infos(
+    include_stats: bool,
 ) -> Result<HashMap<String, EthtoolInfo>, NisporError> {
     let mut infos: HashMap<String, EthtoolInfo> = HashMap::new();
 
@@ -262,6 +466,114 @@ pub(crate) async fn get_ethtool_infos(
         };
     }
 
+    match dump_channel_infos(&mut handle).await {
+        Ok(channel_infos) => {
+            for (iface_name, channel_info) in channel_infos {
+                infos.entry(iface_name).or_default().channel =
+                    Some(channel_info);
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to query ethtool channel: {}", e);
+        }
+    }
+    // ETHTOOL_MSG_FEC_GET is only available since Linux 5.14
+    match dump_fec_infos(&mut handle).await {
+        Ok(fec_infos) => {
+            for (iface_name, fec_info) in fec_infos {
+                infos.entry(iface_name).or_default().fec = Some(fec_info);
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to query ethtool FEC: {}", e);
+        }
+    }
+    match dump_timestamping_infos() {
+        Ok(timestamping_infos) => {
+            for (iface_name, timestamping_info) in timestamping_infos {
+                infos.entry(iface_name).or_default().timestamping =
+                    Some(timestamping_info);
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to query ethtool timestamping: {}", e);
+        }
+    }
+    match dump_eee_infos() {
+        Ok(eee_infos) => {
+            for (iface_name, eee_info) in eee_infos {
+                infos.entry(iface_name).or_default().eee = Some(eee_info);
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to query ethtool EEE: {}", e);
+        }
+    }
+    match dump_wol_infos() {
+        Ok(wol_infos) => {
+            for (iface_name, wol_info) in wol_infos {
+                infos.entry(iface_name).or_default().wol = Some(wol_info);
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to query ethtool WoL: {}", e);
+        }
+    }
+    match dump_link_state_infos() {
+        Ok(link_state_infos) => {
+            for (iface_name, link_state_info) in link_state_infos {
+                infos.entry(iface_name).or_default().link_state =
+                    Some(link_state_info);
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to query ethtool link state: {}", e);
+        }
+    }
+
+    match dump_private_flags() {
+        Ok(private_flags) => {
+            for (iface_name, flags) in private_flags {
+                infos.entry(iface_name).or_default().private_flags =
+                    Some(flags);
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to query ethtool private flags: {}", e);
+        }
+    }
+
+    let iface_names: Vec<String> = infos.keys().cloned().collect();
+    for (iface_name, driver_info) in
+        get_ethtool_driver_infos(iface_names.iter())
+    {
+        infos.entry(iface_name).or_default().driver_info = Some(driver_info);
+    }
+    match get_ethtool_rss_infos(iface_names.iter()) {
+        Ok(rss_infos) => {
+            for (iface_name, rss_info) in rss_infos {
+                infos.entry(iface_name).or_default().rss = Some(rss_info);
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to query ethtool RSS: {}", e);
+        }
+    }
+
+    if include_stats {
+        match get_ethtool_stats(iface_names.iter()) {
+            Ok(stats_infos) => {
+                for (iface_name, stats_info) in stats_infos {
+                    infos.entry(iface_name).or_default().stats =
+                        Some(stats_info);
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to query ethtool statistics: {}", e);
+            }
+        }
+    }
+
     Ok(infos)
 }
 
@@ -560,7 +872,7 @@ async fn dump_link_mode_infos(
                     }
                     EthtoolLinkModeAttr::Speed(d) => {
                         link_mode_info.speed =
-                            if *d == std::u32::MAX { 0 } else { *d }
+                            if *d == u32::MAX { 0 } else { *d }
                     }
                     EthtoolLinkModeAttr::Duplex(d) => {
                         link_mode_info.duplex = d.into()
@@ -589,6 +901,207 @@ async fn dump_link_mode_infos(
     Ok(infos)
 }
 
+async fn dump_channel_infos(
+    handle: &mut EthtoolHandle,
+) -> Result<HashMap<String, EthtoolChannelInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    let mut channel_handle = handle.channel().get(None).execute().await;
+    while let Some(genl_msg) = channel_handle.try_next().await? {
+        let ethtool_msg = genl_msg.payload;
+        let mut iface_name = None;
+        let mut channel_info = EthtoolChannelInfo::default();
+        for nla in ethtool_msg.nlas.as_slice() {
+            if let EthtoolAttr::Channel(nla) = nla {
+                match nla {
+                    EthtoolChannelAttr::Header(hdrs) => {
+                        iface_name = get_iface_name_from_header(hdrs)
+                    }
+                    EthtoolChannelAttr::RxMax(d) => {
+                        channel_info.rx_max = Some(*d)
+                    }
+                    EthtoolChannelAttr::TxMax(d) => {
+                        channel_info.tx_max = Some(*d)
+                    }
+                    EthtoolChannelAttr::OtherMax(d) => {
+                        channel_info.other_max = Some(*d)
+                    }
+                    EthtoolChannelAttr::CombinedMax(d) => {
+                        channel_info.combined_max = Some(*d)
+                    }
+                    EthtoolChannelAttr::RxCount(d) => {
+                        channel_info.rx = Some(*d)
+                    }
+                    EthtoolChannelAttr::TxCount(d) => {
+                        channel_info.tx = Some(*d)
+                    }
+                    EthtoolChannelAttr::OtherCount(d) => {
+                        channel_info.other = Some(*d)
+                    }
+                    EthtoolChannelAttr::CombinedCount(d) => {
+                        channel_info.combined = Some(*d)
+                    }
+                    _ => log::warn!(
+                        "WARN: Unsupported EthtoolChannelAttr {:?}",
+                        nla
+                    ),
+                }
+            }
+        }
+        if let Some(i) = iface_name {
+            infos.insert(i, channel_info);
+        }
+    }
+    Ok(infos)
+}
+
+async fn dump_fec_infos(
+    handle: &mut EthtoolHandle,
+) -> Result<HashMap<String, EthtoolFecInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    let mut fec_handle = handle.fec().get(None).execute().await;
+    while let Some(genl_msg) = fec_handle.try_next().await? {
+        let ethtool_msg = genl_msg.payload;
+        let mut iface_name = None;
+        let mut fec_info = EthtoolFecInfo::default();
+        for nla in ethtool_msg.nlas.as_slice() {
+            if let EthtoolAttr::Fec(nla) = nla {
+                match nla {
+                    EthtoolFecAttr::Header(hdrs) => {
+                        iface_name = get_iface_name_from_header(hdrs)
+                    }
+                    EthtoolFecAttr::Modes(modes) => {
+                        fec_info.modes =
+                            modes.iter().map(fec_mode_to_string).collect()
+                    }
+                    EthtoolFecAttr::Auto(d) => fec_info.auto = *d,
+                    EthtoolFecAttr::Active(d) => {
+                        fec_info.active = Some(fec_mode_to_string(d))
+                    }
+                    // FEC statistics are not requested
+                    _ => {
+                        log::warn!("WARN: Unsupported EthtoolFecAttr {:?}", nla)
+                    }
+                }
+            }
+        }
+        if let Some(i) = iface_name {
+            infos.insert(i, fec_info);
+        }
+    }
+    Ok(infos)
+}
+
+fn fec_mode_to_string(mode: &EthtoolFecMode) -> String {
+    match mode {
+        // The active FEC mode is reported as link mode bit index without name
+        EthtoolFecMode::Other(index, name) if name.is_empty() => {
+            index.to_string()
+        }
+        _ => mode.to_string(),
+    }
+}
+
+fn bitset_to_names(data: &[u8], only_set: bool) -> Vec<String> {
+    parse_ethtool_bitset(data)
+        .into_iter()
+        .filter_map(
+            |(name, value)| {
+                if value || !only_set {
+                    Some(name)
+                } else {
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+// The ethtool crate does not support EEE, WoL and link state yet.
+fn dump_eee_infos() -> Result<HashMap<String, EthtoolEeeInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    for (iface_name, reply) in ethtool_dump(ETHTOOL_MSG_EEE_GET)? {
+        let mut eee_info = EthtoolEeeInfo::default();
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            match nla.kind() {
+                ETHTOOL_A_EEE_MODES_OURS => {
+                    eee_info.ours = bitset_to_names(nla.value(), true)
+                }
+                ETHTOOL_A_EEE_MODES_PEER => {
+                    eee_info.peer = bitset_to_names(nla.value(), true)
+                }
+                ETHTOOL_A_EEE_ACTIVE => {
+                    eee_info.active = parse_as_u8(nla.value())? > 0
+                }
+                ETHTOOL_A_EEE_ENABLED => {
+                    eee_info.enabled = parse_as_u8(nla.value())? > 0
+                }
+                ETHTOOL_A_EEE_TX_LPI_ENABLED => {
+                    eee_info.tx_lpi_enabled = parse_as_u8(nla.value())? > 0
+                }
+                ETHTOOL_A_EEE_TX_LPI_TIMER => {
+                    eee_info.tx_lpi_timer = Some(parse_as_u32(nla.value())?)
+                }
+                _ => (),
+            }
+        }
+        infos.insert(iface_name, eee_info);
+    }
+    Ok(infos)
+}
+
+fn dump_wol_infos() -> Result<HashMap<String, EthtoolWolInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    for (iface_name, reply) in ethtool_dump(ETHTOOL_MSG_WOL_GET)? {
+        let mut wol_info = EthtoolWolInfo::default();
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            // The secure on password is only included for privileged user,
+            // we never store it.
+            if nla.kind() == ETHTOOL_A_WOL_MODES {
+                wol_info.supported = bitset_to_names(nla.value(), false);
+                wol_info.enabled = bitset_to_names(nla.value(), true);
+            }
+        }
+        infos.insert(iface_name, wol_info);
+    }
+    Ok(infos)
+}
+
+fn dump_link_state_infos(
+) -> Result<HashMap<String, EthtoolLinkStateInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    for (iface_name, reply) in ethtool_dump(ETHTOOL_MSG_LINKSTATE_GET)? {
+        let mut link_state_info = EthtoolLinkStateInfo::default();
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            match nla.kind() {
+                ETHTOOL_A_LINKSTATE_LINK => {
+                    link_state_info.link = parse_as_u8(nla.value())? > 0
+                }
+                ETHTOOL_A_LINKSTATE_SQI => {
+                    link_state_info.sqi = Some(parse_as_u32(nla.value())?)
+                }
+                ETHTOOL_A_LINKSTATE_SQI_MAX => {
+                    link_state_info.sqi_max = Some(parse_as_u32(nla.value())?)
+                }
+                ETHTOOL_A_LINKSTATE_EXT_STATE => {
+                    link_state_info.ext_state =
+                        Some(parse_as_u8(nla.value())?.into())
+                }
+                ETHTOOL_A_LINKSTATE_EXT_SUBSTATE => {
+                    link_state_info.ext_substate =
+                        Some(parse_as_u8(nla.value())?)
+                }
+                ETHTOOL_A_LINKSTATE_EXT_DOWN_CNT => {
+                    link_state_info.ext_down_count =
+                        Some(parse_as_u32(nla.value())?)
+                }
+                _ => (),
+            }
+        }
+        infos.insert(iface_name, link_state_info);
+    }
+    Ok(infos)
+}
+
 fn get_iface_name_from_header(hdrs: &[EthtoolHeader]) -> Option<String> {
     for hdr in hdrs {
         if let EthtoolHeader::DevName(iface_name) = hdr {
@@ -597,3 +1110,34 @@ fn get_iface_name_from_header(hdrs: &[EthtoolHeader]) -> Option<String> {
     }
     None
 }
+
+// The ethtool crate ignores bits of bitset without mask which is always the
+// case for timestamping.
+fn dump_timestamping_infos(
+) -> Result<HashMap<String, EthtoolTimestampingInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    for (iface_name, reply) in ethtool_dump(ETHTOOL_MSG_TSINFO_GET)? {
+        let mut ts_info = EthtoolTimestampingInfo::default();
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            match nla.kind() {
+                ETHTOOL_A_TSINFO_TIMESTAMPING => {
+                    ts_info.capabilities = bitset_to_names(nla.value(), true)
+                }
+                ETHTOOL_A_TSINFO_TX_TYPES => {
+                    ts_info.tx_types = bitset_to_names(nla.value(), true)
+                }
+                ETHTOOL_A_TSINFO_RX_FILTERS => {
+                    ts_info.rx_filters = bitset_to_names(nla.value(), true)
+                }
+                ETHTOOL_A_TSINFO_PHC_INDEX => {
+                    let phc_index = parse_as_u32(nla.value())?;
+                    ts_info.phc_index = Some(phc_index);
+                    ts_info.ptp_device = Some(format!("/dev/ptp{phc_index}"));
+                }
+                _ => (),
+            }
+        }
+        infos.insert(iface_name, ts_info);
+    }
+    Ok(infos)
+}
@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ioctl::{ethtool_ioctl, ethtool_ioctl_error},
+    netlink::{
+        ethtool_dump, parse_as_string, parse_as_u32, parse_ethtool_bitset,
+        ETHTOOL_A_PRIVFLAGS_FLAGS, ETHTOOL_MSG_PRIVFLAGS_GET,
+    },
+    NisporError,
+};
+
+/// Equal to `ethtool -i`
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolDriverInfo {
+    pub driver: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub firmware_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bus_info: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expansion_rom_version: Option<String>,
+    pub supports_statistics: bool,
+    pub supports_test: bool,
+    pub supports_eeprom_access: bool,
+    pub supports_register_dump: bool,
+    pub supports_private_flags: bool,
+}
+
+// The driver information has no netlink equivalent.
+const ETHTOOL_GDRVINFO: u32 = 0x03;
+// Size of `struct ethtool_drvinfo`
+const ETHTOOL_DRVINFO_LEN: usize = 196;
+const ETHTOOL_DRVINFO_STR_LEN: usize = 32;
+const ETHTOOL_DRVINFO_DRIVER_OFFSET: usize = 4;
+const ETHTOOL_DRVINFO_VERSION_OFFSET: usize = 36;
+const ETHTOOL_DRVINFO_FW_VERSION_OFFSET: usize = 68;
+const ETHTOOL_DRVINFO_BUS_INFO_OFFSET: usize = 100;
+const ETHTOOL_DRVINFO_EROM_VERSION_OFFSET: usize = 132;
+const ETHTOOL_DRVINFO_N_PRIV_FLAGS_OFFSET: usize = 176;
+const ETHTOOL_DRVINFO_N_STATS_OFFSET: usize = 180;
+const ETHTOOL_DRVINFO_TESTINFO_LEN_OFFSET: usize = 184;
+const ETHTOOL_DRVINFO_EEDUMP_LEN_OFFSET: usize = 188;
+const ETHTOOL_DRVINFO_REGDUMP_LEN_OFFSET: usize = 192;
+
+pub(crate) fn get_ethtool_driver_infos<'a>(
+    iface_names: impl Iterator<Item = &'a String>,
+) -> HashMap<String, EthtoolDriverInfo> {
+    let mut infos = HashMap::new();
+    for iface_name in iface_names {
+        // Interface without parent device like loopback does not have
+        // driver information.
+        match get_driver_info(iface_name) {
+            Ok(info) => {
+                infos.insert(iface_name.to_string(), info);
+            }
+            Err(e) => log::debug!("{}", e),
+        }
+    }
+    infos
+}
+
+fn get_driver_info(iface_name: &str) -> Result<EthtoolDriverInfo, NisporError> {
+    let mut drvinfo = vec![0u8; ETHTOOL_DRVINFO_LEN];
+    drvinfo[0..4].copy_from_slice(&ETHTOOL_GDRVINFO.to_ne_bytes());
+    ethtool_ioctl(iface_name, &mut drvinfo)
+        .map_err(|e| ethtool_ioctl_error(iface_name, e))?;
+
+    let get_str = |offset: usize| -> Result<Option<String>, NisporError> {
+        let s = parse_as_string(
+            &drvinfo[offset..offset + ETHTOOL_DRVINFO_STR_LEN],
+        )?;
+        Ok(if s.is_empty() { None } else { Some(s) })
+    };
+    let get_u32 = |offset: usize| -> Result<u32, NisporError> {
+        parse_as_u32(&drvinfo[offset..offset + 4])
+    };
+
+    Ok(EthtoolDriverInfo {
+        driver: get_str(ETHTOOL_DRVINFO_DRIVER_OFFSET)?.unwrap_or_default(),
+        version: get_str(ETHTOOL_DRVINFO_VERSION_OFFSET)?,
+        firmware_version: get_str(ETHTOOL_DRVINFO_FW_VERSION_OFFSET)?,
+        bus_info: get_str(ETHTOOL_DRVINFO_BUS_INFO_OFFSET)?,
+        expansion_rom_version: get_str(ETHTOOL_DRVINFO_EROM_VERSION_OFFSET)?,
+        supports_statistics: get_u32(ETHTOOL_DRVINFO_N_STATS_OFFSET)? > 0,
+        supports_test: get_u32(ETHTOOL_DRVINFO_TESTINFO_LEN_OFFSET)? > 0,
+        supports_eeprom_access: get_u32(ETHTOOL_DRVINFO_EEDUMP_LEN_OFFSET)? > 0,
+        supports_register_dump: get_u32(ETHTOOL_DRVINFO_REGDUMP_LEN_OFFSET)?
+            > 0,
+        supports_private_flags: get_u32(ETHTOOL_DRVINFO_N_PRIV_FLAGS_OFFSET)?
+            > 0,
+    })
+}
+
+// Interface without private flags is not included.
+pub(crate) fn dump_private_flags(
+) -> Result<HashMap<String, BTreeMap<String, bool>>, NisporError> {
+    let mut infos = HashMap::new();
+    for (iface_name, reply) in ethtool_dump(ETHTOOL_MSG_PRIVFLAGS_GET)? {
+        let flags = parse_private_flags(reply.as_slice());
+        if !flags.is_empty() {
+            infos.insert(iface_name, flags);
+        }
+    }
+    Ok(infos)
+}
+
+pub(crate) fn parse_private_flags(reply: &[u8]) -> BTreeMap<String, bool> {
+    let mut flags = BTreeMap::new();
+    for nla in NlasIterator::new(reply).flatten() {
+        if nla.kind() == ETHTOOL_A_PRIVFLAGS_FLAGS {
+            flags.extend(parse_ethtool_bitset(nla.value()));
+        }
+    }
+    flags
+}
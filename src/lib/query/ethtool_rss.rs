@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ioctl::{ethtool_ioctl, ethtool_ioctl_error, is_ioctl_not_supported},
+    mac::parse_as_mac,
+    netlink::{
+        ethtool_dump, parse_as_u32, ETHTOOL_A_RSS_CONTEXT, ETHTOOL_A_RSS_HFUNC,
+        ETHTOOL_A_RSS_HKEY, ETHTOOL_A_RSS_INDIR, ETHTOOL_MSG_RSS_GET,
+    },
+    NisporError,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EthtoolRssHashFunction {
+    Toeplitz,
+    Xor,
+    Crc32,
+    Other(u32),
+    Unknown,
+}
+
+impl Default for EthtoolRssHashFunction {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u32> for EthtoolRssHashFunction {
+    fn from(d: u32) -> Self {
+        match d {
+            0 => Self::Unknown,
+            ETH_RSS_HASH_TOP => Self::Toeplitz,
+            ETH_RSS_HASH_XOR => Self::Xor,
+            ETH_RSS_HASH_CRC32 => Self::Crc32,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolRssInfo {
+    /// RSS context, 0 is the default context of the NIC.
+    pub context: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash_function: Option<EthtoolRssHashFunction>,
+    /// Hash key in hex string separated by colon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash_key: Option<String>,
+    /// The receive queue index of each indirection table entry.
+    pub indirection_table: Vec<u32>,
+    /// The packet fields used for hashing indexed by flow type, for example
+    /// `tcp4: [ip_src, ip_dst, l4_bytes_0_1, l4_bytes_2_3]`.
+    /// Only queried for the default context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash_fields: Option<BTreeMap<String, Vec<String>>>,
+}
+
+const ETH_RSS_HASH_TOP: u32 = 1 << 0;
+const ETH_RSS_HASH_XOR: u32 = 1 << 1;
+const ETH_RSS_HASH_CRC32: u32 = 1 << 2;
+
+const ETHTOOL_GRXFH: u32 = 0x29;
+const ETHTOOL_SRXFH: u32 = 0x2a;
+const ETHTOOL_GRSSH: u32 = 0x46;
+const ETHTOOL_SRSSH: u32 = 0x47;
+
+// Size of `struct ethtool_rxfh` without the flexible `rss_config` array.
+const ETHTOOL_RXFH_HDR_LEN: usize = 24;
+// Size of `struct ethtool_rxnfc` without the flexible `rule_locs` array.
+const ETHTOOL_RXNFC_LEN: usize = 192;
+
+const RSS_FLOW_TYPES: [(&str, u32); 12] = [
+    ("tcp4", 0x01),
+    ("udp4", 0x02),
+    ("sctp4", 0x03),
+    ("ah4", 0x09),
+    ("esp4", 0x0a),
+    ("tcp6", 0x05),
+    ("udp6", 0x06),
+    ("sctp6", 0x07),
+    ("ah6", 0x0b),
+    ("esp6", 0x0c),
+    ("ip4", 0x10),
+    ("ip6", 0x11),
+];
+
+const RSS_HASH_FIELDS: [(&str, u64); 8] = [
+    ("l2_dst_mac", 1 << 1),
+    ("vlan", 1 << 2),
+    ("l3_proto", 1 << 3),
+    ("ip_src", 1 << 4),
+    ("ip_dst", 1 << 5),
+    ("l4_bytes_0_1", 1 << 6),
+    ("l4_bytes_2_3", 1 << 7),
+    ("discard", 1 << 31),
+];
+
+pub(crate) fn get_ethtool_rss_infos<'a>(
+    iface_names: impl Iterator<Item = &'a String>,
+) -> Result<HashMap<String, Vec<EthtoolRssInfo>>, NisporError> {
+    let mut infos = match dump_rss_infos() {
+        Ok(infos) => infos,
+        Err(e) => {
+            log::debug!(
+                "Failed to dump ethtool RSS via netlink, \
+                falling back to ioctl: {}",
+                e
+            );
+            let mut infos = HashMap::new();
+            for iface_name in iface_names {
+                match get_rss_info_by_ioctl(iface_name) {
+                    Ok(Some(info)) => {
+                        infos.insert(iface_name.to_string(), vec![info]);
+                    }
+                    Ok(None) => (),
+                    Err(e) => log::debug!("{}", e),
+                }
+            }
+            infos
+        }
+    };
+
+    for (iface_name, rss_infos) in infos.iter_mut() {
+        rss_infos.sort_unstable_by_key(|i| i.context);
+        if let Some(info) = rss_infos.iter_mut().find(|i| i.context == 0) {
+            info.hash_fields = get_hash_fields(iface_name);
+        }
+    }
+    Ok(infos)
+}
+
+fn dump_rss_infos() -> Result<HashMap<String, Vec<EthtoolRssInfo>>, NisporError>
+{
+    let mut infos: HashMap<String, Vec<EthtoolRssInfo>> = HashMap::new();
+    for (iface_name, reply) in ethtool_dump(ETHTOOL_MSG_RSS_GET)? {
+        let mut rss_info = EthtoolRssInfo::default();
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            match nla.kind() {
+                ETHTOOL_A_RSS_CONTEXT => {
+                    rss_info.context = parse_as_u32(nla.value())?
+                }
+                ETHTOOL_A_RSS_HFUNC => {
+                    rss_info.hash_function =
+                        Some(parse_as_u32(nla.value())?.into())
+                }
+                ETHTOOL_A_RSS_INDIR => {
+                    rss_info.indirection_table = nla
+                        .value()
+                        .chunks_exact(4)
+                        .map(parse_as_u32)
+                        .collect::<Result<Vec<u32>, NisporError>>()?
+                }
+                ETHTOOL_A_RSS_HKEY if !nla.value().is_empty() => {
+                    rss_info.hash_key =
+                        Some(parse_as_mac(nla.value().len(), nla.value())?)
+                }
+                _ => (),
+            }
+        }
+        if rss_info.indirection_table.is_empty() && rss_info.hash_key.is_none()
+        {
+            continue;
+        }
+        infos.entry(iface_name).or_default().push(rss_info);
+    }
+    Ok(infos)
+}
+
+// Return `(indirection table size, hash key size)` of default context.
+fn get_rss_sizes(iface_name: &str) -> Result<(u32, u32), std::io::Error> {
+    let mut rxfh = vec![0u8; ETHTOOL_RXFH_HDR_LEN];
+    rxfh[0..4].copy_from_slice(&ETHTOOL_GRSSH.to_ne_bytes());
+    ethtool_ioctl(iface_name, &mut rxfh)?;
+    Ok((
+        u32::from_ne_bytes([rxfh[8], rxfh[9], rxfh[10], rxfh[11]]),
+        u32::from_ne_bytes([rxfh[12], rxfh[13], rxfh[14], rxfh[15]]),
+    ))
+}
+
+// Return None if interface does not support RSS.
+fn get_rss_info_by_ioctl(
+    iface_name: &str,
+) -> Result<Option<EthtoolRssInfo>, NisporError> {
+    let (indir_size, key_size) = match get_rss_sizes(iface_name) {
+        Ok(s) => s,
+        Err(e) if is_ioctl_not_supported(&e) => return Ok(None),
+        Err(e) => return Err(ethtool_ioctl_error(iface_name, e)),
+    };
+    if indir_size == 0 && key_size == 0 {
+        return Ok(None);
+    }
+
+    // struct ethtool_rxfh
+    let indir_len = indir_size as usize * 4;
+    let mut rxfh =
+        vec![0u8; ETHTOOL_RXFH_HDR_LEN + indir_len + key_size as usize];
+    rxfh[0..4].copy_from_slice(&ETHTOOL_GRSSH.to_ne_bytes());
+    rxfh[8..12].copy_from_slice(&indir_size.to_ne_bytes());
+    rxfh[12..16].copy_from_slice(&key_size.to_ne_bytes());
+    ethtool_ioctl(iface_name, &mut rxfh)
+        .map_err(|e| ethtool_ioctl_error(iface_name, e))?;
+
+    let rss_config = &rxfh[ETHTOOL_RXFH_HDR_LEN..];
+    Ok(Some(EthtoolRssInfo {
+        context: 0,
+        hash_function: Some(u32::from(rxfh[16]).into()),
+        hash_key: if key_size > 0 {
+            Some(parse_as_mac(key_size as usize, &rss_config[indir_len..])?)
+        } else {
+            None
+        },
+        indirection_table: rss_config[..indir_len]
+            .chunks_exact(4)
+            .map(parse_as_u32)
+            .collect::<Result<Vec<u32>, NisporError>>()?,
+        hash_fields: None,
+    }))
+}
+
+fn get_hash_fields(iface_name: &str) -> Option<BTreeMap<String, Vec<String>>> {
+    let mut ret = BTreeMap::new();
+    for (flow_name, flow_type) in RSS_FLOW_TYPES.iter() {
+        // Not every flow type is supported by driver, ignore failures.
+        if let Ok(bits) = get_hash_field_bits(iface_name, *flow_type) {
+            ret.insert(flow_name.to_string(), hash_field_bits_to_names(bits));
+        }
+    }
+    if ret.is_empty() {
+        None
+    } else {
+        Some(ret)
+    }
+}
+
+fn get_hash_field_bits(
+    iface_name: &str,
+    flow_type: u32,
+) -> Result<u64, std::io::Error> {
+    let mut rxnfc = vec![0u8; ETHTOOL_RXNFC_LEN];
+    rxnfc[0..4].copy_from_slice(&ETHTOOL_GRXFH.to_ne_bytes());
+    rxnfc[4..8].copy_from_slice(&flow_type.to_ne_bytes());
+    ethtool_ioctl(iface_name, &mut rxnfc)?;
+    let mut data = [0u8; 8];
+    data.copy_from_slice(&rxnfc[8..16]);
+    Ok(u64::from_ne_bytes(data))
+}
+
+fn hash_field_bits_to_names(bits: u64) -> Vec<String> {
+    RSS_HASH_FIELDS
+        .iter()
+        .filter_map(|(name, bit)| {
+            if bits & bit > 0 {
+                Some(name.to_string())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn rss_flow_type_from_name(name: &str) -> Result<u32, NisporError> {
+    RSS_FLOW_TYPES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, t)| *t)
+        .ok_or_else(|| {
+            NisporError::invalid_argument(format!(
+                "Unknown RSS flow type {name}, supported: {}",
+                RSS_FLOW_TYPES
+                    .iter()
+                    .map(|(n, _)| *n)
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ))
+        })
+}
+
+pub(crate) fn rss_hash_field_names_to_bits(
+    names: &[String],
+) -> Result<u64, NisporError> {
+    let mut bits = 0u64;
+    for name in names {
+        match RSS_HASH_FIELDS.iter().find(|(n, _)| n == name) {
+            Some((_, bit)) => bits |= bit,
+            None => {
+                return Err(NisporError::invalid_argument(format!(
+                    "Unknown RSS hash field {name}, supported: {}",
+                    RSS_HASH_FIELDS
+                        .iter()
+                        .map(|(n, _)| *n)
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                )));
+            }
+        }
+    }
+    Ok(bits)
+}
+
+// Change indirection table of default RSS context, hash key and hash function
+// are untouched.
+pub(crate) fn set_rss_indirection_table(
+    iface_name: &str,
+    table: &[u32],
+) -> Result<(), NisporError> {
+    let mut rxfh = vec![0u8; ETHTOOL_RXFH_HDR_LEN + table.len() * 4];
+    rxfh[0..4].copy_from_slice(&ETHTOOL_SRSSH.to_ne_bytes());
+    rxfh[8..12].copy_from_slice(&(table.len() as u32).to_ne_bytes());
+    for (i, queue) in table.iter().enumerate() {
+        let offset = ETHTOOL_RXFH_HDR_LEN + i * 4;
+        rxfh[offset..offset + 4].copy_from_slice(&queue.to_ne_bytes());
+    }
+    ethtool_ioctl(iface_name, &mut rxfh)
+        .map_err(|e| ethtool_ioctl_error(iface_name, e))
+}
+
+pub(crate) fn set_rss_hash_fields(
+    iface_name: &str,
+    flow_type: u32,
+    bits: u64,
+) -> Result<(), NisporError> {
+    let mut rxnfc = vec![0u8; ETHTOOL_RXNFC_LEN];
+    rxnfc[0..4].copy_from_slice(&ETHTOOL_SRXFH.to_ne_bytes());
+    rxnfc[4..8].copy_from_slice(&flow_type.to_ne_bytes());
+    rxnfc[8..16].copy_from_slice(&bits.to_ne_bytes());
+    ethtool_ioctl(iface_name, &mut rxnfc)
+        .map_err(|e| ethtool_ioctl_error(iface_name, e))
+}
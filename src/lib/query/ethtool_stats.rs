@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ioctl::{ethtool_ioctl, ethtool_ioctl_error},
+    netlink::{
+        ethtool_dump_full, gen_ethtool_bitset_nla, parse_as_string,
+        parse_as_u32, parse_as_u64, ETHTOOL_A_HEADER, ETHTOOL_A_PAUSE_STATS,
+        ETHTOOL_A_PAUSE_STAT_RX_FRAMES, ETHTOOL_A_PAUSE_STAT_TX_FRAMES,
+        ETHTOOL_A_STATS_GROUPS, ETHTOOL_A_STATS_GRP,
+        ETHTOOL_A_STATS_GRP_HIST_BKT_HI, ETHTOOL_A_STATS_GRP_HIST_BKT_LOW,
+        ETHTOOL_A_STATS_GRP_HIST_RX, ETHTOOL_A_STATS_GRP_HIST_TX,
+        ETHTOOL_A_STATS_GRP_HIST_VAL, ETHTOOL_A_STATS_GRP_ID,
+        ETHTOOL_A_STATS_GRP_STAT, ETHTOOL_A_STATS_HEADER, ETHTOOL_FLAG_STATS,
+        ETHTOOL_MSG_PAUSE_GET, ETHTOOL_MSG_STATS_GET,
+    },
+    NisporError,
+};
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolStatsInfo {
+    /// IEEE 802.3 PHY statistics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_phy: Option<BTreeMap<String, u64>>,
+    /// IEEE 802.3 MAC statistics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_mac: Option<BTreeMap<String, u64>>,
+    /// IEEE 802.3 MAC control statistics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_ctrl: Option<BTreeMap<String, u64>>,
+    /// RMON (RFC 2819) statistics including packet size histogram
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rmon: Option<BTreeMap<String, u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pause: Option<BTreeMap<String, u64>>,
+    /// Driver specific statistics, equal to `ethtool -S`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<BTreeMap<String, u64>>,
+}
+
+const ETHTOOL_STATS_ETH_PHY: u32 = 0;
+const ETHTOOL_STATS_ETH_MAC: u32 = 1;
+const ETHTOOL_STATS_ETH_CTRL: u32 = 2;
+const ETHTOOL_STATS_RMON: u32 = 3;
+
+const STATS_GROUP_NAMES: [&str; 4] = ["eth-phy", "eth-mac", "eth-ctrl", "rmon"];
+
+// Copy from kernel net/ethtool/common.c
+const STATS_ETH_PHY_NAMES: [&str; 1] = ["SymbolErrorDuringCarrier"];
+
+const STATS_ETH_MAC_NAMES: [&str; 22] = [
+    "FramesTransmittedOK",
+    "SingleCollisionFrames",
+    "MultipleCollisionFrames",
+    "FramesReceivedOK",
+    "FrameCheckSequenceErrors",
+    "AlignmentErrors",
+    "OctetsTransmittedOK",
+    "FramesWithDeferredXmissions",
+    "LateCollisions",
+    "FramesAbortedDueToXSColls",
+    "FramesLostDueToIntMACXmitError",
+    "CarrierSenseErrors",
+    "OctetsReceivedOK",
+    "FramesLostDueToIntMACRcvError",
+    "MulticastFramesXmittedOK",
+    "BroadcastFramesXmittedOK",
+    "FramesWithExcessiveDeferral",
+    "MulticastFramesReceivedOK",
+    "BroadcastFramesReceivedOK",
+    "InRangeLengthErrors",
+    "OutOfRangeLengthField",
+    "FrameTooLongErrors",
+];
+
+const STATS_ETH_CTRL_NAMES: [&str; 3] = [
+    "MACControlFramesTransmitted",
+    "MACControlFramesReceived",
+    "UnsupportedOpcodesReceived",
+];
+
+const STATS_RMON_NAMES: [&str; 4] = [
+    "etherStatsUndersizePkts",
+    "etherStatsOversizePkts",
+    "etherStatsFragments",
+    "etherStatsJabbers",
+];
+
+pub(crate) fn get_ethtool_stats<'a>(
+    iface_names: impl Iterator<Item = &'a String>,
+) -> Result<HashMap<String, EthtoolStatsInfo>, NisporError> {
+    let mut infos: HashMap<String, EthtoolStatsInfo> = HashMap::new();
+
+    let group_bits: Vec<(&str, bool)> =
+        STATS_GROUP_NAMES.iter().map(|n| (*n, true)).collect();
+    for (iface_name, reply) in ethtool_dump_full(
+        ETHTOOL_MSG_STATS_GET,
+        ETHTOOL_A_STATS_HEADER,
+        0,
+        &[gen_ethtool_bitset_nla(
+            ETHTOOL_A_STATS_GROUPS,
+            &group_bits,
+            true,
+        )],
+    )? {
+        let info = infos.entry(iface_name).or_default();
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            if nla.kind() == ETHTOOL_A_STATS_GRP {
+                parse_stats_group(nla.value(), info)?;
+            }
+        }
+    }
+
+    for (iface_name, reply) in ethtool_dump_full(
+        ETHTOOL_MSG_PAUSE_GET,
+        ETHTOOL_A_HEADER,
+        ETHTOOL_FLAG_STATS,
+        &[],
+    )? {
+        for nla in NlasIterator::new(reply.as_slice()).flatten() {
+            if nla.kind() == ETHTOOL_A_PAUSE_STATS {
+                let mut stats = BTreeMap::new();
+                for stat_nla in NlasIterator::new(nla.value()).flatten() {
+                    let name = match stat_nla.kind() {
+                        ETHTOOL_A_PAUSE_STAT_TX_FRAMES => "tx_pause_frames",
+                        ETHTOOL_A_PAUSE_STAT_RX_FRAMES => "rx_pause_frames",
+                        _ => continue,
+                    };
+                    stats.insert(
+                        name.to_string(),
+                        parse_as_u64(stat_nla.value())?,
+                    );
+                }
+                if !stats.is_empty() {
+                    infos.entry(iface_name.clone()).or_default().pause =
+                        Some(stats);
+                }
+            }
+        }
+    }
+
+    for iface_name in iface_names {
+        match get_driver_stats(iface_name) {
+            Ok(Some(stats)) => {
+                infos.entry(iface_name.to_string()).or_default().driver =
+                    Some(stats);
+            }
+            Ok(None) => (),
+            Err(e) => {
+                log::debug!(
+                    "Failed to query driver statistics of {}: {}",
+                    iface_name,
+                    e
+                );
+            }
+        }
+    }
+
+    Ok(infos)
+}
+
+fn parse_stats_group(
+    data: &[u8],
+    info: &mut EthtoolStatsInfo,
+) -> Result<(), NisporError> {
+    let mut group_id = None;
+    let mut stats = BTreeMap::new();
+    let mut raw_stats = Vec::new();
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            ETHTOOL_A_STATS_GRP_ID => {
+                group_id = Some(parse_as_u32(nla.value())?)
+            }
+            // Each stat is nested with the index of stat as attribute type
+            ETHTOOL_A_STATS_GRP_STAT => {
+                for stat_nla in NlasIterator::new(nla.value()).flatten() {
+                    raw_stats.push((
+                        stat_nla.kind() as usize,
+                        parse_as_u64(stat_nla.value())?,
+                    ));
+                }
+            }
+            ETHTOOL_A_STATS_GRP_HIST_RX | ETHTOOL_A_STATS_GRP_HIST_TX => {
+                let direction = if nla.kind() == ETHTOOL_A_STATS_GRP_HIST_RX {
+                    "rx"
+                } else {
+                    "tx"
+                };
+                let (name, value) = parse_hist_bucket(direction, nla.value())?;
+                stats.insert(name, value);
+            }
+            _ => (),
+        }
+    }
+    let (names, group): (&[&str], _) = match group_id {
+        Some(ETHTOOL_STATS_ETH_PHY) => {
+            (&STATS_ETH_PHY_NAMES, &mut info.eth_phy)
+        }
+        Some(ETHTOOL_STATS_ETH_MAC) => {
+            (&STATS_ETH_MAC_NAMES, &mut info.eth_mac)
+        }
+        Some(ETHTOOL_STATS_ETH_CTRL) => {
+            (&STATS_ETH_CTRL_NAMES, &mut info.eth_ctrl)
+        }
+        Some(ETHTOOL_STATS_RMON) => (&STATS_RMON_NAMES, &mut info.rmon),
+        _ => {
+            log::debug!("Unsupported ethtool stats group {:?}", group_id);
+            return Ok(());
+        }
+    };
+    for (index, value) in raw_stats {
+        let name = match names.get(index) {
+            Some(n) => n.to_string(),
+            None => format!("unknown_{index}"),
+        };
+        stats.insert(name, value);
+    }
+    *group = Some(stats);
+    Ok(())
+}
+
+// Use the same name as `ethtool -S --groups rmon`
+fn parse_hist_bucket(
+    direction: &str,
+    data: &[u8],
+) -> Result<(String, u64), NisporError> {
+    let mut low = 0;
+    let mut high = 0;
+    let mut value = 0;
+    for nla in NlasIterator::new(data).flatten() {
+        match nla.kind() {
+            ETHTOOL_A_STATS_GRP_HIST_BKT_LOW => {
+                low = parse_as_u32(nla.value())?
+            }
+            ETHTOOL_A_STATS_GRP_HIST_BKT_HI => {
+                high = parse_as_u32(nla.value())?
+            }
+            ETHTOOL_A_STATS_GRP_HIST_VAL => value = parse_as_u64(nla.value())?,
+            _ => (),
+        }
+    }
+    Ok((
+        format!("{direction}-etherStatsPkts{low}to{high}Octets"),
+        value,
+    ))
+}
+
+// The driver specific statistics have no netlink equivalent.
+const ETHTOOL_GSTRINGS: u32 = 0x1b;
+const ETHTOOL_GSTATS: u32 = 0x1d;
+const ETHTOOL_GSSET_INFO: u32 = 0x37;
+const ETH_SS_STATS: u32 = 1;
+const ETH_GSTRING_LEN: usize = 32;
+
+// Return None if driver has no statistics.
+fn get_driver_stats(
+    iface_name: &str,
+) -> Result<Option<BTreeMap<String, u64>>, NisporError> {
+    // struct ethtool_sset_info with single u32 in `data`
+    let mut sset_info = vec![0u8; 20];
+    sset_info[0..4].copy_from_slice(&ETHTOOL_GSSET_INFO.to_ne_bytes());
+    sset_info[8..16].copy_from_slice(&(1u64 << ETH_SS_STATS).to_ne_bytes());
+    ethtool_ioctl(iface_name, &mut sset_info)
+        .map_err(|e| ethtool_ioctl_error(iface_name, e))?;
+    if parse_as_u64(&sset_info[8..16])? == 0 {
+        return Ok(None);
+    }
+    let count = parse_as_u32(&sset_info[16..20])? as usize;
+    if count == 0 {
+        return Ok(None);
+    }
+
+    // The statistics count might change after ETHTOOL_GSSET_INFO. Newer
+    // kernels reply with 0 count on mismatch, while older kernels ignore the
+    // requested count and fill in their current one, hence the headroom.
+    let capacity = count * 2;
+
+    // struct ethtool_gstrings
+    let mut strings = vec![0u8; 12 + capacity * ETH_GSTRING_LEN];
+    strings[0..4].copy_from_slice(&ETHTOOL_GSTRINGS.to_ne_bytes());
+    strings[4..8].copy_from_slice(&ETH_SS_STATS.to_ne_bytes());
+    strings[8..12].copy_from_slice(&(count as u32).to_ne_bytes());
+    ethtool_ioctl(iface_name, &mut strings)
+        .map_err(|e| ethtool_ioctl_error(iface_name, e))?;
+    check_driver_stats_count(
+        iface_name,
+        "strings",
+        count,
+        parse_as_u32(&strings[8..12])? as usize,
+    )?;
+
+    // struct ethtool_stats
+    let mut stats = vec![0u8; 8 + capacity * 8];
+    stats[0..4].copy_from_slice(&ETHTOOL_GSTATS.to_ne_bytes());
+    stats[4..8].copy_from_slice(&(count as u32).to_ne_bytes());
+    ethtool_ioctl(iface_name, &mut stats)
+        .map_err(|e| ethtool_ioctl_error(iface_name, e))?;
+    check_driver_stats_count(
+        iface_name,
+        "values",
+        count,
+        parse_as_u32(&stats[4..8])? as usize,
+    )?;
+
+    let mut ret = BTreeMap::new();
+    for i in 0..count {
+        let name =
+            &strings[12 + i * ETH_GSTRING_LEN..12 + (i + 1) * ETH_GSTRING_LEN];
+        ret.insert(
+            parse_as_string(name)?.trim().to_string(),
+            parse_as_u64(&stats[8 + i * 8..])?,
+        );
+    }
+    Ok(Some(ret))
+}
+
+fn check_driver_stats_count(
+    iface_name: &str,
+    kind: &str,
+    expected: usize,
+    count: usize,
+) -> Result<(), NisporError> {
+    if count == expected {
+        Ok(())
+    } else {
+        Err(NisporError::bug(format!(
+            "Driver statistics count of interface {iface_name} changed \
+            during query, expecting {expected} {kind}, got {count}"
+        )))
+    }
+}
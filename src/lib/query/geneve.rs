@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use netlink_packet_route::link::InfoData;
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use super::ip_tunnel::parse_tunnel_ip;
+use crate::{
+    netlink::{
+        parse_as_be16, parse_as_be32, parse_as_u32, parse_as_u8,
+        IFLA_GENEVE_COLLECT_METADATA, IFLA_GENEVE_DF, IFLA_GENEVE_ID,
+        IFLA_GENEVE_INNER_PROTO_INHERIT, IFLA_GENEVE_LABEL, IFLA_GENEVE_PORT,
+        IFLA_GENEVE_REMOTE, IFLA_GENEVE_REMOTE6, IFLA_GENEVE_TOS,
+        IFLA_GENEVE_TTL, IFLA_GENEVE_TTL_INHERIT, IFLA_GENEVE_UDP_CSUM,
+        IFLA_GENEVE_UDP_ZERO_CSUM6_RX, IFLA_GENEVE_UDP_ZERO_CSUM6_TX,
+    },
+    NisporError,
+};
+
+const GENEVE_DF_UNSET: u8 = 0;
+const GENEVE_DF_SET: u8 = 1;
+const GENEVE_DF_INHERIT: u8 = 2;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum GeneveDf {
+    Unset,
+    Set,
+    /// Copy the DF bit from the inner IPv4 header
+    Inherit,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for GeneveDf {
+    fn default() -> Self {
+        GeneveDf::Unknown
+    }
+}
+
+impl From<u8> for GeneveDf {
+    fn from(d: u8) -> Self {
+        match d {
+            GENEVE_DF_UNSET => Self::Unset,
+            GENEVE_DF_SET => Self::Set,
+            GENEVE_DF_INHERIT => Self::Inherit,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<GeneveDf> for u8 {
+    fn from(v: GeneveDf) -> u8 {
+        match v {
+            GeneveDf::Unset => GENEVE_DF_UNSET,
+            GeneveDf::Set => GENEVE_DF_SET,
+            GeneveDf::Inherit => GENEVE_DF_INHERIT,
+            GeneveDf::Other(d) => d,
+            GeneveDf::Unknown => {
+                log::warn!("Treating GeneveDf::Unknown as GeneveDf::Unset");
+                GENEVE_DF_UNSET
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct GeneveInfo {
+    pub vni: u32,
+    /// Not available in metadata mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    pub ttl: u8,
+    pub ttl_inherit: bool,
+    pub tos: u8,
+    pub df: GeneveDf,
+    pub dst_port: u16,
+    pub collect_metadata: bool,
+    pub udp_check_sum: bool,
+    pub udp6_zero_check_sum_tx: bool,
+    pub udp6_zero_check_sum_rx: bool,
+    /// IPv6 flow label
+    pub label: u32,
+    /// Use the protocol of inner packet instead of Ethernet
+    pub inner_proto_inherit: bool,
+}
+
+// The netlink-packet-route does not support GENEVE, hence we parse the raw
+// bytes.
+pub(crate) fn get_geneve_info(
+    data: &InfoData,
+) -> Result<Option<GeneveInfo>, NisporError> {
+    let raw = if let InfoData::Other(raw) = data {
+        raw
+    } else {
+        return Ok(None);
+    };
+    let mut info = GeneveInfo::default();
+    for nla in NlasIterator::new(raw.as_slice()) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            IFLA_GENEVE_ID => info.vni = parse_as_u32(d)?,
+            IFLA_GENEVE_REMOTE | IFLA_GENEVE_REMOTE6 => {
+                info.remote = parse_tunnel_ip(d)?
+            }
+            IFLA_GENEVE_TTL => info.ttl = parse_as_u8(d)?,
+            IFLA_GENEVE_TTL_INHERIT => info.ttl_inherit = parse_as_u8(d)? > 0,
+            IFLA_GENEVE_TOS => info.tos = parse_as_u8(d)?,
+            IFLA_GENEVE_DF => info.df = parse_as_u8(d)?.into(),
+            IFLA_GENEVE_PORT => info.dst_port = parse_as_be16(d)?,
+            IFLA_GENEVE_COLLECT_METADATA => info.collect_metadata = true,
+            IFLA_GENEVE_UDP_CSUM => info.udp_check_sum = parse_as_u8(d)? > 0,
+            IFLA_GENEVE_UDP_ZERO_CSUM6_TX => {
+                info.udp6_zero_check_sum_tx = parse_as_u8(d)? > 0
+            }
+            IFLA_GENEVE_UDP_ZERO_CSUM6_RX => {
+                info.udp6_zero_check_sum_rx = parse_as_u8(d)? > 0
+            }
+            IFLA_GENEVE_LABEL => info.label = parse_as_be32(d)?,
+            IFLA_GENEVE_INNER_PROTO_INHERIT => info.inner_proto_inherit = true,
+            _ => log::debug!("Unknown GENEVE info {:?}", nla),
+        }
+    }
+    Ok(Some(info))
+}
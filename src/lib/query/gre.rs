@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::InfoData;
+use netlink_packet_utils::{nla::NlasIterator, Emitable};
+use serde::{Deserialize, Serialize};
+
+use super::ip_tunnel::parse_tunnel_ip;
+use crate::{
+    netlink::{
+        parse_as_be16, parse_as_be32, parse_as_u16, parse_as_u32, parse_as_u8,
+        IFLA_GRE_COLLECT_METADATA, IFLA_GRE_ENCAP_DPORT, IFLA_GRE_ENCAP_FLAGS,
+        IFLA_GRE_ENCAP_LIMIT, IFLA_GRE_ENCAP_SPORT, IFLA_GRE_ENCAP_TYPE,
+        IFLA_GRE_ERSPAN_DIR, IFLA_GRE_ERSPAN_HWID, IFLA_GRE_ERSPAN_INDEX,
+        IFLA_GRE_ERSPAN_VER, IFLA_GRE_FLOWINFO, IFLA_GRE_FWMARK,
+        IFLA_GRE_IFLAGS, IFLA_GRE_IGNORE_DF, IFLA_GRE_IKEY, IFLA_GRE_LINK,
+        IFLA_GRE_LOCAL, IFLA_GRE_OFLAGS, IFLA_GRE_OKEY, IFLA_GRE_PMTUDISC,
+        IFLA_GRE_REMOTE, IFLA_GRE_TOS, IFLA_GRE_TTL,
+    },
+    Iface, IfaceType, NisporError, TunnelEncapType,
+};
+
+const ERSPAN_DIR_INGRESS: u8 = 0;
+const ERSPAN_DIR_EGRESS: u8 = 1;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum GreErspanDir {
+    Ingress,
+    Egress,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for GreErspanDir {
+    fn default() -> Self {
+        GreErspanDir::Unknown
+    }
+}
+
+impl From<u8> for GreErspanDir {
+    fn from(d: u8) -> Self {
+        match d {
+            ERSPAN_DIR_INGRESS => Self::Ingress,
+            ERSPAN_DIR_EGRESS => Self::Egress,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<GreErspanDir> for u8 {
+    fn from(v: GreErspanDir) -> u8 {
+        match v {
+            GreErspanDir::Ingress => ERSPAN_DIR_INGRESS,
+            GreErspanDir::Egress => ERSPAN_DIR_EGRESS,
+            GreErspanDir::Other(d) => d,
+            GreErspanDir::Unknown => {
+                log::warn!(
+                    "Treating GreErspanDir::Unknown as GreErspanDir::Ingress"
+                );
+                ERSPAN_DIR_INGRESS
+            }
+        }
+    }
+}
+
+/// Shared by interface type `gre`, `gretap`, `ip6gre`, `ip6gretap`,
+/// `erspan` and `ip6erspan`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct GreInfo {
+    /// The interface tunnel packets are bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    pub ikey: u32,
+    pub okey: u32,
+    /// GRE header flags of incoming packets, e.g. 0x2000 for key, 0x1000
+    /// for sequence number and 0x8000 for checksum.
+    pub iflags: u16,
+    /// GRE header flags of outgoing packets.
+    pub oflags: u16,
+    pub ttl: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tos: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmtudisc: Option<bool>,
+    /// IPv6 only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encap_limit: Option<u8>,
+    /// IPv6 only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flowinfo: Option<u32>,
+    pub encap_type: TunnelEncapType,
+    pub encap_flags: u16,
+    pub encap_sport: u16,
+    pub encap_dport: u16,
+    pub collect_metadata: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_df: Option<bool>,
+    pub fwmark: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub erspan_ver: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub erspan_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub erspan_dir: Option<GreErspanDir>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub erspan_hwid: Option<u16>,
+}
+
+// The netlink-packet-route does not parse GRE attributes and does not
+// support ERSPAN at all, hence we parse the raw bytes.
+pub(crate) fn get_gre_info(
+    data: &InfoData,
+) -> Result<Option<GreInfo>, NisporError> {
+    match data {
+        InfoData::GreTun(_)
+        | InfoData::GreTap(_)
+        | InfoData::GreTun6(_)
+        | InfoData::GreTap6(_)
+        | InfoData::Other(_) => (),
+        _ => return Ok(None),
+    }
+    let mut raw = vec![0u8; data.buffer_len()];
+    data.emit(&mut raw);
+
+    let mut info = GreInfo::default();
+    for nla in NlasIterator::new(raw.as_slice()) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            IFLA_GRE_LINK => {
+                let link = parse_as_u32(d)?;
+                if link != 0 {
+                    info.link = Some(format!("{link}"));
+                }
+            }
+            IFLA_GRE_IFLAGS => info.iflags = parse_as_be16(d)?,
+            IFLA_GRE_OFLAGS => info.oflags = parse_as_be16(d)?,
+            IFLA_GRE_IKEY => info.ikey = parse_as_be32(d)?,
+            IFLA_GRE_OKEY => info.okey = parse_as_be32(d)?,
+            IFLA_GRE_LOCAL => info.local = parse_tunnel_ip(d)?,
+            IFLA_GRE_REMOTE => info.remote = parse_tunnel_ip(d)?,
+            IFLA_GRE_TTL => info.ttl = parse_as_u8(d)?,
+            IFLA_GRE_TOS => info.tos = Some(parse_as_u8(d)?),
+            IFLA_GRE_PMTUDISC => info.pmtudisc = Some(parse_as_u8(d)? > 0),
+            IFLA_GRE_ENCAP_LIMIT => info.encap_limit = Some(parse_as_u8(d)?),
+            IFLA_GRE_FLOWINFO => info.flowinfo = Some(parse_as_be32(d)?),
+            IFLA_GRE_ENCAP_TYPE => info.encap_type = parse_as_u16(d)?.into(),
+            IFLA_GRE_ENCAP_FLAGS => info.encap_flags = parse_as_u16(d)?,
+            IFLA_GRE_ENCAP_SPORT => info.encap_sport = parse_as_be16(d)?,
+            IFLA_GRE_ENCAP_DPORT => info.encap_dport = parse_as_be16(d)?,
+            IFLA_GRE_COLLECT_METADATA => info.collect_metadata = true,
+            IFLA_GRE_IGNORE_DF => info.ignore_df = Some(parse_as_u8(d)? > 0),
+            IFLA_GRE_FWMARK => info.fwmark = parse_as_u32(d)?,
+            IFLA_GRE_ERSPAN_INDEX => info.erspan_index = Some(parse_as_u32(d)?),
+            IFLA_GRE_ERSPAN_VER => info.erspan_ver = Some(parse_as_u8(d)?),
+            IFLA_GRE_ERSPAN_DIR => {
+                info.erspan_dir = Some(parse_as_u8(d)?.into())
+            }
+            IFLA_GRE_ERSPAN_HWID => info.erspan_hwid = Some(parse_as_u16(d)?),
+            _ => log::debug!("Unknown GRE info {:?}", nla),
+        }
+    }
+    Ok(Some(info))
+}
+
+pub(crate) fn is_gre_iface_type(iface_type: &IfaceType) -> bool {
+    matches!(
+        iface_type,
+        IfaceType::Gre
+            | IfaceType::GreTap
+            | IfaceType::Ip6Gre
+            | IfaceType::Ip6GreTap
+            | IfaceType::Erspan
+            | IfaceType::Ip6Erspan
+    )
+}
+
+pub(crate) fn gre_iface_tidy_up(iface_states: &mut HashMap<String, Iface>) {
+    convert_link_index_to_name(iface_states);
+}
+
+fn convert_link_index_to_name(iface_states: &mut HashMap<String, Iface>) {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+    for iface in iface_states.values_mut() {
+        if !is_gre_iface_type(&iface.iface_type) {
+            continue;
+        }
+        if let Some(ref mut link) =
+            iface.gre.as_mut().and_then(|i| i.link.as_mut())
+        {
+            if let Some(link_iface_name) = index_to_name.get(link.as_str()) {
+                link.clone_from(link_iface_name);
+            }
+        }
+    }
+}
@@ -2,10 +2,14 @@
 use std::collections::HashMap;
 
 use netlink_packet_route::link::{InfoData, InfoHsr};
+use netlink_packet_utils::nla::Nla;
 use serde::{Deserialize, Serialize};
 
 use crate::mac::{parse_as_mac, ETH_ALEN};
-use crate::{Iface, IfaceType};
+use crate::{
+    netlink::{parse_as_u32, IFLA_HSR_INTERLINK},
+    Iface, IfaceType,
+};
 
 const HSR_PROTOCOL_HSR: u8 = 0;
 const HSR_PROTOCOL_PRP: u8 = 1;
@@ -36,48 +40,80 @@ impl From<u8> for HsrProtocol {
     }
 }
 
+impl From<HsrProtocol> for u8 {
+    fn from(v: HsrProtocol) -> u8 {
+        match v {
+            HsrProtocol::Hsr => HSR_PROTOCOL_HSR,
+            HsrProtocol::Prp => HSR_PROTOCOL_PRP,
+            HsrProtocol::Other(d) => d,
+            HsrProtocol::Unknown => {
+                log::warn!("Treating HsrProtocol::Unknown as HsrProtocol::Hsr");
+                HSR_PROTOCOL_HSR
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct HsrInfo {
     pub port1: Option<String>,
     pub port2: Option<String>,
+    /// Interlink port of HSR-SAN or PRP-SAN RedBox
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interlink: Option<String>,
     pub supervision_addr: String,
     pub seq_nr: u16,
+    /// The last byte of supervision address.
     pub multicast_spec: u8,
+    /// Kernel does not report HSR version, always 0.
     pub version: u8,
     pub protocol: HsrProtocol,
     #[serde(skip_serializing)]
     _port1_ifindex: u32,
     #[serde(skip_serializing)]
     _port2_ifindex: u32,
+    #[serde(skip_serializing)]
+    _interlink_ifindex: u32,
 }
 
 pub(crate) fn get_hsr_info(data: &InfoData) -> Option<HsrInfo> {
     if let InfoData::Hsr(infos) = data {
         let mut hsr_info = HsrInfo::default();
         for info in infos {
-            match *info {
+            match info {
                 InfoHsr::Port1(d) => {
-                    hsr_info._port1_ifindex = d;
+                    hsr_info._port1_ifindex = *d;
                 }
                 InfoHsr::Port2(d) => {
-                    hsr_info._port2_ifindex = d;
+                    hsr_info._port2_ifindex = *d;
                 }
                 InfoHsr::SupervisionAddr(d) => {
                     hsr_info.supervision_addr =
-                        parse_as_mac(ETH_ALEN, &d).unwrap_or_default();
+                        parse_as_mac(ETH_ALEN, d).unwrap_or_default();
+                    // Kernel does not report IFLA_HSR_MULTICAST_SPEC, but it
+                    // is the last byte of supervision address.
+                    if let Some(v) = d.last() {
+                        hsr_info.multicast_spec = *v;
+                    }
                 }
                 InfoHsr::SeqNr(d) => {
-                    hsr_info.seq_nr = d;
+                    hsr_info.seq_nr = *d;
                 }
                 InfoHsr::MulticastSpec(d) => {
-                    hsr_info.multicast_spec = d;
+                    hsr_info.multicast_spec = *d;
                 }
                 InfoHsr::Version(d) => {
-                    hsr_info.version = d;
+                    hsr_info.version = *d;
                 }
                 InfoHsr::Protocol(d) => {
-                    hsr_info.protocol = u8::from(d).into();
+                    hsr_info.protocol = u8::from(*d).into();
+                }
+                InfoHsr::Other(nla) if nla.kind() == IFLA_HSR_INTERLINK => {
+                    let mut value = vec![0u8; nla.value_len()];
+                    nla.emit_value(&mut value);
+                    hsr_info._interlink_ifindex =
+                        parse_as_u32(&value).unwrap_or_default();
                 }
                 _ => {
                     log::warn!("Unknown HSR info {:?}", info);
@@ -114,6 +150,11 @@ fn fill_port_iface_names(iface_states: &mut HashMap<String, Iface>) {
             {
                 hsr_info.port2 = Some(port2_iface_name.to_string());
             }
+            if let Some(interlink_iface_name) =
+                index_to_name.get(&hsr_info._interlink_ifindex)
+            {
+                hsr_info.interlink = Some(interlink_iface_name.to_string());
+            }
         }
     }
 }
@@ -13,13 +13,17 @@ use super::{
     super::mac::parse_as_mac,
     bond::{get_bond_info, get_bond_subordinate_info},
     bridge::{get_bridge_info, get_bridge_port_info, parse_bridge_vlan_info},
+    geneve::get_geneve_info,
+    gre::get_gre_info,
     hsr::get_hsr_info,
     ip::fill_af_spec_inet_info,
+    ip_tunnel::get_ip_tunnel_info,
+    ip_vlan::get_ip_vlan_info,
     ipoib::get_ipoib_info,
     mac_vlan::get_mac_vlan_info,
     mac_vtap::get_mac_vtap_info,
     macsec::get_macsec_info,
-    sriov::get_sriov_info,
+    sriov::{get_sriov_info, parse_vf_representor_port_name},
     tun::get_tun_info,
     vlan::get_vlan_info,
     vrf::{get_vrf_info, get_vrf_subordinate_info},
@@ -28,10 +32,11 @@ use super::{
 };
 use crate::{
     BondInfo, BondSubordinateInfo, BridgeInfo, BridgePortInfo, BridgeVlanEntry,
-    EthtoolInfo, HsrInfo, IpoibInfo, Ipv4Info, Ipv6Info, MacSecInfo,
-    MacVlanInfo, MacVtapInfo, MptcpAddress, NisporError, SriovInfo, TunInfo,
-    VethInfo, VfInfo, VlanInfo, VrfInfo, VrfSubordinateInfo, VxlanInfo,
-    XfrmInfo,
+    EthtoolInfo, GeneveInfo, GreInfo, HsrInfo, IpTunnelInfo, IpVlanInfo,
+    IpoibInfo, Ipv4Info, Ipv6Info, MacSecInfo, MacVlanInfo, MacVtapInfo,
+    MptcpAddress, NisporError, SriovInfo, TunInfo, VethInfo, VfInfo,
+    VfRepresentorInfo, VlanInfo, VrfInfo, VrfSubordinateInfo, VxlanInfo,
+    WireguardInfo, XfrmInfo,
 };
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -57,6 +62,28 @@ pub enum IfaceType {
     Hsr,
     Unknown,
     Xfrm,
+    Ifb,
+    Nlmon,
+    IpVlan,
+    IpVtap,
+    Gre,
+    #[serde(rename = "gretap")]
+    GreTap,
+    #[serde(rename = "ip6gre")]
+    Ip6Gre,
+    #[serde(rename = "ip6gretap")]
+    Ip6GreTap,
+    Erspan,
+    #[serde(rename = "ip6erspan")]
+    Ip6Erspan,
+    #[serde(rename = "ipip")]
+    IpIp,
+    Sit,
+    #[serde(rename = "ip6tnl")]
+    Ip6Tnl,
+    Geneve,
+    Wireguard,
+    Vcan,
     Other(String),
 }
 
@@ -91,6 +118,22 @@ impl std::fmt::Display for IfaceType {
                 Self::Hsr => "hsr",
                 Self::Unknown => "unknown",
                 Self::Xfrm => "xfrm",
+                Self::Ifb => "ifb",
+                Self::Nlmon => "nlmon",
+                Self::IpVlan => "ipvlan",
+                Self::IpVtap => "ipvtap",
+                Self::Gre => "gre",
+                Self::GreTap => "gretap",
+                Self::Ip6Gre => "ip6gre",
+                Self::Ip6GreTap => "ip6gretap",
+                Self::Erspan => "erspan",
+                Self::Ip6Erspan => "ip6erspan",
+                Self::IpIp => "ipip",
+                Self::Sit => "sit",
+                Self::Ip6Tnl => "ip6tnl",
+                Self::Geneve => "geneve",
+                Self::Wireguard => "wireguard",
+                Self::Vcan => "vcan",
                 Self::Other(s) => s,
             }
         )
@@ -279,10 +322,34 @@ pub struct Iface {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mac_vtap: Option<MacVtapInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_vlan: Option<IpVlanInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_vtap: Option<IpVlanInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gre: Option<GreInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_tunnel: Option<IpTunnelInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geneve: Option<GeneveInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wireguard: Option<WireguardInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sriov: Option<SriovInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sriov_vf: Option<VfInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub sriov_vf_representor: Option<VfRepresentorInfo>,
+    /// Physical port name, e.g. `p0` for uplink and `pf0vf1` for VF
+    /// representor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phys_port_name: Option<String>,
+    /// Hex string of switch ID shared by ports of the same switch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phys_switch_id: Option<String>,
+    /// Devlink port handle in the format of `<bus>/<device>/<port_index>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devlink_port: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ipoib: Option<IpoibInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mptcp: Option<Vec<MptcpAddress>>,
@@ -369,8 +436,24 @@ pub(crate) fn parse_nl_msg_to_iface(
                         InfoKind::MacSec => IfaceType::MacSec,
                         InfoKind::Hsr => IfaceType::Hsr,
                         InfoKind::Xfrm => IfaceType::Xfrm,
+                        InfoKind::Ifb => IfaceType::Ifb,
+                        InfoKind::Nlmon => IfaceType::Nlmon,
+                        InfoKind::IpVlan => IfaceType::IpVlan,
+                        InfoKind::GreTun => IfaceType::Gre,
+                        InfoKind::GreTap => IfaceType::GreTap,
+                        InfoKind::GreTun6 => IfaceType::Ip6Gre,
+                        InfoKind::GreTap6 => IfaceType::Ip6GreTap,
+                        InfoKind::IpTun => IfaceType::IpIp,
+                        InfoKind::SitTun => IfaceType::Sit,
+                        InfoKind::Wireguard => IfaceType::Wireguard,
                         InfoKind::Other(s) => match s.as_ref() {
                             "openvswitch" => IfaceType::OpenvSwitch,
+                            "ipvtap" => IfaceType::IpVtap,
+                            "erspan" => IfaceType::Erspan,
+                            "ip6erspan" => IfaceType::Ip6Erspan,
+                            "ip6tnl" => IfaceType::Ip6Tnl,
+                            "geneve" => IfaceType::Geneve,
+                            "vcan" => IfaceType::Vcan,
                             _ => IfaceType::Other(s.to_lowercase()),
                         },
                         _ => IfaceType::Other(
@@ -428,6 +511,28 @@ pub(crate) fn parse_nl_msg_to_iface(
                         IfaceType::Xfrm => {
                             iface_state.xfrm = get_xfrm_info(d);
                         }
+                        IfaceType::IpVlan => {
+                            iface_state.ip_vlan = get_ip_vlan_info(d)?
+                        }
+                        IfaceType::IpVtap => {
+                            iface_state.ip_vtap = get_ip_vlan_info(d)?
+                        }
+                        IfaceType::Gre
+                        | IfaceType::GreTap
+                        | IfaceType::Ip6Gre
+                        | IfaceType::Ip6GreTap
+                        | IfaceType::Erspan
+                        | IfaceType::Ip6Erspan => {
+                            iface_state.gre = get_gre_info(d)?
+                        }
+                        IfaceType::IpIp
+                        | IfaceType::Sit
+                        | IfaceType::Ip6Tnl => {
+                            iface_state.ip_tunnel = get_ip_tunnel_info(d)?
+                        }
+                        IfaceType::Geneve => {
+                            iface_state.geneve = get_geneve_info(d)?
+                        }
                         _ => log::warn!(
                             "Unhandled IFLA_INFO_DATA for iface type {:?}",
                             iface_state.iface_type
@@ -494,6 +599,20 @@ pub(crate) fn parse_nl_msg_to_iface(
             {
                 iface_state.sriov = Some(info);
             }
+        } else if let LinkAttribute::PhysPortName(name) = nla {
+            iface_state.sriov_vf_representor =
+                parse_vf_representor_port_name(name).map(|(pf, vf)| {
+                    VfRepresentorInfo {
+                        pf_number: pf,
+                        vf_id: vf,
+                        ..Default::default()
+                    }
+                });
+            iface_state.phys_port_name = Some(name.to_string());
+        } else if let LinkAttribute::PhysSwitchId(id) = nla {
+            iface_state.phys_switch_id = Some(
+                id.id[..id.len].iter().map(|b| format!("{b:02x}")).collect(),
+            );
         } else if let LinkAttribute::NetnsId(id) = nla {
             iface_state.link_netnsid = Some(*id);
         } else if let LinkAttribute::AfSpecUnspec(nlas) = nla {
@@ -534,6 +653,16 @@ pub(crate) fn parse_nl_msg_to_iface(
                     mac_vtap_info.base_iface = format!("{iface_index}");
                 }
             }
+            IfaceType::IpVlan => {
+                if let Some(ref mut ip_vlan_info) = iface_state.ip_vlan {
+                    ip_vlan_info.base_iface = format!("{iface_index}");
+                }
+            }
+            IfaceType::IpVtap => {
+                if let Some(ref mut ip_vtap_info) = iface_state.ip_vtap {
+                    ip_vtap_info.base_iface = format!("{iface_index}");
+                }
+            }
             _ => (),
         }
     }
@@ -9,21 +9,25 @@ use rtnetlink::new_connection;
 use super::{
     super::netlink::fill_ip_addr,
     bond::bond_iface_tidy_up,
-    bridge::bridge_iface_tidy_up,
+    bridge::{bridge_iface_tidy_up, fill_bridge_vlan_opts},
     ethtool::get_ethtool_infos,
+    gre::gre_iface_tidy_up,
     hsr::hsr_iface_tidy_up,
     iface::{
         fill_bridge_vlan_info, parse_nl_msg_to_iface,
         parse_nl_msg_to_name_and_index,
     },
+    ip_tunnel::ip_tunnel_iface_tidy_up,
+    ip_vlan::ip_vlan_iface_tidy_up,
     ipoib::ipoib_iface_tidy_up,
     mac_vlan::mac_vlan_iface_tidy_up,
-    macsec::macsec_iface_tidy_up,
-    sriov::sriov_vf_iface_tidy_up,
+    macsec::{fill_macsec_sa_info, macsec_iface_tidy_up},
+    sriov::{fill_sriov_eswitch_mode, sriov_vf_iface_tidy_up},
     veth::veth_iface_tidy_up,
     vlan::vlan_iface_tidy_up,
     vrf::vrf_iface_tidy_up,
     vxlan::vxlan_iface_tidy_up,
+    wireguard::fill_wireguard_info,
     xfrm::xfrm_iface_tidy_up,
 };
 use crate::{EthtoolInfo, Iface, NetStateIfaceFilter, NisporError};
@@ -63,10 +67,10 @@ pub(crate) async fn get_ifaces(
         .and_then(|name| iface_states.get(name))
         .map(|i| i.index);
 
-    if filter.iface_name.is_some() && iface_index.is_none() {
+    if let (Some(iface_name), None) = (filter.iface_name.as_ref(), iface_index)
+    {
         return Err(NisporError::invalid_argument(format!(
-            "Interface {} not found",
-            filter.iface_name.as_ref().unwrap()
+            "Interface {iface_name} not found"
         )));
     }
 
@@ -100,11 +104,28 @@ pub(crate) async fn get_ifaces(
         while let Some(nl_msg) = br_vlan_links.try_next().await? {
             fill_bridge_vlan_info(&mut iface_states, &nl_msg)?;
         }
+
+        if let Err(e) = fill_bridge_vlan_opts(&mut iface_states, iface_index) {
+            // Per-VLAN options are only supported by Linux kernel 5.13+
+            log::warn!("Failed to query bridge VLAN options: {}", e);
+        }
+    }
+
+    if let Err(e) = fill_macsec_sa_info(&mut iface_states) {
+        log::warn!("Failed to query MACsec SC and SA: {}", e);
+    }
+
+    if let Err(e) = fill_wireguard_info(&mut iface_states) {
+        log::warn!("Failed to query WireGuard device: {}", e);
+    }
+
+    if filter.include_sriov_vf_info {
+        fill_sriov_eswitch_mode(&mut iface_states);
     }
 
     if filter.include_ethtool {
         // TODO: Apply interface filter to ethtool dump also
-        match get_ethtool_infos().await {
+        match get_ethtool_infos(filter.include_ethtool_stats).await {
             Ok(mut ethtool_infos) => {
                 ifaces_merge_ethool_infos(
                     &mut iface_states,
@@ -131,6 +152,9 @@ fn tidy_up(iface_states: &mut HashMap<String, Iface>) {
     veth_iface_tidy_up(iface_states);
     vrf_iface_tidy_up(iface_states);
     mac_vlan_iface_tidy_up(iface_states);
+    ip_vlan_iface_tidy_up(iface_states);
+    gre_iface_tidy_up(iface_states);
+    ip_tunnel_iface_tidy_up(iface_states);
     macsec_iface_tidy_up(iface_states);
     hsr_iface_tidy_up(iface_states);
     ipoib_iface_tidy_up(iface_states);
@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+
+use netlink_packet_route::link::InfoData;
+use netlink_packet_utils::{nla::NlasIterator, Emitable};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netlink::{
+        parse_as_be16, parse_as_be32, parse_as_ipv4, parse_as_ipv6,
+        parse_as_u16, parse_as_u32, parse_as_u8, IFLA_IPTUN_6RD_PREFIX,
+        IFLA_IPTUN_6RD_PREFIXLEN, IFLA_IPTUN_6RD_RELAY_PREFIX,
+        IFLA_IPTUN_6RD_RELAY_PREFIXLEN, IFLA_IPTUN_COLLECT_METADATA,
+        IFLA_IPTUN_ENCAP_DPORT, IFLA_IPTUN_ENCAP_FLAGS, IFLA_IPTUN_ENCAP_LIMIT,
+        IFLA_IPTUN_ENCAP_SPORT, IFLA_IPTUN_ENCAP_TYPE, IFLA_IPTUN_FLAGS,
+        IFLA_IPTUN_FLOWINFO, IFLA_IPTUN_FWMARK, IFLA_IPTUN_LINK,
+        IFLA_IPTUN_LOCAL, IFLA_IPTUN_PMTUDISC, IFLA_IPTUN_PROTO,
+        IFLA_IPTUN_REMOTE, IFLA_IPTUN_TOS, IFLA_IPTUN_TTL,
+        IP6_FLOWINFO_FLOWLABEL_MASK, IP6_FLOWINFO_TCLASS_MASK,
+        IP6_FLOWINFO_TCLASS_SHIFT,
+    },
+    Iface, IfaceType, NisporError,
+};
+
+const TUNNEL_ENCAP_NONE: u16 = 0;
+const TUNNEL_ENCAP_FOU: u16 = 1;
+const TUNNEL_ENCAP_GUE: u16 = 2;
+const TUNNEL_ENCAP_MPLS: u16 = 3;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum TunnelEncapType {
+    None,
+    /// Foo over UDP
+    Fou,
+    /// Generic UDP Encapsulation
+    Gue,
+    Mpls,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for TunnelEncapType {
+    fn default() -> Self {
+        TunnelEncapType::Unknown
+    }
+}
+
+impl From<u16> for TunnelEncapType {
+    fn from(d: u16) -> Self {
+        match d {
+            TUNNEL_ENCAP_NONE => Self::None,
+            TUNNEL_ENCAP_FOU => Self::Fou,
+            TUNNEL_ENCAP_GUE => Self::Gue,
+            TUNNEL_ENCAP_MPLS => Self::Mpls,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TunnelEncapType> for u16 {
+    fn from(v: TunnelEncapType) -> u16 {
+        match v {
+            TunnelEncapType::None => TUNNEL_ENCAP_NONE,
+            TunnelEncapType::Fou => TUNNEL_ENCAP_FOU,
+            TunnelEncapType::Gue => TUNNEL_ENCAP_GUE,
+            TunnelEncapType::Mpls => TUNNEL_ENCAP_MPLS,
+            TunnelEncapType::Other(d) => d,
+            TunnelEncapType::Unknown => {
+                log::warn!(
+                    "Treating TunnelEncapType::Unknown as TunnelEncapType::None"
+                );
+                TUNNEL_ENCAP_NONE
+            }
+        }
+    }
+}
+
+/// Shared by interface type `ipip`, `sit` and `ip6tnl`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpTunnelInfo {
+    /// The interface tunnel packets are bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    pub ttl: u8,
+    /// ipip and sit only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tos: Option<u8>,
+    /// ip6tnl only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tclass: Option<u8>,
+    /// ip6tnl only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flowlabel: Option<u32>,
+    /// ip6tnl only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encap_limit: Option<u8>,
+    /// The `IP6_TNL_F_*` flags of ip6tnl or `SIT_ISATAP` flag of sit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<u32>,
+    /// Inner IP protocol number, 0 means any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proto: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmtudisc: Option<bool>,
+    /// IPv6 rapid deployment prefix of sit, e.g. `2001:db8::/32`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip6rd_prefix: Option<String>,
+    /// IPv6 rapid deployment relay prefix of sit, e.g. `192.0.2.0/24`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip6rd_relay_prefix: Option<String>,
+    pub encap_type: TunnelEncapType,
+    pub encap_flags: u16,
+    pub encap_sport: u16,
+    pub encap_dport: u16,
+    pub collect_metadata: bool,
+    pub fwmark: u32,
+}
+
+// The netlink-packet-route does not parse IP tunnel attributes and does not
+// support ip6tnl at all, hence we parse the raw bytes.
+pub(crate) fn get_ip_tunnel_info(
+    data: &InfoData,
+) -> Result<Option<IpTunnelInfo>, NisporError> {
+    match data {
+        InfoData::SitTun(_) | InfoData::Other(_) => (),
+        _ => return Ok(None),
+    }
+    let mut raw = vec![0u8; data.buffer_len()];
+    data.emit(&mut raw);
+
+    let mut info = IpTunnelInfo::default();
+    let mut ip6rd_prefix = None;
+    let mut ip6rd_prefix_len = 0;
+    let mut ip6rd_relay_prefix = None;
+    let mut ip6rd_relay_prefix_len = 0;
+    for nla in NlasIterator::new(raw.as_slice()) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            IFLA_IPTUN_LINK => {
+                let link = parse_as_u32(d)?;
+                if link != 0 {
+                    info.link = Some(format!("{link}"));
+                }
+            }
+            IFLA_IPTUN_LOCAL => info.local = parse_tunnel_ip(d)?,
+            IFLA_IPTUN_REMOTE => info.remote = parse_tunnel_ip(d)?,
+            IFLA_IPTUN_TTL => info.ttl = parse_as_u8(d)?,
+            IFLA_IPTUN_TOS => info.tos = Some(parse_as_u8(d)?),
+            IFLA_IPTUN_ENCAP_LIMIT => info.encap_limit = Some(parse_as_u8(d)?),
+            IFLA_IPTUN_FLOWINFO => {
+                let flowinfo = parse_as_be32(d)?;
+                info.tclass = Some(
+                    ((flowinfo & IP6_FLOWINFO_TCLASS_MASK)
+                        >> IP6_FLOWINFO_TCLASS_SHIFT) as u8,
+                );
+                info.flowlabel = Some(flowinfo & IP6_FLOWINFO_FLOWLABEL_MASK);
+            }
+            // The sit is using 16 bits flags
+            IFLA_IPTUN_FLAGS => {
+                info.flags = Some(if d.len() == 2 {
+                    parse_as_u16(d)?.into()
+                } else {
+                    parse_as_u32(d)?
+                })
+            }
+            IFLA_IPTUN_PROTO => info.proto = Some(parse_as_u8(d)?),
+            IFLA_IPTUN_PMTUDISC => info.pmtudisc = Some(parse_as_u8(d)? > 0),
+            IFLA_IPTUN_6RD_PREFIX => ip6rd_prefix = Some(parse_as_ipv6(d)?),
+            IFLA_IPTUN_6RD_PREFIXLEN => ip6rd_prefix_len = parse_as_u16(d)?,
+            IFLA_IPTUN_6RD_RELAY_PREFIX => {
+                ip6rd_relay_prefix = Some(Ipv4Addr::from(parse_as_be32(d)?))
+            }
+            IFLA_IPTUN_6RD_RELAY_PREFIXLEN => {
+                ip6rd_relay_prefix_len = parse_as_u16(d)?
+            }
+            IFLA_IPTUN_ENCAP_TYPE => info.encap_type = parse_as_u16(d)?.into(),
+            IFLA_IPTUN_ENCAP_FLAGS => info.encap_flags = parse_as_u16(d)?,
+            IFLA_IPTUN_ENCAP_SPORT => info.encap_sport = parse_as_be16(d)?,
+            IFLA_IPTUN_ENCAP_DPORT => info.encap_dport = parse_as_be16(d)?,
+            IFLA_IPTUN_COLLECT_METADATA => info.collect_metadata = true,
+            IFLA_IPTUN_FWMARK => info.fwmark = parse_as_u32(d)?,
+            _ => log::debug!("Unknown IP tunnel info {:?}", nla),
+        }
+    }
+    info.ip6rd_prefix = ip6rd_prefix.map(|p| format!("{p}/{ip6rd_prefix_len}"));
+    info.ip6rd_relay_prefix =
+        ip6rd_relay_prefix.map(|p| format!("{p}/{ip6rd_relay_prefix_len}"));
+    Ok(Some(info))
+}
+
+// Unspecified address means any.
+pub(crate) fn parse_tunnel_ip(
+    data: &[u8],
+) -> Result<Option<String>, NisporError> {
+    let ip = if data.len() == 16 {
+        IpAddr::V6(parse_as_ipv6(data)?)
+    } else {
+        IpAddr::V4(parse_as_ipv4(data)?)
+    };
+    Ok(if ip.is_unspecified() {
+        None
+    } else {
+        Some(ip.to_string())
+    })
+}
+
+pub(crate) fn ip_tunnel_iface_tidy_up(
+    iface_states: &mut HashMap<String, Iface>,
+) {
+    convert_link_index_to_name(iface_states);
+}
+
+fn convert_link_index_to_name(iface_states: &mut HashMap<String, Iface>) {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+    for iface in iface_states.values_mut() {
+        if !matches!(
+            iface.iface_type,
+            IfaceType::IpIp | IfaceType::Sit | IfaceType::Ip6Tnl
+        ) {
+            continue;
+        }
+        if let Some(ref mut link) =
+            iface.ip_tunnel.as_mut().and_then(|i| i.link.as_mut())
+        {
+            if let Some(link_iface_name) = index_to_name.get(link.as_str()) {
+                link.clone_from(link_iface_name);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::link::{InfoData, InfoIpVlan};
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{netlink::parse_as_u16, Iface, IfaceType, NisporError};
+
+const IPVLAN_MODE_L2: u16 = 0;
+const IPVLAN_MODE_L3: u16 = 1;
+const IPVLAN_MODE_L3S: u16 = 2;
+
+const IPVLAN_F_PRIVATE: u16 = 0x01;
+const IPVLAN_F_VEPA: u16 = 0x02;
+
+const IFLA_IPVLAN_MODE: u16 = 1;
+const IFLA_IPVLAN_FLAGS: u16 = 2;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum IpVlanMode {
+    L2,
+    L3,
+    L3S,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for IpVlanMode {
+    fn default() -> Self {
+        IpVlanMode::Unknown
+    }
+}
+
+impl From<u16> for IpVlanMode {
+    fn from(d: u16) -> Self {
+        match d {
+            IPVLAN_MODE_L2 => Self::L2,
+            IPVLAN_MODE_L3 => Self::L3,
+            IPVLAN_MODE_L3S => Self::L3S,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<IpVlanMode> for u16 {
+    fn from(v: IpVlanMode) -> u16 {
+        match v {
+            IpVlanMode::L2 => IPVLAN_MODE_L2,
+            IpVlanMode::L3 => IPVLAN_MODE_L3,
+            IpVlanMode::L3S => IPVLAN_MODE_L3S,
+            IpVlanMode::Other(d) => d,
+            IpVlanMode::Unknown => {
+                log::warn!("Treating IpVlanMode::Unknown as IpVlanMode::L3");
+                IPVLAN_MODE_L3
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum IpVlanFlag {
+    /* talk to other ipvlans on the same base interface */
+    Bridge,
+    /* don't talk to other ipvlans */
+    Private,
+    /* talk to other ports through ext bridge */
+    Vepa,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for IpVlanFlag {
+    fn default() -> Self {
+        IpVlanFlag::Unknown
+    }
+}
+
+impl From<u16> for IpVlanFlag {
+    fn from(d: u16) -> Self {
+        match d {
+            0 => Self::Bridge,
+            IPVLAN_F_PRIVATE => Self::Private,
+            IPVLAN_F_VEPA => Self::Vepa,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<IpVlanFlag> for u16 {
+    fn from(v: IpVlanFlag) -> u16 {
+        match v {
+            IpVlanFlag::Bridge => 0,
+            IpVlanFlag::Private => IPVLAN_F_PRIVATE,
+            IpVlanFlag::Vepa => IPVLAN_F_VEPA,
+            IpVlanFlag::Other(d) => d,
+            IpVlanFlag::Unknown => {
+                log::warn!(
+                    "Treating IpVlanFlag::Unknown as IpVlanFlag::Bridge"
+                );
+                0
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpVlanInfo {
+    pub base_iface: String,
+    pub mode: IpVlanMode,
+    pub flags: IpVlanFlag,
+}
+
+pub(crate) fn get_ip_vlan_info(
+    data: &InfoData,
+) -> Result<Option<IpVlanInfo>, NisporError> {
+    let mut info = IpVlanInfo::default();
+    match data {
+        InfoData::IpVlan(nlas) => {
+            for nla in nlas {
+                match nla {
+                    InfoIpVlan::Mode(d) => info.mode = (*d).into(),
+                    InfoIpVlan::Flags(d) => info.flags = (*d).into(),
+                    _ => log::debug!("Unknown IP VLAN info {:?}", nla),
+                }
+            }
+            Ok(Some(info))
+        }
+        // The netlink-packet-route does not support IP VTAP, but it is
+        // sharing the same netlink attributes with IP VLAN.
+        InfoData::Other(raw) => {
+            for nla in NlasIterator::new(raw.as_slice()) {
+                let nla = nla?;
+                match nla.kind() {
+                    IFLA_IPVLAN_MODE => {
+                        info.mode = parse_as_u16(nla.value())?.into()
+                    }
+                    IFLA_IPVLAN_FLAGS => {
+                        info.flags = parse_as_u16(nla.value())?.into()
+                    }
+                    _ => log::debug!("Unknown IP VTAP info {:?}", nla),
+                }
+            }
+            Ok(Some(info))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn ip_vlan_iface_tidy_up(iface_states: &mut HashMap<String, Iface>) {
+    convert_base_iface_index_to_name(iface_states);
+}
+
+fn convert_base_iface_index_to_name(iface_states: &mut HashMap<String, Iface>) {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+    for iface in iface_states.values_mut() {
+        let info = match iface.iface_type {
+            IfaceType::IpVlan => iface.ip_vlan.as_mut(),
+            IfaceType::IpVtap => iface.ip_vtap.as_mut(),
+            _ => continue,
+        };
+        if let Some(info) = info {
+            if let Some(base_iface_name) = index_to_name.get(&info.base_iface) {
+                info.base_iface.clone_from(base_iface_name);
+            }
+        }
+    }
+}
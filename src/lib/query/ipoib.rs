@@ -38,6 +38,22 @@ impl From<u16> for IpoibMode {
     }
 }
 
+impl From<IpoibMode> for u16 {
+    fn from(v: IpoibMode) -> u16 {
+        match v {
+            IpoibMode::Datagram => IPOIB_MODE_DATAGRAM,
+            IpoibMode::Connected => IPOIB_MODE_CONNECTED,
+            IpoibMode::Other(d) => d,
+            IpoibMode::Unknown => {
+                log::warn!(
+                    "Treating IpoibMode::Unknown as IpoibMode::Datagram"
+                );
+                IPOIB_MODE_DATAGRAM
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct IpoibInfo {
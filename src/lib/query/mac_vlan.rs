@@ -54,6 +54,25 @@ impl From<u32> for MacVlanMode {
     }
 }
 
+impl From<MacVlanMode> for u32 {
+    fn from(v: MacVlanMode) -> u32 {
+        match v {
+            MacVlanMode::Private => MACVLAN_MODE_PRIVATE,
+            MacVlanMode::Vepa => MACVLAN_MODE_VEPA,
+            MacVlanMode::Bridge => MACVLAN_MODE_BRIDGE,
+            MacVlanMode::PassThrough => MACVLAN_MODE_PASSTHRU,
+            MacVlanMode::Source => MACVLAN_MODE_SOURCE,
+            MacVlanMode::Other(d) => d,
+            MacVlanMode::Unknown => {
+                log::warn!(
+                    "Treating MacVlanMode::Unknown as MacVlanMode::Vepa"
+                );
+                MACVLAN_MODE_VEPA
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct MacVlanInfo {
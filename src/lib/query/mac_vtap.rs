@@ -45,6 +45,20 @@ impl From<MacVlanMode> for MacVtapMode {
     }
 }
 
+impl From<MacVtapMode> for MacVlanMode {
+    fn from(d: MacVtapMode) -> Self {
+        match d {
+            MacVtapMode::Private => Self::Private,
+            MacVtapMode::Vepa => Self::Vepa,
+            MacVtapMode::Bridge => Self::Bridge,
+            MacVtapMode::PassThrough => Self::PassThrough,
+            MacVtapMode::Source => Self::Source,
+            MacVtapMode::Unknown => Self::Unknown,
+            MacVtapMode::Other(u32) => Self::Other(u32),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct MacVtapInfo {
@@ -66,6 +80,17 @@ impl From<MacVlanInfo> for MacVtapInfo {
     }
 }
 
+impl From<MacVtapInfo> for MacVlanInfo {
+    fn from(d: MacVtapInfo) -> Self {
+        Self {
+            base_iface: d.base_iface,
+            mode: MacVlanMode::from(d.mode),
+            flags: d.flags,
+            allowed_mac_addresses: d.allowed_mac_addresses,
+        }
+    }
+}
+
 pub(crate) fn get_mac_vtap_info(
     data: &InfoData,
 ) -> Result<Option<MacVtapInfo>, NisporError> {
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use netlink_packet_route::link::{InfoData, InfoMacSec};
 use serde::{Deserialize, Serialize};
 
-use crate::{Iface, IfaceType};
+use crate::{netlink::get_macsec_secys, Iface, IfaceType, NisporError};
 
 const MACSEC_VALIDATE_DISABLED: u8 = 0;
 const MACSEC_VALIDATE_CHECK: u8 = 1;
@@ -48,6 +48,24 @@ impl From<u8> for MacSecValidate {
     }
 }
 
+impl From<MacSecValidate> for u8 {
+    fn from(v: MacSecValidate) -> u8 {
+        match v {
+            MacSecValidate::Disabled => MACSEC_VALIDATE_DISABLED,
+            MacSecValidate::Check => MACSEC_VALIDATE_CHECK,
+            MacSecValidate::Strict => MACSEC_VALIDATE_STRICT,
+            MacSecValidate::Other(d) => d,
+            MacSecValidate::Unknown => {
+                log::warn!(
+                    "Treating MacSecValidate::Unknown as \
+                    MacSecValidate::Strict"
+                );
+                MACSEC_VALIDATE_STRICT
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
@@ -76,6 +94,23 @@ impl From<u8> for MacSecOffload {
     }
 }
 
+impl From<MacSecOffload> for u8 {
+    fn from(v: MacSecOffload) -> u8 {
+        match v {
+            MacSecOffload::Off => MACSEC_OFFLOAD_OFF,
+            MacSecOffload::Phy => MACSEC_OFFLOAD_PHY,
+            MacSecOffload::Mac => MACSEC_OFFLOAD_MAC,
+            MacSecOffload::Other(d) => d,
+            MacSecOffload::Unknown => {
+                log::warn!(
+                    "Treating MacSecOffload::Unknown as MacSecOffload::Off"
+                );
+                MACSEC_OFFLOAD_OFF
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
@@ -106,6 +141,47 @@ impl From<u64> for MacSecCipherId {
     }
 }
 
+impl From<MacSecCipherId> for u64 {
+    fn from(v: MacSecCipherId) -> u64 {
+        match v {
+            MacSecCipherId::GcmAes128 => MACSEC_CIPHER_ID_GCM_AES_128,
+            MacSecCipherId::GcmAes256 => MACSEC_CIPHER_ID_GCM_AES_256,
+            MacSecCipherId::GcmAesXpn128 => MACSEC_CIPHER_ID_GCM_AES_XPN_128,
+            MacSecCipherId::GcmAesXpn256 => MACSEC_CIPHER_ID_GCM_AES_XPN_256,
+            MacSecCipherId::Other(d) => d,
+        }
+    }
+}
+
+impl MacSecCipherId {
+    pub(crate) fn is_xpn(&self) -> bool {
+        matches!(self, Self::GcmAesXpn128 | Self::GcmAesXpn256)
+    }
+}
+
+/// MACsec Secure Association. The key is never included.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacSecSaInfo {
+    pub an: u8,
+    pub pn: u64,
+    pub active: bool,
+    /// Hex string of the 128 bits key identifier
+    pub key_id: String,
+    /// Short Secure Channel Identifier, only for XPN cipher.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssci: Option<u32>,
+}
+
+/// MACsec receive Secure Channel
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacSecRxScInfo {
+    pub sci: u64,
+    pub active: bool,
+    pub sa: Vec<MacSecSaInfo>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct MacSecInfo {
@@ -125,6 +201,10 @@ pub struct MacSecInfo {
     pub offload: MacSecOffload,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_iface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_sa: Option<Vec<MacSecSaInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rx_sc: Option<Vec<MacSecRxScInfo>>,
 }
 
 pub(crate) fn get_macsec_info(data: &InfoData) -> Option<MacSecInfo> {
@@ -209,3 +289,24 @@ fn convert_base_iface_index_to_name(iface_states: &mut HashMap<String, Iface>) {
         }
     }
 }
+
+pub(crate) fn fill_macsec_sa_info(
+    iface_states: &mut HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    if !iface_states
+        .values()
+        .any(|i| i.iface_type == IfaceType::MacSec)
+    {
+        return Ok(());
+    }
+    let mut secys = get_macsec_secys()?;
+    for iface in iface_states.values_mut() {
+        if let Some(macsec_info) = iface.macsec.as_mut() {
+            if let Some(secy) = secys.remove(&iface.index) {
+                macsec_info.tx_sa = Some(secy.tx_sa);
+                macsec_info.rx_sc = Some(secy.rx_sc);
+            }
+        }
+    }
+    Ok(())
+}
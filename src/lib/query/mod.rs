@@ -2,14 +2,22 @@
 
 mod bond;
 mod bridge;
+mod devlink;
+mod geneve;
+mod gre;
 mod hsr;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod mptcp;
 mod xfrm;
 // Disable `needless_pass_by_ref_mut` check due to upstream issue:
 // https://github.com/rust-netlink/ethtool/issues/12
 #[allow(clippy::needless_pass_by_ref_mut)]
 mod ethtool;
+mod ethtool_driver;
+mod ethtool_rss;
+mod ethtool_stats;
 mod iface;
 mod inter_ifaces;
 mod ipoib;
@@ -24,6 +32,7 @@ mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
+mod wireguard;
 
 pub use self::bond::{
     BondAdInfo, BondAdSelect, BondAllSubordinatesActive, BondArpValidate,
@@ -33,13 +42,26 @@ pub use self::bond::{
 };
 pub use self::bridge::{
     BridgeInfo, BridgePortInfo, BridgePortMulticastRouterType,
-    BridgePortStpState, BridgeStpState, BridgeVlanEntry, BridgeVlanProtocol,
+    BridgePortStpState, BridgeStpState, BridgeVlanEntry, BridgeVlanGlobalOpts,
+    BridgeVlanOpts, BridgeVlanProtocol,
+};
+pub use self::devlink::{
+    Devlink, DevlinkDevice, DevlinkEswitchEncapMode, DevlinkEswitchInlineMode,
+    DevlinkParam, DevlinkParamCmode, DevlinkParamData, DevlinkParamType,
+    DevlinkParamValue, DevlinkPort, DevlinkPortFlavour,
+    DevlinkPortFunctionOpstate, DevlinkPortFunctionState, DevlinkPortType,
 };
 pub use self::ethtool::{
-    EthtoolCoalesceInfo, EthtoolFeatureInfo, EthtoolInfo,
-    EthtoolLinkModeDuplex, EthtoolLinkModeInfo, EthtoolPauseInfo,
-    EthtoolRingInfo,
+    EthtoolChannelInfo, EthtoolCoalesceInfo, EthtoolEeeInfo,
+    EthtoolFeatureInfo, EthtoolFecInfo, EthtoolInfo, EthtoolLinkExtState,
+    EthtoolLinkModeDuplex, EthtoolLinkModeInfo, EthtoolLinkStateInfo,
+    EthtoolPauseInfo, EthtoolRingInfo, EthtoolTimestampingInfo, EthtoolWolInfo,
 };
+pub use self::ethtool_driver::EthtoolDriverInfo;
+pub use self::ethtool_rss::{EthtoolRssHashFunction, EthtoolRssInfo};
+pub use self::ethtool_stats::EthtoolStatsInfo;
+pub use self::geneve::{GeneveDf, GeneveInfo};
+pub use self::gre::{GreErspanDir, GreInfo};
 pub use self::hsr::{HsrInfo, HsrProtocol};
 pub use self::iface::{
     ControllerType, Iface, IfaceFlag, IfaceState, IfaceType,
@@ -47,11 +69,14 @@ pub use self::iface::{
 pub use self::ip::{
     IpFamily, Ipv4AddrInfo, Ipv4Info, Ipv6AddrFlag, Ipv6AddrInfo, Ipv6Info,
 };
+pub use self::ip_tunnel::{IpTunnelInfo, TunnelEncapType};
+pub use self::ip_vlan::{IpVlanFlag, IpVlanInfo, IpVlanMode};
 pub use self::ipoib::{IpoibInfo, IpoibMode};
 pub use self::mac_vlan::{MacVlanInfo, MacVlanMode};
 pub use self::mac_vtap::{MacVtapInfo, MacVtapMode};
 pub use self::macsec::{
-    MacSecCipherId, MacSecInfo, MacSecOffload, MacSecValidate,
+    MacSecCipherId, MacSecInfo, MacSecOffload, MacSecRxScInfo, MacSecSaInfo,
+    MacSecValidate,
 };
 pub use self::mptcp::{Mptcp, MptcpAddress, MptcpAddressFlag};
 pub use self::route::{
@@ -59,18 +84,30 @@ pub use self::route::{
     RouteScope, RouteType,
 };
 pub use self::route_rule::{RouteRule, RuleAction};
-pub use self::sriov::{SriovInfo, VfInfo, VfLinkState, VfState};
+pub use self::sriov::{
+    SriovEswitchMode, SriovInfo, VfInfo, VfLinkState, VfRepresentorInfo,
+    VfState,
+};
 pub use self::tun::{TunInfo, TunMode};
 pub use self::veth::VethInfo;
 pub use self::vlan::{VlanInfo, VlanProtocol};
 pub use self::vrf::{VrfInfo, VrfSubordinateInfo};
 pub use self::vxlan::VxlanInfo;
+pub use self::wireguard::{WireguardInfo, WireguardPeerInfo};
 pub use self::xfrm::XfrmInfo;
 
 pub(crate) use self::{
+    devlink::{get_devlink, merge_devlink_info},
+    ethtool_rss::{
+        rss_flow_type_from_name, rss_hash_field_names_to_bits,
+        set_rss_hash_fields, set_rss_indirection_table,
+    },
     inter_ifaces::{get_iface_name2index, get_ifaces},
     ip::{is_ipv6_addr, parse_ip_addr_str, parse_ip_net_addr_str},
     mptcp::{get_mptcp, merge_mptcp_info},
     route::get_routes,
     route_rule::get_route_rules,
 };
+
+#[cfg(test)]
+pub(crate) use self::ethtool_driver::parse_private_flags;
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     mac::{parse_as_mac, ETH_ALEN, INFINIBAND_ALEN},
+    netlink::devlink_get_eswitch_mode,
     Iface, IfaceType, NisporError, VlanProtocol,
 };
 
@@ -49,10 +50,69 @@ pub struct VfState {
     pub tx_dropped: u64,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SriovEswitchMode {
+    Legacy,
+    Switchdev,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for SriovEswitchMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u16> for SriovEswitchMode {
+    fn from(d: u16) -> Self {
+        match d {
+            DEVLINK_ESWITCH_MODE_LEGACY => Self::Legacy,
+            DEVLINK_ESWITCH_MODE_SWITCHDEV => Self::Switchdev,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<SriovEswitchMode> for u16 {
+    fn from(v: SriovEswitchMode) -> u16 {
+        match v {
+            SriovEswitchMode::Legacy => DEVLINK_ESWITCH_MODE_LEGACY,
+            SriovEswitchMode::Switchdev => DEVLINK_ESWITCH_MODE_SWITCHDEV,
+            SriovEswitchMode::Other(d) => d,
+            SriovEswitchMode::Unknown => {
+                log::warn!(
+                    "Treating SriovEswitchMode::Unknown as \
+                    SriovEswitchMode::Legacy"
+                );
+                DEVLINK_ESWITCH_MODE_LEGACY
+            }
+        }
+    }
+}
+
+const DEVLINK_ESWITCH_MODE_LEGACY: u16 = 0;
+const DEVLINK_ESWITCH_MODE_SWITCHDEV: u16 = 1;
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct SriovInfo {
     pub vfs: Vec<VfInfo>,
+    /// E-switch mode of PF queried from devlink
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eswitch_mode: Option<SriovEswitchMode>,
+}
+
+/// The VF representor in switchdev mode
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct VfRepresentorInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pf_name: Option<String>,
+    pub pf_number: u32,
+    pub vf_id: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
@@ -84,6 +144,9 @@ pub struct VfInfo {
     pub ib_node_guid: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ib_port_guid: Option<String>,
+    /// Representor interface of this VF in switchdev mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub representor: Option<String>,
 }
 
 pub(crate) fn get_sriov_info(
@@ -209,6 +272,8 @@ fn read_folder(folder_path: &str) -> Vec<String> {
 pub(crate) fn sriov_vf_iface_tidy_up(
     iface_states: &mut HashMap<String, Iface>,
 ) {
+    vf_representor_tidy_up(iface_states);
+
     let mut vf_info_dict: HashMap<String, VfInfo> = HashMap::new();
 
     for iface in iface_states.values() {
@@ -226,3 +291,106 @@ pub(crate) fn sriov_vf_iface_tidy_up(
         }
     }
 }
+
+// The phys_port_name of VF representor is `pf<N>vf<M>` with optional
+// `c<N>` controller prefix, return the PF number and VF ID.
+pub(crate) fn parse_vf_representor_port_name(
+    port_name: &str,
+) -> Option<(u32, u32)> {
+    let mut name = port_name;
+    if let Some(rest) = name.strip_prefix('c') {
+        name = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    }
+    let (pf, vf) = name.strip_prefix("pf")?.split_once("vf")?;
+    Some((pf.parse().ok()?, vf.parse().ok()?))
+}
+
+// Link VF representor to its PF which sharing the same switch ID.
+fn vf_representor_tidy_up(iface_states: &mut HashMap<String, Iface>) {
+    let mut pfs: Vec<(&str, &str, Option<&str>)> = iface_states
+        .values()
+        .filter(|i| i.sriov.is_some())
+        .filter_map(|i| {
+            i.phys_switch_id.as_deref().map(|switch_id| {
+                (i.name.as_str(), switch_id, i.phys_port_name.as_deref())
+            })
+        })
+        .collect();
+    pfs.sort_unstable();
+
+    let mut rep_to_pf: Vec<(String, String, u32)> = Vec::new();
+    for iface in iface_states.values() {
+        if let (Some(rep), Some(switch_id)) = (
+            iface.sriov_vf_representor.as_ref(),
+            iface.phys_switch_id.as_ref(),
+        ) {
+            let uplink_port_name = format!("p{}", rep.pf_number);
+            let candidates: Vec<&(&str, &str, Option<&str>)> =
+                pfs.iter().filter(|(_, s, _)| s == switch_id).collect();
+            let pf_name = candidates
+                .iter()
+                .find(|(_, _, p)| *p == Some(uplink_port_name.as_str()))
+                .or_else(|| candidates.first())
+                .map(|(n, _, _)| n.to_string());
+            if let Some(pf_name) = pf_name {
+                rep_to_pf.push((iface.name.clone(), pf_name, rep.vf_id));
+            }
+        }
+    }
+
+    for (rep_name, pf_name, vf_id) in rep_to_pf {
+        if let Some(rep) = iface_states
+            .get_mut(&rep_name)
+            .and_then(|i| i.sriov_vf_representor.as_mut())
+        {
+            rep.pf_name = Some(pf_name.clone());
+        }
+        if let Some(vf_info) = iface_states
+            .get_mut(&pf_name)
+            .and_then(|i| i.sriov.as_mut())
+            .and_then(|s| s.vfs.iter_mut().find(|v| v.id == vf_id))
+        {
+            vf_info.representor = Some(rep_name);
+        }
+    }
+}
+
+pub(crate) fn fill_sriov_eswitch_mode(
+    iface_states: &mut HashMap<String, Iface>,
+) {
+    for iface in iface_states.values_mut() {
+        if let Some(sriov_info) = iface.sriov.as_mut() {
+            let (bus_name, dev_name) = match get_devlink_handle(&iface.name) {
+                Some(h) => h,
+                None => continue,
+            };
+            match devlink_get_eswitch_mode(&bus_name, &dev_name) {
+                Ok(mode) => sriov_info.eswitch_mode = mode.map(|m| m.into()),
+                Err(e) => log::debug!(
+                    "Failed to query e-switch mode of {}/{}: {}",
+                    bus_name,
+                    dev_name,
+                    e
+                ),
+            }
+        }
+    }
+}
+
+// The devlink handle is `<bus_name>/<dev_name>` of the parent device:
+// /sys/class/net/<iface_name>/device/subsystem and
+// /sys/class/net/<iface_name>/device
+pub(crate) fn get_devlink_handle(iface_name: &str) -> Option<(String, String)> {
+    let device_path = format!("/sys/class/net/{iface_name}/device");
+    let file_name = |path: &str| -> Option<String> {
+        std::fs::read_link(path)
+            .ok()?
+            .file_name()?
+            .to_str()
+            .map(|s| s.to_string())
+    };
+    Some((
+        file_name(&format!("{device_path}/subsystem"))?,
+        file_name(&device_path)?,
+    ))
+}
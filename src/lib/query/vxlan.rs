@@ -100,6 +100,8 @@ pub(crate) fn get_vxlan_info(
                 vxlan_info.gpe = d;
             } else if let InfoVxlan::Gbp(d) = *info {
                 vxlan_info.gbp = d;
+            } else if let InfoVxlan::RemCsumNoPartial(d) = *info {
+                vxlan_info.remote_check_sum_no_partial = d;
             } else if let InfoVxlan::TtlInherit(d) = *info {
                 vxlan_info.ttl_inherit = d;
             } else if let InfoVxlan::CollectMetadata(d) = *info {
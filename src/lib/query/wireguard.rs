@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{netlink::get_wireguard_info, Iface, IfaceType, NisporError};
+
+/// The private key of interface and preshared keys of peers are never
+/// included.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct WireguardInfo {
+    /// Base64 encoded public key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    pub listen_port: u16,
+    pub fwmark: u32,
+    pub peers: Vec<WireguardPeerInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct WireguardPeerInfo {
+    /// Base64 encoded public key
+    pub public_key: String,
+    /// In the format of `192.0.2.1:51820` or `[2001:db8::1]:51820`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// In the format of `198.51.100.0/24`
+    pub allowed_ips: Vec<String>,
+    /// Seconds since UNIX epoch, 0 means no handshake yet.
+    pub latest_handshake: u64,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    /// Persistent keepalive interval in seconds, 0 means disabled.
+    pub persistent_keepalive: u16,
+}
+
+pub(crate) fn fill_wireguard_info(
+    iface_states: &mut HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in iface_states.values_mut() {
+        if iface.iface_type == IfaceType::Wireguard {
+            iface.wireguard = Some(get_wireguard_info(iface.index)?);
+        }
+    }
+    Ok(())
+}